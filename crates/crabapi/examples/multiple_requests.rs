@@ -1,32 +1,25 @@
-use crabapi::core::requests::{Url, build_request, send_requests};
-use http::{HeaderMap, Method};
-use reqwest::{Body, Client};
-use tokio;
+use crabapi::core::requests::{BodySpec, Method, RequestSpec, Session, send_requests};
+use reqwest::Client;
 
 #[tokio::main]
 async fn main() -> Result<(), Box<dyn std::error::Error>> {
-    let client = Client::new();
+    let session = Session::new(Client::new());
 
     // send 10 requests
     let mut reqs = vec![];
     for i in 0..10 {
-        let mut headers = HeaderMap::new();
-        let query = vec![];
-        headers.insert("key", i.to_string().parse().unwrap());
-        reqs.push(build_request(
-            &client,
-            Url::parse("http://localhost:7878").unwrap(),
-            query,
-            Method::POST,
-            headers,
-            Body::from("POST Request"),
-        ));
+        let spec = RequestSpec::new(Method::POST, "http://localhost:7878")
+            .with_header("key", i.to_string())
+            .with_body(BodySpec::Text {
+                text: "POST Request".to_string(),
+            });
+        reqs.push(spec);
     }
 
-    let handles = send_requests(reqs);
+    let handles = send_requests(&session, reqs);
     for handle in handles {
-        let body = handle.await??.text().await?;
-        println!("body: {}", body.len());
+        let response = handle.await??;
+        println!("{}: {} bytes", response.status, response.size());
     }
 
     Ok(())
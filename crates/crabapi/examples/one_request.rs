@@ -1,26 +1,19 @@
-use crabapi::core::requests::{Url, build_request, send_requests};
-use http::{HeaderMap, Method};
-use reqwest::{Body, Client};
-use tokio;
+use crabapi::core::requests::{BodySpec, Method, RequestSpec, Session, send_requests};
+use reqwest::Client;
 
 #[tokio::main]
 async fn main() -> Result<(), Box<dyn std::error::Error>> {
-    let client = Client::new();
+    let session = Session::new(Client::new());
 
     // send 1 request
-    let req = build_request(
-        &client,
-        Url::parse("http://localhost:7878").unwrap(),
-        vec![],
-        Method::GET,
-        HeaderMap::new(),
-        Body::from("Get Request"),
-    );
-    let handles = send_requests(vec![req]);
+    let spec = RequestSpec::new(Method::GET, "http://localhost:7878").with_body(BodySpec::Text {
+        text: "Get Request".to_string(),
+    });
+    let handles = send_requests(&session, vec![spec]);
 
     for handle in handles {
-        let body = handle.await??.text().await?;
-        println!("body: {}", body.len());
+        let response = handle.await??;
+        println!("{}: {} bytes", response.status, response.size());
     }
 
     Ok(())
@@ -0,0 +1,139 @@
+use crate::core::bench::{Bench, Load, MAX_RATE};
+use crate::core::collections::Collection;
+use crate::core::requests::{Method, RequestSpec, Session, build_request};
+use clap::{Arg, ArgMatches, Command, value_parser};
+use reqwest::Client;
+use std::error::Error;
+use std::path::PathBuf;
+use std::time::Duration;
+
+pub const BENCH_COMMAND: &str = "bench";
+
+pub fn bench_command() -> Command {
+    Command::new(BENCH_COMMAND)
+        .about("Load test a saved request or a URL")
+        .arg(
+            Arg::new("target")
+                .required(true)
+                .help("Request path inside the collection, or a URL to GET"),
+        )
+        .arg(
+            Arg::new("concurrency")
+                .short('c')
+                .long("concurrency")
+                .value_parser(value_parser!(u64).range(1..))
+                .default_value("10")
+                .conflicts_with("rate")
+                .help("Number of requests in flight"),
+        )
+        .arg(
+            Arg::new("requests")
+                .short('n')
+                .long("requests")
+                .value_parser(value_parser!(usize))
+                .default_value("200")
+                .conflicts_with("rate")
+                .help("Total number of requests"),
+        )
+        .arg(
+            Arg::new("rate")
+                .long("rate")
+                .value_name("RPS")
+                .value_parser(positive_rate)
+                .requires("duration")
+                .help("Send at a fixed rate of requests per second instead"),
+        )
+        .arg(
+            Arg::new("duration")
+                .long("duration")
+                .value_name("DURATION")
+                .value_parser(duration)
+                .requires("rate")
+                .help("How long to send at --rate, e.g. 30s or 1m"),
+        )
+        .arg(
+            Arg::new("ramp-up")
+                .long("ramp-up")
+                .value_name("DURATION")
+                .value_parser(duration)
+                .help("Start the workers, or raise the rate, gradually over DURATION"),
+        )
+        .arg(
+            Arg::new("json")
+                .long("json")
+                .value_name("FILE")
+                .value_parser(value_parser!(PathBuf))
+                .help("Write the results as JSON to FILE"),
+        )
+}
+
+pub async fn run(client: &Client, matches: &ArgMatches) -> Result<(), Box<dyn Error>> {
+    let target = matches.get_one::<String>("target").unwrap();
+    let collection = Collection::open_default();
+    let request = if collection.exists(target) {
+        collection.load(target)?.request
+    } else {
+        RequestSpec::new(Method::GET, target)
+    };
+
+    let load = match matches.get_one::<f64>("rate") {
+        Some(rate) => Load::Rate {
+            rate: *rate,
+            duration: *matches.get_one::<Duration>("duration").unwrap(),
+        },
+        None => Load::Fixed {
+            concurrency: *matches.get_one::<u64>("concurrency").unwrap() as usize,
+            requests: *matches.get_one::<usize>("requests").unwrap(),
+        },
+    };
+    let ramp_up = matches
+        .get_one::<Duration>("ramp-up")
+        .copied()
+        .unwrap_or_default();
+
+    // Every request would otherwise be validated against --spec and recorded to the history.
+    let (name, variables) = super::environments::variables(matches, None)?;
+    let session = Session::new(client.clone()).with_environment(name, variables);
+    // Fail early on requests that cannot be built, instead of once per request.
+    let _ = build_request(session.client(), &request, session.variables())?;
+
+    println!("Benchmarking {} {}...\n", request.method, request.url);
+    let report = Bench::new(session, request, load)
+        .with_ramp_up(ramp_up)
+        .run()
+        .await;
+    print!("{report}");
+
+    if let Some(file) = matches.get_one::<PathBuf>("json") {
+        std::fs::write(file, report.to_json()?)
+            .map_err(|error| format!("{}: {error}", file.display()))?;
+    }
+    Ok(())
+}
+
+fn duration(value: &str) -> Result<Duration, humantime::DurationError> {
+    humantime::parse_duration(value)
+}
+
+fn positive_rate(value: &str) -> Result<f64, String> {
+    match value.parse::<f64>() {
+        Ok(rate) if rate > 0.0 && rate <= MAX_RATE => Ok(rate),
+        _ => Err(format!(
+            "expected a positive number of requests per second, at most {MAX_RATE}"
+        )),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn rate_is_positive_and_bounded() {
+        assert_eq!(positive_rate("2.5"), Ok(2.5));
+        assert_eq!(positive_rate("1e6"), Ok(MAX_RATE));
+        for rate in ["0", "-1", "1e10", "inf", "NaN", "fast"] {
+            assert!(positive_rate(rate).is_err(), "{rate}");
+        }
+    }
+}
@@ -0,0 +1,25 @@
+use crate::core::collections::{Collection, SavedRequest};
+use crate::core::scripts::Hooks;
+use clap::{Arg, ArgMatches, Command};
+use std::error::Error;
+
+pub const RUN_COMMAND: &str = "run";
+
+pub fn run_command() -> Command {
+    Command::new(RUN_COMMAND)
+        .about("Run a request saved in the collection")
+        .arg(
+            Arg::new("path")
+                .help("Request path inside the collection (e.g. users/get-by-id)")
+                .required(true),
+        )
+}
+
+/// The request at the `path` argument and the scripts around it.
+pub fn saved_request(matches: &ArgMatches) -> Result<(SavedRequest, Hooks), Box<dyn Error>> {
+    let path = matches.get_one::<String>("path").unwrap();
+    let collection = Collection::open_default();
+    let saved = collection.load(path)?;
+    let hooks = collection.hooks(path, &saved)?;
+    Ok((saved, hooks))
+}
@@ -0,0 +1,126 @@
+use crate::core::environments::{Environments, Variables};
+use crate::core::requests::Session;
+use clap::{Arg, ArgAction, ArgMatches, Command};
+use reqwest::Client;
+use std::error::Error;
+
+pub const ENV_COMMAND: &str = "env";
+
+pub fn env_arg() -> Arg {
+    Arg::new("env")
+        .short('e')
+        .long("env")
+        .value_name("ENVIRONMENT")
+        .global(true)
+        .help("Environment used to resolve {{variables}} (defaults to the active one)")
+}
+
+pub fn var_arg() -> Arg {
+    Arg::new("var")
+        .long("var")
+        .value_name("KEY=VALUE")
+        .action(ArgAction::Append)
+        .global(true)
+        .help("Set or override a variable")
+}
+
+pub fn env_command() -> Command {
+    Command::new(ENV_COMMAND)
+        .about("Manage environments")
+        .subcommand_required(true)
+        .subcommand(Command::new("list").about("List environments and their variables"))
+        .subcommand(
+            Command::new("use")
+                .about("Set the active environment")
+                .arg(Arg::new("name").help("Environment name, omit to clear")),
+        )
+        .subcommand(
+            Command::new("set")
+                .about("Set variables in an environment, creating it if needed")
+                .arg(Arg::new("name").required(true).help("Environment name"))
+                .arg(
+                    Arg::new("vars")
+                        .value_name("KEY=VALUE")
+                        .action(ArgAction::Append)
+                        .required(true),
+                ),
+        )
+}
+
+/// Session using the variables from `--env` (or `environment`, or the active environment),
+/// overridden by `--var`, and validating responses against `--spec`.
+pub fn session(
+    client: &Client,
+    matches: &ArgMatches,
+    environment: Option<String>,
+) -> Result<Session, Box<dyn Error>> {
+    let (name, variables) = variables(matches, environment)?;
+    let session = Session::new(client.clone()).with_environment(name, variables);
+    super::openapi::with_spec(session, matches)
+}
+
+/// The environment name and variables used by [`session`].
+pub fn variables(
+    matches: &ArgMatches,
+    environment: Option<String>,
+) -> Result<(Option<String>, Variables), Box<dyn Error>> {
+    let environments = Environments::load_default()?;
+    let name = matches
+        .get_one::<String>("env")
+        .cloned()
+        .or(environment)
+        .or_else(|| environments.active.clone());
+    let mut variables = environments.variables(name.as_deref())?;
+
+    if let Some(values) = matches.get_many::<String>("var") {
+        for value in values {
+            let (key, value) = split_var(value)?;
+            variables.insert(key, value);
+        }
+    }
+
+    Ok((name, variables))
+}
+
+pub fn run(matches: &ArgMatches) -> Result<(), Box<dyn Error>> {
+    let mut environments = Environments::load_default()?;
+
+    match matches.subcommand() {
+        Some(("use", matches)) => {
+            let name = matches.get_one::<String>("name").map(String::as_str);
+            environments.set_active(name)?;
+            environments.save_default()?;
+        }
+        Some(("set", matches)) => {
+            let name = matches.get_one::<String>("name").unwrap();
+            let environment = environments.get_or_insert(name);
+            for value in matches.get_many::<String>("vars").unwrap() {
+                let (key, value) = split_var(value)?;
+                environment.set(key, value);
+            }
+            environments.save_default()?;
+        }
+        _ => {
+            for environment in &environments.environments {
+                let marker = if environments.active.as_ref() == Some(&environment.name) {
+                    " (active)"
+                } else {
+                    ""
+                };
+                println!("{}{marker}", environment.name);
+                for variable in &environment.variables {
+                    let disabled = if variable.enabled { "" } else { " (disabled)" };
+                    println!("\t{} = {}{disabled}", variable.key, variable.value);
+                }
+            }
+        }
+    }
+
+    Ok(())
+}
+
+fn split_var(value: &str) -> Result<(&str, &str), Box<dyn Error>> {
+    value
+        .split_once('=')
+        .ok_or_else(|| format!("invalid variable '{value}', expected 'key=value'").into())
+}
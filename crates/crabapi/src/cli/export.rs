@@ -0,0 +1,46 @@
+use crate::core::codegen::{self, Format};
+use crate::core::collections::Collection;
+use clap::{Arg, ArgAction, ArgMatches, Command, builder::PossibleValuesParser};
+use std::error::Error;
+
+pub const EXPORT_COMMAND: &str = "export";
+
+pub fn export_command() -> Command {
+    Command::new(EXPORT_COMMAND)
+        .about("Print a saved request as a code snippet")
+        .arg(
+            Arg::new("path")
+                .help("Request path inside the collection (e.g. users/get-by-id)")
+                .required(true),
+        )
+        .arg(
+            Arg::new("format")
+                .short('f')
+                .long("format")
+                .value_parser(PossibleValuesParser::new(
+                    Format::ALL.iter().map(|format| format.name()),
+                ))
+                .default_value(Format::Curl.name())
+                .help("Snippet language or tool"),
+        )
+        .arg(
+            Arg::new("raw")
+                .long("raw")
+                .action(ArgAction::SetTrue)
+                .help("Flag: Keep {{variables}} instead of resolving them"),
+        )
+}
+
+pub fn run(matches: &ArgMatches) -> Result<(), Box<dyn Error>> {
+    let path = matches.get_one::<String>("path").unwrap();
+    let format: Format = matches.get_one::<String>("format").unwrap().parse()?;
+
+    let mut request = Collection::open_default().load(path)?.request;
+    if !matches.get_flag("raw") {
+        let (_, variables) = super::environments::variables(matches, None)?;
+        request = request.resolve(&variables)?;
+    }
+
+    println!("{}", codegen::render(&request, format));
+    Ok(())
+}
@@ -0,0 +1,79 @@
+use crate::core::history::{History, HistoryEntry};
+use clap::{Arg, ArgMatches, Command, value_parser};
+use std::error::Error;
+
+pub const HISTORY_COMMAND: &str = "history";
+
+pub fn history_command() -> Command {
+    let id_arg = Arg::new("id")
+        .required(true)
+        .value_parser(value_parser!(u64))
+        .help("History entry id");
+
+    Command::new(HISTORY_COMMAND)
+        .about("List, inspect and replay sent requests")
+        .subcommand_required(true)
+        .subcommand(
+            Command::new("list").about("List sent requests").arg(
+                Arg::new("limit")
+                    .short('n')
+                    .long("limit")
+                    .value_parser(value_parser!(usize))
+                    .default_value("20")
+                    .help("Number of most recent entries to show"),
+            ),
+        )
+        .subcommand(
+            Command::new("show")
+                .about("Show a sent request and its response summary")
+                .arg(id_arg.clone()),
+        )
+        .subcommand(
+            Command::new("replay")
+                .about("Send a request from the history again")
+                .arg(id_arg),
+        )
+        .subcommand(Command::new("clear").about("Delete the whole history"))
+}
+
+/// Runs the history subcommand. `replay` returns the entry to send again.
+pub fn run(matches: &ArgMatches) -> Result<Option<HistoryEntry>, Box<dyn Error>> {
+    let history = History::open_default();
+
+    match matches.subcommand() {
+        Some(("show", matches)) => {
+            let entry = history.get(*matches.get_one::<u64>("id").unwrap())?;
+            println!("{}", summary(&entry));
+            if let Some(environment) = &entry.environment {
+                println!("Environment: {environment}");
+            }
+            println!("{}", serde_json::to_string_pretty(&entry.request)?);
+        }
+        Some(("replay", matches)) => {
+            return Ok(Some(history.get(*matches.get_one::<u64>("id").unwrap())?));
+        }
+        Some(("clear", _)) => history.clear()?,
+        Some(("list", matches)) => {
+            let limit = *matches.get_one::<usize>("limit").unwrap();
+            let entries = history.entries()?;
+            for entry in entries.iter().rev().take(limit).rev() {
+                println!("{}", summary(entry));
+            }
+        }
+        _ => {}
+    }
+
+    Ok(None)
+}
+
+fn summary(entry: &HistoryEntry) -> String {
+    format!(
+        "{}\t{}\t{} {}\t{}\t{} ms",
+        entry.id,
+        entry.time_string(),
+        entry.request.method,
+        entry.request.url,
+        entry.outcome,
+        entry.duration_ms
+    )
+}
@@ -0,0 +1,121 @@
+use crate::core::collections::{Collection, SavedRequest};
+use crate::core::environments::Environments;
+use crate::core::import::{self, Import};
+use clap::{Arg, ArgMatches, Command};
+use std::error::Error;
+use std::path::{Path, PathBuf};
+
+pub const IMPORT_COMMAND: &str = "import";
+
+pub fn import_command() -> Command {
+    let save_arg = Arg::new("save")
+        .long("save")
+        .value_name("PATH")
+        .help("Save the imported request in the collection under PATH");
+
+    Command::new(IMPORT_COMMAND)
+        .about("Import requests written for other tools")
+        .subcommand_required(true)
+        .subcommand(
+            Command::new("curl")
+                .about("Import a curl command line")
+                .arg(
+                    Arg::new("command")
+                        .required(true)
+                        .help("The curl command, quoted as a single argument"),
+                )
+                .arg(save_arg),
+        )
+        .subcommand(file_command(
+            "postman",
+            "Import a Postman v2.1 collection or environment export",
+        ))
+        .subcommand(file_command(
+            "insomnia",
+            "Import an Insomnia v4 export (workspaces, folders, requests, environments)",
+        ))
+        .subcommand(file_command(
+            "har",
+            "Import the entries of a HAR capture, keeping the recorded responses",
+        ))
+        .subcommand(file_command(
+            "openapi",
+            "Generate one request per operation of an OpenAPI 3 or Swagger 2 spec (JSON or YAML)",
+        ))
+}
+
+fn file_command(name: &'static str, about: &'static str) -> Command {
+    Command::new(name)
+        .about(about)
+        .arg(
+            Arg::new("file")
+                .required(true)
+                .value_parser(clap::value_parser!(PathBuf))
+                .help("The file to import"),
+        )
+        .arg(Arg::new("folder").long("folder").value_name("PATH").help(
+            "Collection folder for the imported requests [default: the collection or file name]",
+        ))
+}
+
+pub fn run(matches: &ArgMatches) -> Result<(), Box<dyn Error>> {
+    let (request, matches) = match matches.subcommand() {
+        Some(("curl", matches)) => {
+            let command = matches.get_one::<String>("command").unwrap();
+            (import::curl::parse(command)?, matches)
+        }
+        Some((format @ ("postman" | "insomnia" | "har" | "openapi"), matches)) => {
+            let file = matches.get_one::<PathBuf>("file").unwrap();
+            let imported = match format {
+                "postman" => import::postman::parse_file(file)?,
+                "insomnia" => import::insomnia::parse_file(file)?,
+                "openapi" => import::openapi::parse_file(file)?,
+                _ => import::har::parse_file(file)?,
+            };
+            return save_import(imported, file, matches);
+        }
+        _ => return Ok(()),
+    };
+
+    let request = SavedRequest::new(request);
+    match matches.get_one::<String>("save") {
+        Some(path) => {
+            Collection::open_default().save(path, &request)?;
+            println!("Saved request as '{path}'");
+        }
+        None => println!("{}", serde_json::to_string_pretty(&request)?),
+    }
+    Ok(())
+}
+
+/// Saves the requests and environments of a collection import and reports what was skipped.
+fn save_import(import: Import, file: &Path, matches: &ArgMatches) -> Result<(), Box<dyn Error>> {
+    let folder = matches
+        .get_one::<String>("folder")
+        .cloned()
+        .unwrap_or_else(|| import.default_folder(file));
+
+    let collection = Collection::open_default();
+    let paths = import.save_requests(&collection, &folder)?;
+    for path in &paths {
+        println!("Saved request as '{path}'");
+    }
+
+    if !import.environments.is_empty() {
+        let mut environments = Environments::load_default()?;
+        import.merge_environments(&mut environments);
+        environments.save_default()?;
+        for environment in &import.environments {
+            println!(
+                "Imported {} variables into environment '{}'",
+                environment.variables.len(),
+                environment.name
+            );
+        }
+    }
+
+    for warning in &import.warnings {
+        eprintln!("Warning: {warning}");
+    }
+    Ok(())
+}
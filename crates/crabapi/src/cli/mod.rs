@@ -1,12 +1,33 @@
+mod bench;
+mod collections;
+mod environments;
+mod export;
+mod history;
+mod import;
+mod openapi;
+mod run_collection;
+mod scripts;
+mod test;
+
 use crate::core::app::constants;
+use crate::core::assertions;
+use crate::core::auth::Auth;
+use crate::core::collections::{Collection, SavedRequest};
+use crate::core::history::History;
+use crate::core::import::curl;
+use crate::core::jsonpath::JsonPath;
 use crate::core::requests::{
-    Url, build_request, constants as requests_constants, print_response, send_requests,
+    BodySpec, Error as RequestError, KeyValue, RequestSpec, Session,
+    constants as requests_constants, print_response, send_requests, wait_response,
 };
-use clap::{Arg, ArgAction, Command};
+use crate::core::scripts::Hooks;
+use clap::{Arg, ArgAction, ArgMatches, Command};
 use const_format::formatcp;
-use http::{HeaderMap, HeaderName, HeaderValue, Method};
-use reqwest::{Body, Client};
+use http::Method;
+use reqwest::Client;
 use std::error::Error;
+use std::io::IsTerminal;
+use std::str::FromStr;
 
 pub struct Cli {
     client: Client,
@@ -16,6 +37,15 @@ pub struct Cli {
     headers_arg: Arg,
     gui_arg: Arg,
     body_arg: Arg,
+    multipart_arg: Arg,
+    form_arg: Arg,
+    save_arg: Arg,
+    user_arg: Arg,
+    digest_arg: Arg,
+    bearer_arg: Arg,
+    assert_arg: Arg,
+    extract_arg: Arg,
+    filter_arg: Arg,
 }
 
 impl Default for Cli {
@@ -63,6 +93,53 @@ impl Cli {
                 .long("data")
                 .value_name("BODY")
                 .help("Request body (For POST, PUT, PATCH request)"),
+            multipart_arg: Arg::new("multipart")
+                .short('F')
+                .long("multipart")
+                .value_name("NAME=VALUE")
+                .action(ArgAction::Append)
+                .conflicts_with("body")
+                .help("Multipart form field like curl's -F: 'name=value', 'file=@path' to attach a file, optionally followed by ';type=image/png' and ';filename=name.png'"),
+            form_arg: Arg::new("form")
+                .long("form")
+                .value_name("KEY=VALUE")
+                .action(ArgAction::Append)
+                .conflicts_with_all(["body", "multipart"])
+                .help("URL-encoded form field, sent as application/x-www-form-urlencoded unless a Content-Type header is given"),
+            save_arg: Arg::new("save")
+                .long("save")
+                .value_name("PATH")
+                .help("Save the request in the collection under PATH (e.g. users/get-by-id)"),
+            user_arg: Arg::new("user")
+                .short('u')
+                .long("user")
+                .value_name("USER:PASSWORD")
+                .conflicts_with("bearer")
+                .help("Basic auth credentials (Digest with --digest)"),
+            digest_arg: Arg::new("digest")
+                .long("digest")
+                .action(ArgAction::SetTrue)
+                .requires("user")
+                .help("Flag: Use HTTP Digest auth with the --user credentials"),
+            bearer_arg: Arg::new("bearer")
+                .long("bearer")
+                .value_name("TOKEN")
+                .help("Bearer token sent in the Authorization header"),
+            assert_arg: Arg::new("assert")
+                .long("assert")
+                .value_name("ASSERTION")
+                .action(ArgAction::Append)
+                .help("Check the response, e.g. 'status == 200' or '$.id is integer' (saved with --save)"),
+            extract_arg: Arg::new("extract")
+                .long("extract")
+                .value_name("NAME = SOURCE")
+                .action(ArgAction::Append)
+                .help("Set a variable from the response for the next requests of run-collection, e.g. 'token = $.access_token' (saved with --save)"),
+            filter_arg: Arg::new("filter")
+                .long("filter")
+                .value_name("JSONPATH")
+                .global(true)
+                .help("Print only the parts of a JSON body the path selects, e.g. '$.items[*].id' or '.items[].id'"),
         }
     }
 
@@ -81,6 +158,30 @@ impl Cli {
             .arg(self.headers_arg)
             .arg(self.gui_arg)
             .arg(self.body_arg)
+            .arg(self.multipart_arg)
+            .arg(self.form_arg)
+            .arg(self.save_arg)
+            .arg(self.user_arg)
+            .arg(self.digest_arg)
+            .arg(self.bearer_arg)
+            .arg(self.assert_arg)
+            .arg(self.extract_arg)
+            .arg(self.filter_arg)
+            .arg(environments::env_arg())
+            .arg(environments::var_arg())
+            .arg(openapi::spec_arg())
+            .args(scripts::script_args())
+            .subcommand(collections::run_command())
+            .subcommand(environments::env_command())
+            .subcommand(history::history_command())
+            .subcommand(import::import_command())
+            .subcommand(export::export_command())
+            .subcommand(test::test_command())
+            .subcommand(run_collection::run_collection_command())
+            .subcommand(bench::bench_command())
+            .subcommand(scripts::scripts_command())
+            .args_conflicts_with_subcommands(true)
+            .subcommand_negates_reqs(true)
             .get_matches();
 
         if matches.get_flag("gui") {
@@ -88,51 +189,207 @@ impl Cli {
             return Ok(());
         }
 
-        let mut query = vec![];
-        if let Some(query_values) = matches.get_many::<String>("query") {
-            for query_value in query_values {
-                if let Some((key, value)) = query_value.split_once(": ") {
-                    query.push((key.to_string(), value.to_string()));
+        let (saved, hooks, session) = match matches.subcommand() {
+            Some((environments::ENV_COMMAND, matches)) => return environments::run(matches),
+            Some((scripts::SCRIPTS_COMMAND, matches)) => return scripts::run(matches),
+            Some((import::IMPORT_COMMAND, matches)) => return import::run(matches),
+            Some((export::EXPORT_COMMAND, matches)) => return export::run(matches),
+            Some((test::TEST_COMMAND, matches)) => return test::run(&self.client, matches).await,
+            Some((bench::BENCH_COMMAND, matches)) => {
+                return bench::run(&self.client, matches).await;
+            }
+            Some((run_collection::RUN_COLLECTION_COMMAND, matches)) => {
+                return run_collection::run(&self.client, matches).await;
+            }
+            Some((collections::RUN_COMMAND, matches)) => {
+                let (saved, hooks) = collections::saved_request(matches)?;
+                let session = environments::session(&self.client, matches, None)?;
+                (saved, hooks, session)
+            }
+            // History keeps requests as they were sent, after their scripts ran.
+            Some((history::HISTORY_COMMAND, matches)) => match history::run(matches)? {
+                Some(entry) => {
+                    let session = environments::session(&self.client, matches, entry.environment)?;
+                    (SavedRequest::new(entry.request), Hooks::new(), session)
+                }
+                None => return Ok(()),
+            },
+            _ => {
+                let saved = SavedRequest::new(Self::request_spec(&matches)?)
+                    .with_assertions(Self::parse_all(&matches, "assert")?)
+                    .with_extract(Self::parse_all(&matches, "extract")?)
+                    .with_scripts(scripts::scripts(&matches)?);
+                if let Some(path) = matches.get_one::<String>("save") {
+                    Collection::open_default().save(path, &saved)?;
+                    println!("Saved request as '{path}'\n");
+                }
+                let hooks = Hooks::new().with_request(&saved.scripts);
+                (
+                    saved,
+                    hooks,
+                    environments::session(&self.client, &matches, None)?,
+                )
+            }
+        };
+
+        // Global arguments reach the innermost subcommand, e.g. `history replay`.
+        let mut innermost = &matches;
+        while let Some((_, matches)) = innermost.subcommand() {
+            innermost = matches;
+        }
+        let filter = innermost
+            .get_one::<String>("filter")
+            .map(|filter| JsonPath::parse(filter))
+            .transpose()?;
+        Self::send(
+            session.with_history(History::open_default()),
+            saved,
+            hooks,
+            filter,
+        )
+        .await
+    }
+
+    async fn send(
+        mut session: Session,
+        saved: SavedRequest,
+        hooks: Hooks,
+        filter: Option<JsonPath>,
+    ) -> Result<(), Box<dyn Error>> {
+        let mut request = saved.request.clone();
+        let outcome = hooks.pre_request(&mut session, &mut request)?;
+        scripts::print_outcome(&outcome);
+        outcome.save_environment(session.environment())?;
+        println!(
+            "Send request: {:?}\n",
+            request.resolve(session.variables())?
+        );
+
+        let handles = send_requests(&session, vec![request.clone()]);
+        for handle in handles {
+            let response = wait_response(handle).await?;
+            print_response(&response, std::io::stdout().is_terminal(), filter.as_ref());
+            if !saved.assertions.is_empty() {
+                println!("\nAssertions:");
+                let resolved: Vec<_> = saved
+                    .assertions
+                    .iter()
+                    .map(|assertion| assertion.resolve(session.variables()))
+                    .collect();
+                for result in assertions::check(&resolved, &response) {
+                    let label = if result.passed { "PASS" } else { "FAIL" };
+                    println!("\t{label} {result}");
+                }
+            }
+            for extraction in &saved.extract {
+                match extraction.extract(&response) {
+                    Ok(value) => println!("Extracted {} = {value}", extraction.name),
+                    Err(reason) => println!("Could not extract {}: {reason}", extraction.name),
                 }
             }
+            if !hooks.is_empty() {
+                let sent = request.resolve(session.variables())?;
+                let outcome = hooks.post_response(&mut session, &sent, &response)?;
+                if !outcome.logs.is_empty() || !outcome.tests.is_empty() {
+                    println!("\nScripts:");
+                    scripts::print_outcome(&outcome);
+                }
+                outcome.save_environment(session.environment())?;
+            }
+        }
+        Ok(())
+    }
+
+    /// Parses every value of a repeatable argument, such as `--assert`.
+    fn parse_all<T>(matches: &ArgMatches, id: &str) -> Result<Vec<T>, Box<dyn Error>>
+    where
+        T: FromStr,
+        T::Err: Error + 'static,
+    {
+        let mut parsed = vec![];
+        for expression in matches.get_many::<String>(id).into_iter().flatten() {
+            parsed.push(expression.parse()?);
         }
+        Ok(parsed)
+    }
 
+    fn request_spec(matches: &ArgMatches) -> Result<RequestSpec, Box<dyn Error>> {
         let method = matches
             .get_one::<String>("method")
             .unwrap()
             .parse::<Method>()?;
         let url = matches.get_one::<String>("url").unwrap();
-        let url = Url::parse(url).unwrap();
+        let mut request = RequestSpec::new(method, url);
+
+        if let Some(query_values) = matches.get_many::<String>("query") {
+            for query_value in query_values {
+                let Some((key, value)) = query_value.split_once(": ") else {
+                    return Err(RequestError::InvalidQuery {
+                        query: query_value.clone(),
+                        reason: "expected 'key: value'",
+                    }
+                    .into());
+                };
+                request.query.push(KeyValue::new(key, value));
+            }
+        }
 
-        let mut headers = HeaderMap::new();
         if let Some(header_values) = matches.get_many::<String>("headers") {
             for header_value in header_values {
-                if let Some((key, value)) = header_value.split_once(": ") {
-                    headers.insert(
-                        HeaderName::from_bytes(key.trim().as_bytes())?,
-                        HeaderValue::from_str(value.trim())?,
-                    );
-                }
+                let Some((key, value)) = header_value.split_once(':') else {
+                    return Err(RequestError::InvalidHeader {
+                        header: header_value.clone(),
+                        reason: "expected 'Name: value'",
+                    }
+                    .into());
+                };
+                request
+                    .headers
+                    .push(KeyValue::new(key.trim(), value.trim()));
             }
         }
-        let default_body = String::from("");
-        let body = matches.get_one::<String>("body").unwrap_or(&default_body);
-
-        let request = build_request(
-            &self.client,
-            url,
-            query,
-            method,
-            headers,
-            Body::from(body.to_string()),
-        );
 
-        println!("Send request: {:?}\n", request);
+        if let Some(body) = matches.get_one::<String>("body") {
+            request.body = BodySpec::Text { text: body.clone() };
+        }
 
-        let handles = send_requests(vec![request]);
-        for handle in handles {
-            print_response(handle).await?;
+        if let Some(fields) = matches.get_many::<String>("multipart") {
+            let mut parts = vec![];
+            for field in fields {
+                parts.push(curl::form_part(field)?);
+            }
+            request.body = BodySpec::Multipart { parts };
         }
-        Ok(())
+
+        if let Some(fields) = matches.get_many::<String>("form") {
+            let mut form = vec![];
+            for field in fields {
+                let Some((key, value)) = field.split_once('=') else {
+                    return Err(RequestError::InvalidFormField {
+                        field: field.clone(),
+                        reason: "expected 'key=value'",
+                    }
+                    .into());
+                };
+                form.push(KeyValue::new(key, value));
+            }
+            request.body = BodySpec::Form { fields: form };
+        }
+
+        if let Some(token) = matches.get_one::<String>("bearer") {
+            request.auth = Auth::Bearer {
+                token: token.clone(),
+            };
+        } else if let Some(user) = matches.get_one::<String>("user") {
+            let (username, password) = user.split_once(':').unwrap_or((user, ""));
+            let (username, password) = (username.to_string(), password.to_string());
+            request.auth = if matches.get_flag("digest") {
+                Auth::Digest { username, password }
+            } else {
+                Auth::Basic { username, password }
+            };
+        }
+
+        Ok(request)
     }
 }
@@ -0,0 +1,22 @@
+use crate::core::openapi::OpenApi;
+use crate::core::requests::Session;
+use clap::{Arg, ArgMatches};
+use std::error::Error;
+use std::path::PathBuf;
+
+pub fn spec_arg() -> Arg {
+    Arg::new("spec")
+        .long("spec")
+        .value_name("FILE")
+        .value_parser(clap::value_parser!(PathBuf))
+        .global(true)
+        .help("OpenAPI 3 or Swagger 2 spec (JSON or YAML) to validate responses against")
+}
+
+/// Loads the spec given with `--spec`, if any, into the session.
+pub fn with_spec(session: Session, matches: &ArgMatches) -> Result<Session, Box<dyn Error>> {
+    match matches.get_one::<PathBuf>("spec") {
+        Some(path) => Ok(session.with_spec(OpenApi::load(path)?)),
+        None => Ok(session),
+    }
+}
@@ -0,0 +1,50 @@
+use super::test;
+use crate::core::collections::Collection;
+use crate::core::runner::{self, Runner};
+use clap::{Arg, ArgAction, ArgMatches, Command, value_parser};
+use reqwest::Client;
+use std::error::Error;
+use std::path::PathBuf;
+
+pub const RUN_COLLECTION_COMMAND: &str = "run-collection";
+
+pub fn run_collection_command() -> Command {
+    Command::new(RUN_COLLECTION_COMMAND)
+        .about("Run the requests of a folder in order, passing extracted values along")
+        .arg(test::path_arg())
+        .arg(
+            Arg::new("data")
+                .long("data")
+                .value_name("FILE")
+                .value_parser(value_parser!(PathBuf))
+                .help("CSV or JSON file with one set of variables per iteration"),
+        )
+        .arg(
+            Arg::new("bail")
+                .long("bail")
+                .action(ArgAction::SetTrue)
+                .help("Flag: Stop at the first failed request"),
+        )
+        .args(test::report_args())
+}
+
+pub async fn run(client: &Client, matches: &ArgMatches) -> Result<(), Box<dyn Error>> {
+    let (name, requests) = test::saved_requests(matches)?;
+    let iterations = match matches.get_one::<PathBuf>("data") {
+        Some(file) => runner::load_data(file)?,
+        None => vec![],
+    };
+    let session = super::environments::session(client, matches, None)?;
+    let runner = Runner::new(session)
+        .with_collection(Collection::open_default())
+        .with_iterations(iterations)
+        .with_bail(matches.get_flag("bail"));
+
+    let report = runner.run(&name, &requests, |case| print!("{case}")).await;
+    let expected = requests.len() * runner.iterations().max(1);
+    if report.cases.len() < expected {
+        println!("Stopped at the first failure");
+    }
+    println!("\n{}", report.summary());
+    test::finish(&report, matches)
+}
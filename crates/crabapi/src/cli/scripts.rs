@@ -0,0 +1,99 @@
+use crate::core::collections::Collection;
+use crate::core::scripts::{Outcome, Scripts};
+use clap::{Arg, ArgMatches, Command, value_parser};
+use std::error::Error;
+use std::fs;
+use std::path::PathBuf;
+
+pub const SCRIPTS_COMMAND: &str = "scripts";
+
+pub fn scripts_command() -> Command {
+    Command::new(SCRIPTS_COMMAND)
+        .about("Show or replace the scripts of a saved request or folder")
+        .arg(
+            Arg::new("path")
+                .help("Request or folder inside the collection, defaults to the whole collection"),
+        )
+        .args(script_args())
+}
+
+/// `--pre-script FILE` and `--post-script FILE`, holding Rhai source.
+pub fn script_args() -> [Arg; 2] {
+    [
+        Arg::new("pre_script")
+            .long("pre-script")
+            .value_name("FILE")
+            .value_parser(value_parser!(PathBuf))
+            .help("Rhai script run before the request is sent, e.g. to sign it"),
+        Arg::new("post_script")
+            .long("post-script")
+            .value_name("FILE")
+            .value_parser(value_parser!(PathBuf))
+            .help("Rhai script run on the response, e.g. to set variables or test it"),
+    ]
+}
+
+/// Prints the scripts at `path`, or replaces those given; an empty file removes one.
+pub fn run(matches: &ArgMatches) -> Result<(), Box<dyn Error>> {
+    let collection = Collection::open_default();
+    let path = matches
+        .get_one::<String>("path")
+        .map(String::as_str)
+        .unwrap_or_default();
+    let mut scripts = collection.scripts(path)?;
+
+    if !has_script_args(matches) {
+        print_script("Pre-request", &scripts.pre_request);
+        print_script("Post-response", &scripts.post_response);
+        return Ok(());
+    }
+    if let Some(source) = read(matches, "pre_script")? {
+        scripts.pre_request = source;
+    }
+    if let Some(source) = read(matches, "post_script")? {
+        scripts.post_response = source;
+    }
+    collection.save_scripts(path, &scripts)?;
+    println!("Saved the scripts of '{path}'");
+    Ok(())
+}
+
+/// The scripts given with [`script_args`]; missing ones are empty.
+pub fn scripts(matches: &ArgMatches) -> Result<Scripts, Box<dyn Error>> {
+    Ok(Scripts {
+        pre_request: read(matches, "pre_script")?.unwrap_or_default(),
+        post_response: read(matches, "post_script")?.unwrap_or_default(),
+    })
+}
+
+/// Prints what scripts printed, then their tests.
+pub fn print_outcome(outcome: &Outcome) {
+    for line in &outcome.logs {
+        println!("> {line}");
+    }
+    for result in &outcome.tests {
+        let label = if result.passed { "PASS" } else { "FAIL" };
+        println!("\t{label} {result}");
+    }
+}
+
+fn has_script_args(matches: &ArgMatches) -> bool {
+    matches.contains_id("pre_script") || matches.contains_id("post_script")
+}
+
+fn read(matches: &ArgMatches, id: &str) -> Result<Option<String>, Box<dyn Error>> {
+    match matches.get_one::<PathBuf>(id) {
+        Some(file) => fs::read_to_string(file)
+            .map(Some)
+            .map_err(|error| format!("{}: {error}", file.display()).into()),
+        None => Ok(None),
+    }
+}
+
+fn print_script(title: &str, source: &str) {
+    if source.trim().is_empty() {
+        println!("{title} script: none");
+    } else {
+        println!("{title} script:\n{}", source.trim_end());
+    }
+}
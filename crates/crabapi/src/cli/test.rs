@@ -0,0 +1,97 @@
+use crate::core::assertions::Report;
+use crate::core::collections::{Collection, SavedRequest};
+use crate::core::runner::Runner;
+use clap::{Arg, ArgMatches, Command, value_parser};
+use reqwest::Client;
+use std::error::Error;
+use std::path::PathBuf;
+
+pub const TEST_COMMAND: &str = "test";
+
+/// Saved requests with their path in the collection.
+type SavedRequests = Vec<(String, SavedRequest)>;
+
+pub fn test_command() -> Command {
+    Command::new(TEST_COMMAND)
+        .about("Send saved requests one after the other and check their assertions")
+        .arg(path_arg())
+        .args(report_args())
+}
+
+/// Prints a summary and fails when any request got no response or failed an assertion.
+pub async fn run(client: &Client, matches: &ArgMatches) -> Result<(), Box<dyn Error>> {
+    let (name, requests) = saved_requests(matches)?;
+    let session = super::environments::session(client, matches, None)?;
+    let report = Runner::new(session)
+        .with_collection(Collection::open_default())
+        .run(&name, &requests, |_| {})
+        .await;
+    print!("{report}");
+    finish(&report, matches)
+}
+
+pub fn path_arg() -> Arg {
+    Arg::new("path")
+        .help("Folder or request inside the collection, defaults to the whole collection")
+}
+
+pub fn report_args() -> [Arg; 2] {
+    [
+        Arg::new("junit")
+            .long("junit")
+            .value_name("FILE")
+            .value_parser(value_parser!(PathBuf))
+            .help("Write a JUnit XML report to FILE"),
+        Arg::new("json")
+            .long("json")
+            .value_name("FILE")
+            .value_parser(value_parser!(PathBuf))
+            .help("Write a JSON report to FILE"),
+    ]
+}
+
+/// The requests under the `path` argument in collection order, and a name for the report.
+pub fn saved_requests(matches: &ArgMatches) -> Result<(String, SavedRequests), Box<dyn Error>> {
+    let collection = Collection::open_default();
+    let path = matches
+        .get_one::<String>("path")
+        .map(String::as_str)
+        .unwrap_or_default();
+    let paths = if collection.exists(path) {
+        vec![path.to_string()]
+    } else {
+        collection.request_paths(path)?
+    };
+
+    let mut requests = vec![];
+    for path in paths {
+        let saved = collection.load(&path)?;
+        requests.push((path, saved));
+    }
+    let name = match path {
+        "" => "collection".to_string(),
+        path => path.to_string(),
+    };
+    Ok((name, requests))
+}
+
+/// Writes the reports asked for and turns a failed run into an error.
+pub fn finish(report: &Report, matches: &ArgMatches) -> Result<(), Box<dyn Error>> {
+    if let Some(file) = matches.get_one::<PathBuf>("junit") {
+        report.save_junit(file)?;
+    }
+    if let Some(file) = matches.get_one::<PathBuf>("json") {
+        report.save_json(file)?;
+    }
+
+    if report.is_success() {
+        Ok(())
+    } else {
+        Err(format!(
+            "{} of {} requests failed",
+            report.cases.len() - report.passed(),
+            report.cases.len()
+        )
+        .into())
+    }
+}
@@ -1,7 +1,21 @@
+use std::path::PathBuf;
+
 pub mod constants {
     pub const APP_NAME: &str = "CrabAPI";
     pub const APP_DESCRIPTION: &str = "Web API Test tool";
     pub const APP_AUTHOR: &str = "CrabAPI Devs";
     pub const APP_VERSION: &str = "0.1.0";
     pub const APP_COMMAND_NAME: &str = "crabapi";
+    pub const HOME_ENV_VAR: &str = "CRABAPI_HOME";
+}
+
+/// Directory where collections and other local state are kept: `$CRABAPI_HOME` if set,
+/// otherwise the platform data directory.
+pub fn data_dir() -> PathBuf {
+    if let Some(home) = std::env::var_os(constants::HOME_ENV_VAR) {
+        return PathBuf::from(home);
+    }
+    dirs::data_dir()
+        .map(|dir| dir.join(constants::APP_COMMAND_NAME))
+        .unwrap_or_else(|| PathBuf::from(".").join(constants::APP_COMMAND_NAME))
 }
@@ -0,0 +1,733 @@
+//! Checks attached to saved requests, one per line of a small DSL:
+//!
+//! ```text
+//! status == 200
+//! status in 200..299
+//! header Content-Type exists
+//! header Content-Type matches ^application/json
+//! $.items[0].id == 42
+//! $.tags contains "new"
+//! $.id is integer
+//! body contains Welcome
+//! time < 500
+//! schema
+//! schema {"type": "object", "required": ["id"]}
+//! ```
+//!
+//! JSONPath values are JSON, or plain text when they do not parse as JSON, and may use
+//! `{{variables}}`. `schema` alone checks the response against the OpenAPI spec of the
+//! session.
+
+mod error;
+mod report;
+
+use crate::core::environments::Variables;
+use crate::core::jsonpath::JsonPath;
+use crate::core::openapi::{self, Violation, has_type, type_name};
+use crate::core::requests::ResponseRecord;
+use regex::Regex;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::fmt;
+use std::str::FromStr;
+
+pub use error::Error;
+pub use report::{Report, TestCase};
+
+const JSON_TYPES: [&str; 7] = [
+    "string", "number", "integer", "boolean", "array", "object", "null",
+];
+
+/// Longest rendering of an actual value in a failure message.
+const PREVIEW_LENGTH: usize = 120;
+
+/// A check on a response. Stored in collections in its DSL form.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(try_from = "String", into = "String")]
+pub enum Assertion {
+    Status(u16),
+    /// Both bounds included.
+    StatusRange {
+        min: u16,
+        max: u16,
+    },
+    HeaderExists(String),
+    /// Some value of the header matches the regular expression.
+    HeaderMatches {
+        name: String,
+        pattern: String,
+    },
+    JsonEquals {
+        path: JsonPath,
+        value: Value,
+    },
+    /// Substring of a string, element of an array, key or subset of an object.
+    JsonContains {
+        path: JsonPath,
+        value: Value,
+    },
+    JsonType {
+        path: JsonPath,
+        kind: String,
+    },
+    BodyContains(String),
+    /// Response time in milliseconds, strictly below.
+    TimeBelow(u64),
+    /// Matches the given JSON Schema, or the session's OpenAPI spec when `None`.
+    Schema(Option<Value>),
+}
+
+/// Outcome of one assertion, with what was received instead when it failed.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct AssertionResult {
+    pub assertion: String,
+    pub passed: bool,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub message: Option<String>,
+}
+
+/// Checks every assertion against `response`, in order.
+pub fn check(assertions: &[Assertion], response: &ResponseRecord) -> Vec<AssertionResult> {
+    let body = serde_json::from_slice::<Value>(&response.body).ok();
+    assertions
+        .iter()
+        .map(|assertion| {
+            let message = assertion.evaluate(response, body.as_ref()).err();
+            AssertionResult {
+                assertion: assertion.to_string(),
+                passed: message.is_none(),
+                message,
+            }
+        })
+        .collect()
+}
+
+impl Assertion {
+    /// Expands the `{{name}}` placeholders of the assertion, as in `$.id == {{id}}`. Expanded
+    /// values are read again as JSON or text; unknown names are left as they are.
+    pub fn resolve(&self, variables: &Variables) -> Self {
+        let expand = |text: &str| variables.expand(text).unwrap_or_else(|_| text.to_string());
+        let expand_value = |value: &Value| match value {
+            Value::String(text) => literal(&expand(text)),
+            value => value.clone(),
+        };
+        match self {
+            Assertion::HeaderExists(name) => Assertion::HeaderExists(expand(name)),
+            Assertion::HeaderMatches { name, pattern } => Assertion::HeaderMatches {
+                name: expand(name),
+                pattern: expand(pattern),
+            },
+            Assertion::JsonEquals { path, value } => Assertion::JsonEquals {
+                path: path.clone(),
+                value: expand_value(value),
+            },
+            Assertion::JsonContains { path, value } => Assertion::JsonContains {
+                path: path.clone(),
+                value: expand_value(value),
+            },
+            Assertion::BodyContains(text) => Assertion::BodyContains(expand(text)),
+            assertion => assertion.clone(),
+        }
+    }
+
+    /// `Err` describes how the response differs.
+    fn evaluate(&self, response: &ResponseRecord, body: Option<&Value>) -> Result<(), String> {
+        match self {
+            Assertion::Status(expected) => expect(response.status.as_u16() == *expected, || {
+                format!("got {}", response.status)
+            }),
+            Assertion::StatusRange { min, max } => {
+                expect((*min..=*max).contains(&response.status.as_u16()), || {
+                    format!("got {}", response.status)
+                })
+            }
+            Assertion::HeaderExists(name) => {
+                expect(response.headers.contains_key(name.as_str()), || {
+                    "header is missing".to_string()
+                })
+            }
+            Assertion::HeaderMatches { name, pattern } => {
+                let values: Vec<String> = response
+                    .headers
+                    .get_all(name.as_str())
+                    .iter()
+                    .map(|value| String::from_utf8_lossy(value.as_bytes()).into_owned())
+                    .collect();
+                if values.is_empty() {
+                    return Err("header is missing".to_string());
+                }
+                let regex = Regex::new(pattern).map_err(|error| error.to_string())?;
+                expect(values.iter().any(|value| regex.is_match(value)), || {
+                    format!("got '{}'", values.join(", "))
+                })
+            }
+            Assertion::JsonEquals { path, value } => {
+                let actual = selected(path, body)?;
+                expect(json_equal(&actual, value), || {
+                    format!("got {}", preview(&actual))
+                })
+            }
+            Assertion::JsonContains { path, value } => {
+                let actual = selected(path, body)?;
+                expect(contains(&actual, value), || {
+                    format!("got {}", preview(&actual))
+                })
+            }
+            Assertion::JsonType { path, kind } => {
+                let values = path.select(json_body(body)?);
+                if values.is_empty() {
+                    return Err(format!("{path} selects nothing"));
+                }
+                match values.into_iter().find(|value| !has_type(value, kind)) {
+                    Some(value) => Err(format!("got {} {}", type_name(value), preview(value))),
+                    None => Ok(()),
+                }
+            }
+            Assertion::BodyContains(text) => {
+                expect(response.text_lossy().contains(text.as_str()), || {
+                    "not found in the body".to_string()
+                })
+            }
+            Assertion::TimeBelow(limit) => {
+                let elapsed = response.elapsed.as_millis();
+                expect(elapsed < u128::from(*limit), || {
+                    format!("took {elapsed} ms")
+                })
+            }
+            Assertion::Schema(None) => match &response.validation {
+                Some(validation) => expect(validation.is_valid(), || {
+                    violations_message(&validation.violations)
+                }),
+                None => Err("no OpenAPI spec to validate against, use --spec".to_string()),
+            },
+            Assertion::Schema(Some(schema)) => {
+                let violations = openapi::validate_schema(schema, json_body(body)?);
+                expect(violations.is_empty(), || violations_message(&violations))
+            }
+        }
+    }
+}
+
+fn expect(condition: bool, message: impl FnOnce() -> String) -> Result<(), String> {
+    if condition { Ok(()) } else { Err(message()) }
+}
+
+/// JSON when it parses as JSON, text otherwise.
+fn literal(text: &str) -> Value {
+    serde_json::from_str(text).unwrap_or_else(|_| Value::String(text.to_string()))
+}
+
+fn json_body(body: Option<&Value>) -> Result<&Value, String> {
+    body.ok_or_else(|| "body is not JSON".to_string())
+}
+
+/// The value selected by `path`, or an array of them when it selects several.
+fn selected(path: &JsonPath, body: Option<&Value>) -> Result<Value, String> {
+    match path.select(json_body(body)?).as_slice() {
+        [] => Err(format!("{path} selects nothing")),
+        [value] => Ok((*value).clone()),
+        values => Ok(Value::Array(
+            values.iter().map(|value| (*value).clone()).collect(),
+        )),
+    }
+}
+
+/// Equality where `1` and `1.0` are the same number.
+fn json_equal(left: &Value, right: &Value) -> bool {
+    match (left, right) {
+        (Value::Number(left), Value::Number(right)) => left.as_f64() == right.as_f64(),
+        (Value::Array(left), Value::Array(right)) => {
+            left.len() == right.len()
+                && left
+                    .iter()
+                    .zip(right)
+                    .all(|(left, right)| json_equal(left, right))
+        }
+        (Value::Object(left), Value::Object(right)) => {
+            left.len() == right.len()
+                && left
+                    .iter()
+                    .all(|(key, left)| right.get(key).is_some_and(|right| json_equal(left, right)))
+        }
+        _ => left == right,
+    }
+}
+
+fn contains(haystack: &Value, needle: &Value) -> bool {
+    match (haystack, needle) {
+        (Value::String(haystack), Value::String(needle)) => haystack.contains(needle.as_str()),
+        (Value::String(haystack), needle) => haystack.contains(&needle.to_string()),
+        (Value::Array(items), needle) => items.iter().any(|item| json_equal(item, needle)),
+        (Value::Object(fields), Value::String(key)) => fields.contains_key(key),
+        (Value::Object(fields), Value::Object(subset)) => subset.iter().all(|(key, value)| {
+            fields
+                .get(key)
+                .is_some_and(|field| json_equal(field, value))
+        }),
+        _ => false,
+    }
+}
+
+fn preview(value: &Value) -> String {
+    let text = value.to_string();
+    if text.chars().count() > PREVIEW_LENGTH {
+        let truncated: String = text.chars().take(PREVIEW_LENGTH - 3).collect();
+        format!("{truncated}...")
+    } else {
+        text
+    }
+}
+
+fn violations_message(violations: &[Violation]) -> String {
+    let violations: Vec<String> = violations.iter().map(Violation::to_string).collect();
+    violations.join("; ")
+}
+
+/// The DSL form, which parses back to the same assertion.
+impl fmt::Display for Assertion {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Assertion::Status(status) => write!(f, "status == {status}"),
+            Assertion::StatusRange { min, max } => write!(f, "status in {min}..{max}"),
+            Assertion::HeaderExists(name) => write!(f, "header {name} exists"),
+            Assertion::HeaderMatches { name, pattern } => {
+                write!(f, "header {name} matches {pattern}")
+            }
+            Assertion::JsonEquals { path, value } => write!(f, "{path} == {value}"),
+            Assertion::JsonContains { path, value } => write!(f, "{path} contains {value}"),
+            Assertion::JsonType { path, kind } => write!(f, "{path} is {kind}"),
+            Assertion::BodyContains(text) => write!(f, "body contains {text}"),
+            Assertion::TimeBelow(limit) => write!(f, "time < {limit}"),
+            Assertion::Schema(None) => write!(f, "schema"),
+            Assertion::Schema(Some(schema)) => write!(f, "schema {schema}"),
+        }
+    }
+}
+
+impl FromStr for Assertion {
+    type Err = Error;
+
+    fn from_str(expression: &str) -> Result<Self, Self::Err> {
+        let expression = expression.trim();
+        let invalid = |reason| Error::Invalid {
+            expression: expression.to_string(),
+            reason,
+        };
+
+        if expression.starts_with('$') {
+            let (path, rest) = JsonPath::parse_prefix(expression)?;
+            let (operator, operand) = split_word(rest);
+            if operand.is_empty() {
+                return Err(invalid("missing value after the JSONPath operator"));
+            }
+            let value = || literal(operand);
+            return match operator {
+                "==" => Ok(Assertion::JsonEquals {
+                    path,
+                    value: value(),
+                }),
+                "contains" => Ok(Assertion::JsonContains {
+                    path,
+                    value: value(),
+                }),
+                "is" if JSON_TYPES.contains(&operand) => Ok(Assertion::JsonType {
+                    path,
+                    kind: operand.to_string(),
+                }),
+                "is" => Err(invalid(
+                    "expected string, number, integer, boolean, array, object or null",
+                )),
+                _ => Err(invalid(
+                    "expected '==', 'contains' or 'is' after the JSONPath",
+                )),
+            };
+        }
+
+        let (subject, rest) = split_word(expression);
+        match subject {
+            "status" => {
+                let (operator, operand) = split_word(rest);
+                let status = |text: &str| {
+                    text.trim()
+                        .parse::<u16>()
+                        .map_err(|_| invalid("expected a status code"))
+                };
+                match operator {
+                    "==" => Ok(Assertion::Status(status(operand)?)),
+                    "in" => {
+                        let (min, max) = operand
+                            .split_once("..")
+                            .ok_or_else(|| invalid("expected a range such as 200..299"))?;
+                        let max = max.strip_prefix('=').unwrap_or(max);
+                        Ok(Assertion::StatusRange {
+                            min: status(min)?,
+                            max: status(max)?,
+                        })
+                    }
+                    _ => Err(invalid("expected 'status == CODE' or 'status in MIN..MAX'")),
+                }
+            }
+            "header" => {
+                let (name, rest) = split_word(rest);
+                let (operator, operand) = split_word(rest);
+                match operator {
+                    _ if name.is_empty() => Err(invalid("missing header name")),
+                    "exists" if operand.is_empty() => Ok(Assertion::HeaderExists(name.to_string())),
+                    "matches" if !operand.is_empty() => {
+                        Regex::new(operand)?;
+                        Ok(Assertion::HeaderMatches {
+                            name: name.to_string(),
+                            pattern: operand.to_string(),
+                        })
+                    }
+                    _ => Err(invalid(
+                        "expected 'header NAME exists' or 'header NAME matches PATTERN'",
+                    )),
+                }
+            }
+            "body" => match split_word(rest) {
+                ("contains", text) if !text.is_empty() => {
+                    Ok(Assertion::BodyContains(text.to_string()))
+                }
+                _ => Err(invalid("expected 'body contains TEXT'")),
+            },
+            "time" => match split_word(rest) {
+                ("<", limit) => limit
+                    .trim_end_matches("ms")
+                    .trim()
+                    .parse()
+                    .map(Assertion::TimeBelow)
+                    .map_err(|_| invalid("expected a number of milliseconds")),
+                _ => Err(invalid("expected 'time < MILLISECONDS'")),
+            },
+            "schema" if rest.is_empty() => Ok(Assertion::Schema(None)),
+            "schema" => serde_json::from_str(rest)
+                .map(|schema| Assertion::Schema(Some(schema)))
+                .map_err(|_| invalid("the schema is not valid JSON")),
+            _ => Err(invalid(
+                "expected status, header, body, time, schema or a JSONPath starting with '$'",
+            )),
+        }
+    }
+}
+
+impl TryFrom<String> for Assertion {
+    type Error = Error;
+
+    fn try_from(expression: String) -> Result<Self, Self::Error> {
+        expression.parse()
+    }
+}
+
+impl From<Assertion> for String {
+    fn from(assertion: Assertion) -> Self {
+        assertion.to_string()
+    }
+}
+
+/// `status == 200`, or `status == 200: got 404 Not Found` when it failed.
+impl fmt::Display for AssertionResult {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.assertion)?;
+        if let Some(message) = &self.message {
+            write!(f, ": {message}")?;
+        }
+        Ok(())
+    }
+}
+
+/// The first word of `text` and the rest, both trimmed.
+fn split_word(text: &str) -> (&str, &str) {
+    let text = text.trim();
+    match text.split_once(char::is_whitespace) {
+        Some((word, rest)) => (word, rest.trim()),
+        None => (text, ""),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+    use std::time::Duration;
+
+    fn path(expression: &str) -> JsonPath {
+        JsonPath::parse(expression).unwrap()
+    }
+
+    fn reason(expression: &str) -> &'static str {
+        match expression.parse::<Assertion>() {
+            Err(Error::Invalid { reason, .. }) => reason,
+            other => panic!("expected an invalid assertion, got {other:?}"),
+        }
+    }
+
+    #[test]
+    fn parses_every_kind() {
+        let cases = [
+            ("status == 200", Assertion::Status(200)),
+            (
+                "status in 200..299",
+                Assertion::StatusRange { min: 200, max: 299 },
+            ),
+            (
+                "header Content-Type exists",
+                Assertion::HeaderExists("Content-Type".to_string()),
+            ),
+            (
+                "header Content-Type matches ^application/json",
+                Assertion::HeaderMatches {
+                    name: "Content-Type".to_string(),
+                    pattern: "^application/json".to_string(),
+                },
+            ),
+            (
+                "$.items[0].id == 42",
+                Assertion::JsonEquals {
+                    path: path("$.items[0].id"),
+                    value: json!(42),
+                },
+            ),
+            (
+                "$.tags contains \"new\"",
+                Assertion::JsonContains {
+                    path: path("$.tags"),
+                    value: json!("new"),
+                },
+            ),
+            (
+                "$.id is integer",
+                Assertion::JsonType {
+                    path: path("$.id"),
+                    kind: "integer".to_string(),
+                },
+            ),
+            (
+                "body contains Welcome back",
+                Assertion::BodyContains("Welcome back".to_string()),
+            ),
+            ("time < 500", Assertion::TimeBelow(500)),
+            ("schema", Assertion::Schema(None)),
+            (
+                r#"schema {"type":"object","required":["id"]}"#,
+                Assertion::Schema(Some(json!({"type": "object", "required": ["id"]}))),
+            ),
+        ];
+        for (expression, expected) in cases {
+            let assertion: Assertion = expression.parse().unwrap();
+            assert_eq!(assertion, expected, "{expression}");
+            assert_eq!(assertion.to_string(), expression);
+        }
+    }
+
+    #[test]
+    fn display_parses_back_to_the_same_assertion() {
+        let expressions = [
+            "  status in 200..=299 ",
+            "time < 250ms",
+            "$.name == Ferris",
+            "$.user contains {\"role\": \"admin\"}",
+            "$..price == 8.95",
+            "$.items[?(@.price < 10)].name contains Sayings",
+            "body contains {{greeting}}",
+            "header X-Id matches ^[0-9a-f]{8}$",
+            "schema {\"type\": \"array\"}",
+        ];
+        for expression in expressions {
+            let assertion: Assertion = expression.parse().unwrap();
+            let text = assertion.to_string();
+            assert_eq!(text.parse::<Assertion>().unwrap(), assertion, "{text}");
+        }
+        assert_eq!(
+            "status in 200..=299"
+                .parse::<Assertion>()
+                .unwrap()
+                .to_string(),
+            "status in 200..299"
+        );
+        assert_eq!(
+            "$.name == Ferris".parse::<Assertion>().unwrap().to_string(),
+            "$.name == \"Ferris\""
+        );
+    }
+
+    #[test]
+    fn serializes_as_the_dsl() {
+        let assertions: Vec<Assertion> =
+            serde_json::from_value(json!(["status == 201", "$.id is string"])).unwrap();
+
+        assert_eq!(assertions[0], Assertion::Status(201));
+        assert_eq!(
+            serde_json::to_value(&assertions).unwrap(),
+            json!(["status == 201", "$.id is string"])
+        );
+        assert!(serde_json::from_value::<Assertion>(json!("status is ok")).is_err());
+    }
+
+    /// `passed` or the failure message of each assertion, in order.
+    fn outcomes(assertions: &[&str], response: &ResponseRecord) -> Vec<String> {
+        let assertions: Vec<Assertion> = assertions
+            .iter()
+            .map(|assertion| assertion.parse().unwrap())
+            .collect();
+        check(&assertions, response)
+            .into_iter()
+            .map(|result| match result.message {
+                Some(message) => {
+                    assert!(!result.passed);
+                    message
+                }
+                None => "passed".to_string(),
+            })
+            .collect()
+    }
+
+    fn response() -> ResponseRecord {
+        let body = r#"{"id": 7, "name": "Ferris", "tags": ["crab", "rust"], "price": 8.0}"#;
+        let mut response = ResponseRecord::stub(
+            201,
+            &[
+                ("content-type", "application/json"),
+                ("set-cookie", "a=1"),
+                ("set-cookie", "b=2"),
+            ],
+            body,
+        );
+        response.elapsed = Duration::from_millis(120);
+        response
+    }
+
+    #[test]
+    fn checks_status_and_headers() {
+        let assertions = [
+            "status == 201",
+            "status == 200",
+            "status in 200..299",
+            "status in 400..499",
+            "header Content-Type exists",
+            "header X-Id exists",
+            "header content-type matches ^application/json",
+            "header Set-Cookie matches ^b=",
+            "header Set-Cookie matches ^c=",
+            "header X-Id matches .",
+        ];
+        assert_eq!(
+            outcomes(&assertions, &response()),
+            [
+                "passed",
+                "got 201 Created",
+                "passed",
+                "got 201 Created",
+                "passed",
+                "header is missing",
+                "passed",
+                "passed",
+                "got 'a=1, b=2'",
+                "header is missing",
+            ]
+        );
+    }
+
+    #[test]
+    fn checks_json_paths() {
+        let assertions = [
+            "$.id == 7",
+            "$.id == 8",
+            "$.price == 8",
+            "$.tags contains rust",
+            "$.tags contains go",
+            "$ contains {\"name\": \"Ferris\"}",
+            "$.missing == 1",
+            "$.name is string",
+            "$.tags is object",
+        ];
+        assert_eq!(
+            outcomes(&assertions, &response()),
+            [
+                "passed",
+                "got 7",
+                "passed",
+                "passed",
+                "got [\"crab\",\"rust\"]",
+                "passed",
+                "$.missing selects nothing",
+                "passed",
+                "got array [\"crab\",\"rust\"]",
+            ]
+        );
+
+        let text = ResponseRecord::stub(200, &[], "Welcome back");
+        assert_eq!(outcomes(&["$.id == 7"], &text), ["body is not JSON"]);
+    }
+
+    #[test]
+    fn checks_body_time_and_schema() {
+        let assertions = [
+            "body contains Ferris",
+            "body contains Gopher",
+            "time < 500",
+            "time < 120",
+            r#"schema {"type": "object", "required": ["id"]}"#,
+            r#"schema {"properties": {"id": {"type": "string"}, "name": {"maxLength": 3}}}"#,
+            "schema",
+        ];
+        assert_eq!(
+            outcomes(&assertions, &response()),
+            [
+                "passed",
+                "not found in the body",
+                "passed",
+                "took 120 ms",
+                "passed",
+                "#/id: expected string, found integer; #/name: longer than 3 characters",
+                "no OpenAPI spec to validate against, use --spec",
+            ]
+        );
+    }
+
+    #[test]
+    fn reports_failures_after_the_assertion() {
+        let assertions = [Assertion::Status(200), Assertion::TimeBelow(500)];
+        let results = check(&assertions, &response());
+        assert_eq!(results[0].to_string(), "status == 200: got 201 Created");
+        assert_eq!(results[1].to_string(), "time < 500");
+        assert!(results[1].passed);
+    }
+
+    #[test]
+    fn rejects_invalid_expressions() {
+        assert_eq!(reason("status == ok"), "expected a status code");
+        assert_eq!(reason("status in 200"), "expected a range such as 200..299");
+        assert_eq!(
+            reason("header exists"),
+            "expected 'header NAME exists' or 'header NAME matches PATTERN'"
+        );
+        assert_eq!(reason("body has text"), "expected 'body contains TEXT'");
+        assert_eq!(reason("time < soon"), "expected a number of milliseconds");
+        assert_eq!(reason("schema {"), "the schema is not valid JSON");
+        assert_eq!(
+            reason("$.id =="),
+            "missing value after the JSONPath operator"
+        );
+        assert_eq!(
+            reason("$.id is uuid"),
+            "expected string, number, integer, boolean, array, object or null"
+        );
+        assert_eq!(
+            reason("$.id != 1"),
+            "expected '==', 'contains' or 'is' after the JSONPath"
+        );
+        assert_eq!(
+            reason("latency < 5"),
+            "expected status, header, body, time, schema or a JSONPath starting with '$'"
+        );
+        assert!(matches!(
+            "header X matches (".parse::<Assertion>(),
+            Err(Error::Regex(_))
+        ));
+        assert!(matches!(
+            "$[ == 1".parse::<Assertion>(),
+            Err(Error::JsonPath(_))
+        ));
+    }
+}
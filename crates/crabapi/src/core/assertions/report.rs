@@ -0,0 +1,468 @@
+use super::{Assertion, AssertionResult, Error, check};
+use crate::core::requests::{Error as RequestError, RequestSpec, ResponseRecord};
+use serde::Serialize;
+use std::fmt;
+use std::path::Path;
+use std::time::Duration;
+
+/// Outcome of running the assertions of a set of saved requests.
+#[derive(Debug, Clone)]
+pub struct Report {
+    pub name: String,
+    pub duration: Duration,
+    pub cases: Vec<TestCase>,
+}
+
+/// One request: the response it got, or the error that prevented one, and its assertions.
+#[derive(Debug, Clone, Serialize)]
+pub struct TestCase {
+    pub path: String,
+    /// Row of the data file, counted from 1, when the run iterates over one.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub iteration: Option<usize>,
+    pub method: String,
+    pub url: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub status: Option<u16>,
+    pub duration_ms: u64,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+    pub assertions: Vec<AssertionResult>,
+    /// Values taken from the response for the next requests; failing when not found.
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub extracted: Vec<AssertionResult>,
+    /// What the request's scripts printed.
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub logs: Vec<String>,
+}
+
+#[derive(Serialize)]
+struct JsonReport<'a> {
+    name: &'a str,
+    tests: usize,
+    passed: usize,
+    failed: usize,
+    errors: usize,
+    duration_ms: u64,
+    cases: &'a [TestCase],
+}
+
+impl TestCase {
+    /// `elapsed` is used when the request failed before a response was timed.
+    pub fn new(
+        path: &str,
+        request: &RequestSpec,
+        result: &Result<ResponseRecord, RequestError>,
+        assertions: &[Assertion],
+        elapsed: Duration,
+    ) -> Self {
+        let mut case = Self::pending(path, request, elapsed);
+        match result {
+            Ok(response) => {
+                case.url = response.url.to_string();
+                case.status = Some(response.status.as_u16());
+                case.duration_ms = response.elapsed.as_millis() as u64;
+                case.assertions = check(assertions, response);
+            }
+            Err(error) => case.error = Some(error.to_string()),
+        }
+        case
+    }
+
+    /// A request that was not sent because of `error`, e.g. a failed pre-request script.
+    pub fn unsent(path: &str, request: &RequestSpec, error: String, elapsed: Duration) -> Self {
+        Self {
+            error: Some(error),
+            ..Self::pending(path, request, elapsed)
+        }
+    }
+
+    fn pending(path: &str, request: &RequestSpec, elapsed: Duration) -> Self {
+        Self {
+            path: path.to_string(),
+            iteration: None,
+            method: request.method.to_string(),
+            url: request.url.clone(),
+            status: None,
+            duration_ms: elapsed.as_millis() as u64,
+            error: None,
+            assertions: vec![],
+            extracted: vec![],
+            logs: vec![],
+        }
+    }
+
+    /// Requests without assertions pass as long as they get a response.
+    pub fn passed(&self) -> bool {
+        self.error.is_none() && self.failures().next().is_none()
+    }
+
+    /// Failed assertions, then failed extractions.
+    pub fn failures(&self) -> impl Iterator<Item = &AssertionResult> {
+        self.assertions
+            .iter()
+            .chain(&self.extracted)
+            .filter(|result| !result.passed)
+    }
+
+    /// The request path, followed by the iteration when there are several.
+    pub fn name(&self) -> String {
+        match self.iteration {
+            Some(iteration) => format!("{} (iteration {iteration})", self.path),
+            None => self.path.clone(),
+        }
+    }
+}
+
+/// What the scripts printed, the outcome on one line, failed assertions below it.
+impl fmt::Display for TestCase {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for line in &self.logs {
+            writeln!(f, "> {line}")?;
+        }
+        if let Some(error) = &self.error {
+            return writeln!(f, "ERROR {}: {error}", self.name());
+        }
+        let label = if self.passed() { "PASS " } else { "FAIL " };
+        writeln!(
+            f,
+            "{label} {} ({}, {} ms)",
+            self.name(),
+            self.status.unwrap_or_default(),
+            self.duration_ms
+        )?;
+        for failure in self.failures() {
+            writeln!(f, "\t{failure}")?;
+        }
+        Ok(())
+    }
+}
+
+impl Report {
+    pub fn passed(&self) -> usize {
+        self.cases.iter().filter(|case| case.passed()).count()
+    }
+
+    /// Requests that got a response failing at least one assertion.
+    pub fn failed(&self) -> usize {
+        self.cases
+            .iter()
+            .filter(|case| case.error.is_none() && !case.passed())
+            .count()
+    }
+
+    /// Requests that got no response.
+    pub fn errors(&self) -> usize {
+        self.cases
+            .iter()
+            .filter(|case| case.error.is_some())
+            .count()
+    }
+
+    pub fn is_success(&self) -> bool {
+        self.passed() == self.cases.len()
+    }
+
+    pub fn to_json(&self) -> Result<String, Error> {
+        let report = JsonReport {
+            name: &self.name,
+            tests: self.cases.len(),
+            passed: self.passed(),
+            failed: self.failed(),
+            errors: self.errors(),
+            duration_ms: self.duration.as_millis() as u64,
+            cases: &self.cases,
+        };
+        serde_json::to_string_pretty(&report).map_err(Error::Serialize)
+    }
+
+    /// One test suite with a test case per request, named after its path in the collection.
+    pub fn to_junit(&self) -> String {
+        let counts = format!(
+            "tests=\"{}\" failures=\"{}\" errors=\"{}\" time=\"{}\"",
+            self.cases.len(),
+            self.failed(),
+            self.errors(),
+            seconds(self.duration)
+        );
+        let mut xml = String::from("<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n");
+        xml += &format!("<testsuites name=\"crabapi\" {counts}>\n");
+        xml += &format!("  <testsuite name=\"{}\" {counts}>\n", escape(&self.name));
+        for case in &self.cases {
+            let name = case.name();
+            let (folder, name) = name.rsplit_once('/').unwrap_or(("", &name));
+            let classname = match folder {
+                "" => self.name.clone(),
+                folder => folder.replace('/', "."),
+            };
+            xml += &format!(
+                "    <testcase classname=\"{}\" name=\"{}\" time=\"{}\"",
+                escape(&classname),
+                escape(name),
+                seconds(Duration::from_millis(case.duration_ms))
+            );
+            if case.passed() {
+                xml += " />\n";
+                continue;
+            }
+            xml += ">\n";
+            if let Some(error) = &case.error {
+                xml += &format!(
+                    "      <error message=\"{}\" type=\"RequestError\" />\n",
+                    escape(error)
+                );
+            } else {
+                let failures: Vec<String> = case.failures().map(ToString::to_string).collect();
+                xml += &format!(
+                    "      <failure message=\"{} of {} checks failed\" type=\"AssertionFailure\">{}</failure>\n",
+                    failures.len(),
+                    case.assertions.len() + case.extracted.len(),
+                    escape(&failures.join("\n"))
+                );
+            }
+            xml += "    </testcase>\n";
+        }
+        xml += "  </testsuite>\n</testsuites>\n";
+        xml
+    }
+
+    pub fn save_json(&self, path: &Path) -> Result<(), Error> {
+        write(path, self.to_json()?)
+    }
+
+    pub fn save_junit(&self, path: &Path) -> Result<(), Error> {
+        write(path, self.to_junit())
+    }
+
+    /// Totals, e.g. `3 requests: 2 passed, 1 failed, 0 errors; 7 of 8 assertions passed in
+    /// 0.120 s`.
+    pub fn summary(&self) -> String {
+        let assertions: usize = self.cases.iter().map(|case| case.assertions.len()).sum();
+        let failed_assertions: usize = self
+            .cases
+            .iter()
+            .flat_map(|case| &case.assertions)
+            .filter(|result| !result.passed)
+            .count();
+        format!(
+            "{} requests: {} passed, {} failed, {} errors; {} of {assertions} assertions passed in {} s",
+            self.cases.len(),
+            self.passed(),
+            self.failed(),
+            self.errors(),
+            assertions - failed_assertions,
+            seconds(self.duration)
+        )
+    }
+}
+
+/// Every test case, then the totals.
+impl fmt::Display for Report {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for case in &self.cases {
+            write!(f, "{case}")?;
+        }
+        writeln!(f, "\n{}", self.summary())
+    }
+}
+
+fn write(path: &Path, content: String) -> Result<(), Error> {
+    std::fs::write(path, content).map_err(|source| Error::Io {
+        path: path.to_path_buf(),
+        source,
+    })
+}
+
+fn seconds(duration: Duration) -> String {
+    format!("{:.3}", duration.as_secs_f64())
+}
+
+/// Escapes text for XML attributes and content, dropping the control characters XML 1.0
+/// does not allow.
+fn escape(text: &str) -> String {
+    let mut escaped = String::with_capacity(text.len());
+    for c in text.chars() {
+        match c {
+            '&' => escaped.push_str("&amp;"),
+            '<' => escaped.push_str("&lt;"),
+            '>' => escaped.push_str("&gt;"),
+            '"' => escaped.push_str("&quot;"),
+            '\'' => escaped.push_str("&apos;"),
+            '\n' | '\r' | '\t' => escaped.push(c),
+            c if c.is_control() => {}
+            c => escaped.push(c),
+        }
+    }
+    escaped
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn result(assertion: &str, message: Option<&str>) -> AssertionResult {
+        AssertionResult {
+            assertion: assertion.to_string(),
+            passed: message.is_none(),
+            message: message.map(str::to_string),
+        }
+    }
+
+    fn case(path: &str, status: Option<u16>, duration_ms: u64) -> TestCase {
+        TestCase {
+            path: path.to_string(),
+            iteration: None,
+            method: "GET".to_string(),
+            url: "http://127.0.0.1:7878/users".to_string(),
+            status,
+            duration_ms,
+            error: None,
+            assertions: vec![],
+            extracted: vec![],
+            logs: vec![],
+        }
+    }
+
+    fn report() -> Report {
+        let passed = TestCase {
+            assertions: vec![result("status == 200", None)],
+            ..case("Users/List users", Some(200), 12)
+        };
+        let failed = TestCase {
+            iteration: Some(2),
+            assertions: vec![
+                result("status == 200", Some("got 404 Not Found")),
+                result("$.name == \"<Tom & 'Jerry'>\"", Some("got \"Tom\u{1}\"")),
+            ],
+            extracted: vec![result("id = $.id", None)],
+            logs: vec!["fetching".to_string()],
+            ..case("Users/Admins/Get user", Some(404), 8)
+        };
+        let error = TestCase {
+            error: Some("connection refused: \"127.0.0.1\"".to_string()),
+            ..case("Ping", None, 1500)
+        };
+        Report {
+            name: "Echo & <Friends>".to_string(),
+            duration: Duration::from_millis(1520),
+            cases: vec![passed, failed, error],
+        }
+    }
+
+    #[test]
+    fn counts_outcomes() {
+        let report = report();
+
+        assert_eq!(report.passed(), 1);
+        assert_eq!(report.failed(), 1);
+        assert_eq!(report.errors(), 1);
+        assert!(!report.is_success());
+        assert_eq!(
+            report.summary(),
+            "3 requests: 1 passed, 1 failed, 1 errors; 1 of 3 assertions passed in 1.520 s"
+        );
+    }
+
+    #[test]
+    fn renders_junit() {
+        assert_eq!(
+            report().to_junit(),
+            r#"<?xml version="1.0" encoding="UTF-8"?>
+<testsuites name="crabapi" tests="3" failures="1" errors="1" time="1.520">
+  <testsuite name="Echo &amp; &lt;Friends&gt;" tests="3" failures="1" errors="1" time="1.520">
+    <testcase classname="Users" name="List users" time="0.012" />
+    <testcase classname="Users.Admins" name="Get user (iteration 2)" time="0.008">
+      <failure message="2 of 3 checks failed" type="AssertionFailure">status == 200: got 404 Not Found
+$.name == &quot;&lt;Tom &amp; &apos;Jerry&apos;&gt;&quot;: got &quot;Tom&quot;</failure>
+    </testcase>
+    <testcase classname="Echo &amp; &lt;Friends&gt;" name="Ping" time="1.500">
+      <error message="connection refused: &quot;127.0.0.1&quot;" type="RequestError" />
+    </testcase>
+  </testsuite>
+</testsuites>
+"#
+        );
+    }
+
+    #[test]
+    fn renders_json() {
+        assert_eq!(
+            report().to_json().unwrap(),
+            r#"{
+  "name": "Echo & <Friends>",
+  "tests": 3,
+  "passed": 1,
+  "failed": 1,
+  "errors": 1,
+  "duration_ms": 1520,
+  "cases": [
+    {
+      "path": "Users/List users",
+      "method": "GET",
+      "url": "http://127.0.0.1:7878/users",
+      "status": 200,
+      "duration_ms": 12,
+      "assertions": [
+        {
+          "assertion": "status == 200",
+          "passed": true
+        }
+      ]
+    },
+    {
+      "path": "Users/Admins/Get user",
+      "iteration": 2,
+      "method": "GET",
+      "url": "http://127.0.0.1:7878/users",
+      "status": 404,
+      "duration_ms": 8,
+      "assertions": [
+        {
+          "assertion": "status == 200",
+          "passed": false,
+          "message": "got 404 Not Found"
+        },
+        {
+          "assertion": "$.name == \"<Tom & 'Jerry'>\"",
+          "passed": false,
+          "message": "got \"Tom\u0001\""
+        }
+      ],
+      "extracted": [
+        {
+          "assertion": "id = $.id",
+          "passed": true
+        }
+      ],
+      "logs": [
+        "fetching"
+      ]
+    },
+    {
+      "path": "Ping",
+      "method": "GET",
+      "url": "http://127.0.0.1:7878/users",
+      "duration_ms": 1500,
+      "error": "connection refused: \"127.0.0.1\"",
+      "assertions": []
+    }
+  ]
+}"#
+        );
+    }
+
+    #[test]
+    fn renders_text() {
+        assert_eq!(
+            report().to_string(),
+            "PASS  Users/List users (200, 12 ms)\n\
+             > fetching\n\
+             FAIL  Users/Admins/Get user (iteration 2) (404, 8 ms)\n\
+             \tstatus == 200: got 404 Not Found\n\
+             \t$.name == \"<Tom & 'Jerry'>\": got \"Tom\u{1}\"\n\
+             ERROR Ping: connection refused: \"127.0.0.1\"\n\
+             \n\
+             3 requests: 1 passed, 1 failed, 1 errors; 1 of 3 assertions passed in 1.520 s\n"
+        );
+    }
+}
@@ -0,0 +1,395 @@
+use md5::Md5;
+use sha2::{Digest, Sha256};
+use std::collections::HashMap;
+use std::fmt::Write;
+
+/// A parsed `WWW-Authenticate: Digest ...` challenge (RFC 7616).
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DigestChallenge {
+    realm: String,
+    nonce: String,
+    opaque: Option<String>,
+    algorithm: Algorithm,
+    session: bool,
+    qop: Option<Qop>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Algorithm {
+    Md5,
+    Sha256,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Qop {
+    Auth,
+    AuthInt,
+}
+
+impl DigestChallenge {
+    /// Parses the value of a `WWW-Authenticate` header, returning `None` if it is not a
+    /// Digest challenge we can answer.
+    pub fn parse(header: &str) -> Option<Self> {
+        let (scheme, params) = header.trim().split_once(' ')?;
+        if !scheme.eq_ignore_ascii_case("digest") {
+            return None;
+        }
+        let params = parse_params(params);
+
+        let (algorithm, session) = match params
+            .get("algorithm")
+            .map(|algorithm| algorithm.to_ascii_uppercase())
+            .as_deref()
+        {
+            None | Some("MD5") => (Algorithm::Md5, false),
+            Some("MD5-SESS") => (Algorithm::Md5, true),
+            Some("SHA-256") => (Algorithm::Sha256, false),
+            Some("SHA-256-SESS") => (Algorithm::Sha256, true),
+            Some(_) => return None,
+        };
+
+        // Prefer "auth" when the server offers both.
+        let qop = params.get("qop").map(|qop| {
+            let options: Vec<&str> = qop.split(',').map(str::trim).collect();
+            if options.contains(&"auth") || !options.contains(&"auth-int") {
+                Qop::Auth
+            } else {
+                Qop::AuthInt
+            }
+        });
+
+        Some(Self {
+            realm: params.get("realm").cloned().unwrap_or_default(),
+            nonce: params.get("nonce")?.clone(),
+            opaque: params.get("opaque").cloned(),
+            algorithm,
+            session,
+            qop,
+        })
+    }
+
+    /// Value of the `Authorization` header answering this challenge. `uri` is the request
+    /// target (path and query) and `body` is only used for `qop=auth-int`; without it, the
+    /// answer falls back to `qop=auth`.
+    pub fn authorization(
+        &self,
+        username: &str,
+        password: &str,
+        method: &str,
+        uri: &str,
+        body: Option<&[u8]>,
+    ) -> String {
+        let cnonce = hex(&rand::random::<[u8; 16]>());
+        self.answer(username, password, method, uri, body, &cnonce)
+    }
+
+    fn answer(
+        &self,
+        username: &str,
+        password: &str,
+        method: &str,
+        uri: &str,
+        body: Option<&[u8]>,
+        cnonce: &str,
+    ) -> String {
+        let nc = "00000001";
+        let qop = match (self.qop, body) {
+            (Some(Qop::AuthInt), None) => Some(Qop::Auth),
+            (qop, _) => qop,
+        };
+
+        let mut ha1 = self.hash(format!("{username}:{}:{password}", self.realm).as_bytes());
+        if self.session {
+            ha1 = self.hash(format!("{ha1}:{}:{cnonce}", self.nonce).as_bytes());
+        }
+
+        let ha2 = match (qop, body) {
+            (Some(Qop::AuthInt), Some(body)) => {
+                self.hash(format!("{method}:{uri}:{}", self.hash(body)).as_bytes())
+            }
+            _ => self.hash(format!("{method}:{uri}").as_bytes()),
+        };
+
+        let response = match qop {
+            Some(qop) => self.hash(
+                format!("{ha1}:{}:{nc}:{cnonce}:{}:{ha2}", self.nonce, qop.as_str()).as_bytes(),
+            ),
+            None => self.hash(format!("{ha1}:{}:{ha2}", self.nonce).as_bytes()),
+        };
+
+        let mut header = format!(
+            r#"Digest username="{}", realm="{}", nonce="{}", uri="{}", algorithm={}, response="{response}""#,
+            quote(username),
+            quote(&self.realm),
+            quote(&self.nonce),
+            quote(uri),
+            self.algorithm_name(),
+        );
+        if let Some(qop) = qop {
+            header.push_str(&format!(
+                r#", qop={}, nc={nc}, cnonce="{cnonce}""#,
+                qop.as_str()
+            ));
+        }
+        if let Some(opaque) = &self.opaque {
+            header.push_str(&format!(r#", opaque="{}""#, quote(opaque)));
+        }
+        header
+    }
+
+    fn hash(&self, data: &[u8]) -> String {
+        match self.algorithm {
+            Algorithm::Md5 => hex(&Md5::digest(data)),
+            Algorithm::Sha256 => hex(&Sha256::digest(data)),
+        }
+    }
+
+    fn algorithm_name(&self) -> &'static str {
+        match (self.algorithm, self.session) {
+            (Algorithm::Md5, false) => "MD5",
+            (Algorithm::Md5, true) => "MD5-sess",
+            (Algorithm::Sha256, false) => "SHA-256",
+            (Algorithm::Sha256, true) => "SHA-256-sess",
+        }
+    }
+}
+
+impl Qop {
+    fn as_str(&self) -> &'static str {
+        match self {
+            Qop::Auth => "auth",
+            Qop::AuthInt => "auth-int",
+        }
+    }
+}
+
+/// Parses `key=value, key="quoted, value"` pairs. Keys are lowercased.
+fn parse_params(input: &str) -> HashMap<String, String> {
+    let mut params = HashMap::new();
+    let mut chars = input.chars().peekable();
+
+    loop {
+        while chars.next_if(|c| c.is_whitespace() || *c == ',').is_some() {}
+        let key: String = std::iter::from_fn(|| chars.next_if(|c| *c != '=')).collect();
+        if key.is_empty() || chars.next().is_none() {
+            break;
+        }
+
+        let mut value = String::new();
+        if chars.next_if_eq(&'"').is_some() {
+            while let Some(c) = chars.next() {
+                match c {
+                    '\\' => value.extend(chars.next()),
+                    '"' => break,
+                    c => value.push(c),
+                }
+            }
+        } else {
+            value = std::iter::from_fn(|| chars.next_if(|c| *c != ','))
+                .collect::<String>()
+                .trim()
+                .to_string();
+        }
+        params.insert(key.trim().to_ascii_lowercase(), value);
+    }
+
+    params
+}
+
+fn quote(value: &str) -> String {
+    value.replace('\\', "\\\\").replace('"', "\\\"")
+}
+
+fn hex(bytes: &[u8]) -> String {
+    bytes.iter().fold(String::new(), |mut output, byte| {
+        let _ = write!(output, "{byte:02x}");
+        output
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const RFC_2617: &str = r#"Digest realm="testrealm@host.com", qop="auth,auth-int", nonce="dcd98b7102dd2f0e8b11d0f600bfb0c093", opaque="5ccc069c403ebaf9f0171e9517f40e41""#;
+
+    fn rfc_7616(algorithm: &str) -> String {
+        format!(
+            r#"Digest realm="http-auth@example.org", qop="auth, auth-int", algorithm={algorithm}, nonce="7ypf/xlj9XXwfDPEoM4URrv/xwf94BcCAzFZH4GiTo0v", opaque="FQhe/qaU925kfnzjCev0ciny7QMkPqMAFRtzCUYo5tdS""#
+        )
+    }
+
+    fn md5(data: &str) -> String {
+        hex(&Md5::digest(data.as_bytes()))
+    }
+
+    #[test]
+    fn parses_challenges() {
+        let challenge = DigestChallenge::parse(RFC_2617).unwrap();
+
+        assert_eq!(
+            challenge,
+            DigestChallenge {
+                realm: "testrealm@host.com".to_string(),
+                nonce: "dcd98b7102dd2f0e8b11d0f600bfb0c093".to_string(),
+                opaque: Some("5ccc069c403ebaf9f0171e9517f40e41".to_string()),
+                algorithm: Algorithm::Md5,
+                session: false,
+                qop: Some(Qop::Auth),
+            }
+        );
+
+        let challenge = DigestChallenge::parse(
+            r#"digest REALM="a \"quoted\", realm", nonce=abc, algorithm=sha-256-sess, qop="auth-int""#,
+        )
+        .unwrap();
+        assert_eq!(challenge.realm, r#"a "quoted", realm"#);
+        assert_eq!(challenge.nonce, "abc");
+        assert_eq!(challenge.algorithm, Algorithm::Sha256);
+        assert!(challenge.session);
+        assert_eq!(challenge.qop, Some(Qop::AuthInt));
+    }
+
+    #[test]
+    fn rejects_other_challenges() {
+        assert_eq!(DigestChallenge::parse(r#"Basic realm="x""#), None);
+        assert_eq!(DigestChallenge::parse(r#"Digest realm="x""#), None);
+        assert_eq!(
+            DigestChallenge::parse(r#"Digest nonce="n", algorithm=SHA-512-256"#),
+            None
+        );
+        assert_eq!(DigestChallenge::parse("Digest"), None);
+    }
+
+    #[test]
+    fn answers_the_rfc_2617_example() {
+        let challenge = DigestChallenge::parse(RFC_2617).unwrap();
+
+        let header = challenge.answer(
+            "Mufasa",
+            "Circle Of Life",
+            "GET",
+            "/dir/index.html",
+            Some(b""),
+            "0a4f113b",
+        );
+
+        assert_eq!(
+            header,
+            r#"Digest username="Mufasa", realm="testrealm@host.com", nonce="dcd98b7102dd2f0e8b11d0f600bfb0c093", uri="/dir/index.html", algorithm=MD5, response="6629fae49393a05397450978507c4ef1", qop=auth, nc=00000001, cnonce="0a4f113b", opaque="5ccc069c403ebaf9f0171e9517f40e41""#
+        );
+    }
+
+    #[test]
+    fn answers_the_rfc_7616_examples() {
+        let cnonce = "f2/wE4q74E6zIJEtWaHKaf5wv/H5QzzpXusqGemxURZJ";
+        let cases = [
+            ("MD5", "8ca523f5e9506fed4657c9700eebdbec"),
+            (
+                "SHA-256",
+                "753927fa0e85d155564e2e272a28d1802ca10daf4496794697cf8db5856cb6c1",
+            ),
+        ];
+        for (algorithm, response) in cases {
+            let challenge = DigestChallenge::parse(&rfc_7616(algorithm)).unwrap();
+            let header = challenge.answer(
+                "Mufasa",
+                "Circle of Life",
+                "GET",
+                "/dir/index.html",
+                Some(b""),
+                cnonce,
+            );
+
+            assert!(
+                header.contains(&format!(r#"response="{response}""#)),
+                "{algorithm}: {header}"
+            );
+            assert!(header.contains(&format!("algorithm={algorithm},")));
+            assert!(header.contains(r#"opaque="FQhe/qaU925kfnzjCev0ciny7QMkPqMAFRtzCUYo5tdS""#));
+        }
+    }
+
+    #[test]
+    fn hashes_the_body_for_auth_int() {
+        let challenge = DigestChallenge::parse(
+            r#"Digest realm="testrealm@host.com", qop="auth-int", nonce="dcd98b7102dd2f0e8b11d0f600bfb0c093""#,
+        )
+        .unwrap();
+        let body = br#"{"name":"Ferris"}"#;
+
+        let header = challenge.answer(
+            "Mufasa",
+            "Circle Of Life",
+            "POST",
+            "/users",
+            Some(body),
+            "0a4f113b",
+        );
+
+        let ha1 = md5("Mufasa:testrealm@host.com:Circle Of Life");
+        let ha2 = md5(&format!("POST:/users:{}", md5(r#"{"name":"Ferris"}"#)));
+        let response = md5(&format!(
+            "{ha1}:dcd98b7102dd2f0e8b11d0f600bfb0c093:00000001:0a4f113b:auth-int:{ha2}"
+        ));
+        assert!(
+            header.contains(&format!(r#"response="{response}""#)),
+            "{header}"
+        );
+        assert!(header.contains("qop=auth-int,"));
+    }
+
+    #[test]
+    fn falls_back_to_auth_without_a_body() {
+        let challenge = DigestChallenge::parse(
+            r#"Digest realm="testrealm@host.com", qop="auth-int", nonce="dcd98b7102dd2f0e8b11d0f600bfb0c093""#,
+        )
+        .unwrap();
+        let expected = DigestChallenge {
+            qop: Some(Qop::Auth),
+            ..challenge.clone()
+        };
+
+        let header = challenge.answer("Mufasa", "pw", "POST", "/upload", None, "0a4f113b");
+
+        assert!(header.contains("qop=auth,"), "{header}");
+        assert_eq!(
+            header,
+            expected.answer("Mufasa", "pw", "POST", "/upload", None, "0a4f113b")
+        );
+    }
+
+    #[test]
+    fn answers_without_qop_and_with_sessions() {
+        let legacy = DigestChallenge::parse(r#"Digest realm="r", nonce="n""#).unwrap();
+        let header = legacy.answer("u", "p", "GET", "/", None, "c");
+        let response = md5(&format!("{}:n:{}", md5("u:r:p"), md5("GET:/")));
+        assert_eq!(
+            header,
+            format!(
+                r#"Digest username="u", realm="r", nonce="n", uri="/", algorithm=MD5, response="{response}""#
+            )
+        );
+
+        let session =
+            DigestChallenge::parse(r#"Digest realm="r", nonce="n", qop=auth, algorithm=MD5-sess"#)
+                .unwrap();
+        let header = session.answer("u", "p", "GET", "/", None, "c");
+        let ha1 = md5(&format!("{}:n:c", md5("u:r:p")));
+        let response = md5(&format!("{ha1}:n:00000001:c:auth:{}", md5("GET:/")));
+        assert!(
+            header.contains(&format!(r#"response="{response}""#)),
+            "{header}"
+        );
+        assert!(header.contains("algorithm=MD5-sess,"));
+    }
+
+    #[test]
+    fn uses_a_fresh_cnonce() {
+        let challenge = DigestChallenge::parse(RFC_2617).unwrap();
+        let first = challenge.authorization("u", "p", "GET", "/", Some(b""));
+        let second = challenge.authorization("u", "p", "GET", "/", Some(b""));
+
+        assert_ne!(first, second);
+    }
+}
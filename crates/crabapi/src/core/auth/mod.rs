@@ -0,0 +1,267 @@
+mod digest;
+mod oauth2;
+
+use crate::core::environments::{Variables, placeholders};
+use reqwest::RequestBuilder;
+use serde::{Deserialize, Serialize};
+use std::fmt;
+
+pub use digest::DigestChallenge;
+pub use oauth2::{OAuth2, OAuth2Grant, OAuth2GrantKind, TokenCache};
+
+/// How a request authenticates itself.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum Auth {
+    #[default]
+    None,
+    Basic {
+        username: String,
+        password: String,
+    },
+    Bearer {
+        token: String,
+    },
+    ApiKey {
+        key: String,
+        value: String,
+        #[serde(default)]
+        location: ApiKeyLocation,
+    },
+    /// HTTP Digest: the request is sent once, and retried with credentials computed from the
+    /// server's `WWW-Authenticate` challenge.
+    Digest {
+        username: String,
+        password: String,
+    },
+    /// OAuth 2.0: a token is fetched from the token endpoint, cached, and sent as a Bearer
+    /// header. A 401 response discards the token and retries once with a new one.
+    #[serde(rename = "oauth2")]
+    OAuth2(OAuth2),
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ApiKeyLocation {
+    #[default]
+    Header,
+    Query,
+}
+
+/// The variant of an [`Auth`], without its data.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AuthKind {
+    None,
+    Basic,
+    Bearer,
+    ApiKey,
+    Digest,
+    OAuth2,
+}
+
+impl AuthKind {
+    pub const ALL: [AuthKind; 6] = [
+        AuthKind::None,
+        AuthKind::Basic,
+        AuthKind::Bearer,
+        AuthKind::ApiKey,
+        AuthKind::Digest,
+        AuthKind::OAuth2,
+    ];
+}
+
+impl fmt::Display for AuthKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let name = match self {
+            AuthKind::None => "No Auth",
+            AuthKind::Basic => "Basic",
+            AuthKind::Bearer => "Bearer Token",
+            AuthKind::ApiKey => "API Key",
+            AuthKind::Digest => "Digest",
+            AuthKind::OAuth2 => "OAuth 2.0",
+        };
+        write!(f, "{name}")
+    }
+}
+
+impl fmt::Display for ApiKeyLocation {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ApiKeyLocation::Header => write!(f, "Header"),
+            ApiKeyLocation::Query => write!(f, "Query"),
+        }
+    }
+}
+
+impl Auth {
+    pub fn is_none(&self) -> bool {
+        matches!(self, Auth::None)
+    }
+
+    pub fn kind(&self) -> AuthKind {
+        match self {
+            Auth::None => AuthKind::None,
+            Auth::Basic { .. } => AuthKind::Basic,
+            Auth::Bearer { .. } => AuthKind::Bearer,
+            Auth::ApiKey { .. } => AuthKind::ApiKey,
+            Auth::Digest { .. } => AuthKind::Digest,
+            Auth::OAuth2(_) => AuthKind::OAuth2,
+        }
+    }
+
+    /// Switches to another kind, keeping the username and password where both kinds have one.
+    pub fn with_kind(&self, kind: AuthKind) -> Auth {
+        let (username, password) = match self {
+            Auth::Basic { username, password } | Auth::Digest { username, password } => {
+                (username.clone(), password.clone())
+            }
+            _ => (String::new(), String::new()),
+        };
+
+        match kind {
+            AuthKind::None => Auth::None,
+            AuthKind::Basic => Auth::Basic { username, password },
+            AuthKind::Bearer => Auth::Bearer {
+                token: String::new(),
+            },
+            AuthKind::ApiKey => Auth::ApiKey {
+                key: String::new(),
+                value: String::new(),
+                location: ApiKeyLocation::Header,
+            },
+            AuthKind::Digest => Auth::Digest { username, password },
+            AuthKind::OAuth2 => Auth::OAuth2(OAuth2::default()),
+        }
+    }
+
+    /// Expands `{{name}}` placeholders in every field, collecting unresolved names.
+    pub fn resolve(&self, variables: &Variables, unresolved: &mut Vec<String>) -> Auth {
+        let mut expand = |input: &String| match variables.expand(input) {
+            Ok(output) => output,
+            Err(names) => {
+                for name in names {
+                    if !unresolved.contains(&name) {
+                        unresolved.push(name);
+                    }
+                }
+                input.clone()
+            }
+        };
+
+        match self {
+            Auth::None => Auth::None,
+            Auth::Basic { username, password } => Auth::Basic {
+                username: expand(username),
+                password: expand(password),
+            },
+            Auth::Bearer { token } => Auth::Bearer {
+                token: expand(token),
+            },
+            Auth::ApiKey {
+                key,
+                value,
+                location,
+            } => Auth::ApiKey {
+                key: expand(key),
+                value: expand(value),
+                location: *location,
+            },
+            Auth::Digest { username, password } => Auth::Digest {
+                username: expand(username),
+                password: expand(password),
+            },
+            Auth::OAuth2(oauth2) => Auth::OAuth2(OAuth2 {
+                token_url: expand(&oauth2.token_url),
+                client_id: expand(&oauth2.client_id),
+                client_secret: expand(&oauth2.client_secret),
+                scope: expand(&oauth2.scope),
+                credentials_in_body: oauth2.credentials_in_body,
+                grant: match &oauth2.grant {
+                    OAuth2Grant::ClientCredentials => OAuth2Grant::ClientCredentials,
+                    OAuth2Grant::Password { username, password } => OAuth2Grant::Password {
+                        username: expand(username),
+                        password: expand(password),
+                    },
+                    OAuth2Grant::RefreshToken { refresh_token } => OAuth2Grant::RefreshToken {
+                        refresh_token: expand(refresh_token),
+                    },
+                },
+            }),
+        }
+    }
+
+    /// Copy without the secrets written literally: passwords, tokens, API key values and
+    /// client secrets are emptied unless they only consist of `{{name}}` placeholders, which
+    /// are kept so that the copy still resolves to working credentials.
+    pub fn redacted(&self) -> Auth {
+        let secret = |input: &String| {
+            let mut rest = input.clone();
+            for (range, _) in placeholders(input).into_iter().rev() {
+                rest.replace_range(range, "");
+            }
+            if rest.trim().is_empty() {
+                input.clone()
+            } else {
+                String::new()
+            }
+        };
+
+        match self {
+            Auth::None => Auth::None,
+            Auth::Basic { username, password } => Auth::Basic {
+                username: username.clone(),
+                password: secret(password),
+            },
+            Auth::Bearer { token } => Auth::Bearer {
+                token: secret(token),
+            },
+            Auth::ApiKey {
+                key,
+                value,
+                location,
+            } => Auth::ApiKey {
+                key: key.clone(),
+                value: secret(value),
+                location: *location,
+            },
+            Auth::Digest { username, password } => Auth::Digest {
+                username: username.clone(),
+                password: secret(password),
+            },
+            Auth::OAuth2(oauth2) => Auth::OAuth2(OAuth2 {
+                client_secret: secret(&oauth2.client_secret),
+                grant: match &oauth2.grant {
+                    OAuth2Grant::ClientCredentials => OAuth2Grant::ClientCredentials,
+                    OAuth2Grant::Password { username, password } => OAuth2Grant::Password {
+                        username: username.clone(),
+                        password: secret(password),
+                    },
+                    OAuth2Grant::RefreshToken { refresh_token } => OAuth2Grant::RefreshToken {
+                        refresh_token: secret(refresh_token),
+                    },
+                },
+                ..oauth2.clone()
+            }),
+        }
+    }
+
+    /// Adds the credentials that can be sent up front. Digest needs a challenge first, see
+    /// [`DigestChallenge`], and OAuth 2.0 a token, see [`OAuth2::access_token`].
+    pub fn apply(&self, request: RequestBuilder) -> RequestBuilder {
+        match self {
+            Auth::None | Auth::Digest { .. } | Auth::OAuth2(_) => request,
+            Auth::Basic { username, password } => request.basic_auth(username, Some(password)),
+            Auth::Bearer { token } => request.bearer_auth(token),
+            Auth::ApiKey {
+                key,
+                value,
+                location: ApiKeyLocation::Header,
+            } => request.header(key.as_str(), value.as_str()),
+            Auth::ApiKey {
+                key,
+                value,
+                location: ApiKeyLocation::Query,
+            } => request.query(&[(key, value)]),
+        }
+    }
+}
@@ -0,0 +1,467 @@
+use crate::core::requests::Error;
+use reqwest::Client;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+use tokio::sync::Mutex;
+
+/// Tokens expiring within this margin are refreshed before use.
+const EXPIRY_MARGIN: Duration = Duration::from_secs(30);
+
+/// OAuth 2.0 token endpoint settings. The token is sent as a Bearer header.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct OAuth2 {
+    pub token_url: String,
+    pub client_id: String,
+    #[serde(default)]
+    pub client_secret: String,
+    #[serde(default, skip_serializing_if = "String::is_empty")]
+    pub scope: String,
+    /// Send the client credentials as form fields instead of a Basic auth header.
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub credentials_in_body: bool,
+    #[serde(default)]
+    pub grant: OAuth2Grant,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(tag = "grant_type", rename_all = "snake_case")]
+pub enum OAuth2Grant {
+    #[default]
+    ClientCredentials,
+    Password {
+        username: String,
+        password: String,
+    },
+    RefreshToken {
+        refresh_token: String,
+    },
+}
+
+/// The variant of an [`OAuth2Grant`], without its data.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum OAuth2GrantKind {
+    ClientCredentials,
+    Password,
+    RefreshToken,
+}
+
+/// Access tokens shared by every request of a [`crate::core::requests::Session`], keyed by
+/// environment and token endpoint settings.
+#[derive(Debug, Clone, Default)]
+pub struct TokenCache(Arc<Mutex<HashMap<TokenKey, Token>>>);
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+struct TokenKey {
+    environment: Option<String>,
+    token_url: String,
+    client_id: String,
+    client_secret: String,
+    scope: String,
+    grant: OAuth2Grant,
+}
+
+#[derive(Debug, Clone)]
+struct Token {
+    access_token: String,
+    refresh_token: Option<String>,
+    expires_at: Option<Instant>,
+}
+
+#[derive(Debug, Deserialize)]
+struct TokenResponse {
+    access_token: String,
+    #[serde(default)]
+    refresh_token: Option<String>,
+    #[serde(default)]
+    expires_in: Option<u64>,
+}
+
+#[derive(Debug, Deserialize)]
+struct TokenErrorResponse {
+    error: String,
+    #[serde(default)]
+    error_description: Option<String>,
+}
+
+impl OAuth2GrantKind {
+    pub const ALL: [OAuth2GrantKind; 3] = [
+        OAuth2GrantKind::ClientCredentials,
+        OAuth2GrantKind::Password,
+        OAuth2GrantKind::RefreshToken,
+    ];
+}
+
+impl std::fmt::Display for OAuth2GrantKind {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let name = match self {
+            OAuth2GrantKind::ClientCredentials => "Client Credentials",
+            OAuth2GrantKind::Password => "Password",
+            OAuth2GrantKind::RefreshToken => "Refresh Token",
+        };
+        write!(f, "{name}")
+    }
+}
+
+impl OAuth2Grant {
+    pub fn kind(&self) -> OAuth2GrantKind {
+        match self {
+            OAuth2Grant::ClientCredentials => OAuth2GrantKind::ClientCredentials,
+            OAuth2Grant::Password { .. } => OAuth2GrantKind::Password,
+            OAuth2Grant::RefreshToken { .. } => OAuth2GrantKind::RefreshToken,
+        }
+    }
+
+    pub fn from_kind(kind: OAuth2GrantKind) -> Self {
+        match kind {
+            OAuth2GrantKind::ClientCredentials => OAuth2Grant::ClientCredentials,
+            OAuth2GrantKind::Password => OAuth2Grant::Password {
+                username: String::new(),
+                password: String::new(),
+            },
+            OAuth2GrantKind::RefreshToken => OAuth2Grant::RefreshToken {
+                refresh_token: String::new(),
+            },
+        }
+    }
+}
+
+impl OAuth2 {
+    /// Returns a usable access token, from the cache when it has not expired. With
+    /// `force_refresh` (after a 401) the cached token is discarded first. Whenever a new
+    /// token is needed, the refresh token from the previous one is tried before the grant.
+    pub async fn access_token(
+        &self,
+        client: &Client,
+        cache: &TokenCache,
+        environment: Option<&str>,
+        force_refresh: bool,
+    ) -> Result<String, Error> {
+        let key = TokenKey {
+            environment: environment.map(str::to_string),
+            token_url: self.token_url.clone(),
+            client_id: self.client_id.clone(),
+            client_secret: self.client_secret.clone(),
+            scope: self.scope.clone(),
+            grant: self.grant.clone(),
+        };
+
+        // Held while fetching so concurrent requests wait for a single token request.
+        let mut tokens = cache.0.lock().await;
+        if !force_refresh {
+            if let Some(token) = tokens.get(&key).filter(|token| token.is_fresh()) {
+                return Ok(token.access_token.clone());
+            }
+        }
+
+        let previous = tokens.remove(&key);
+        let refreshed = match previous.and_then(|token| token.refresh_token) {
+            Some(refresh_token) => {
+                let grant = OAuth2Grant::RefreshToken {
+                    refresh_token: refresh_token.clone(),
+                };
+                // Servers may keep the refresh token valid without sending it again.
+                self.request_token(client, &grant)
+                    .await
+                    .ok()
+                    .map(|token| Token {
+                        refresh_token: token.refresh_token.or(Some(refresh_token)),
+                        ..token
+                    })
+            }
+            None => None,
+        };
+        let token = match refreshed {
+            Some(token) => token,
+            None => self.request_token(client, &self.grant).await?,
+        };
+
+        let access_token = token.access_token.clone();
+        tokens.insert(key, token);
+        Ok(access_token)
+    }
+
+    async fn request_token(&self, client: &Client, grant: &OAuth2Grant) -> Result<Token, Error> {
+        let mut form: Vec<(&str, &str)> = vec![];
+        match grant {
+            OAuth2Grant::ClientCredentials => form.push(("grant_type", "client_credentials")),
+            OAuth2Grant::Password { username, password } => {
+                form.push(("grant_type", "password"));
+                form.push(("username", username));
+                form.push(("password", password));
+            }
+            OAuth2Grant::RefreshToken { refresh_token } => {
+                form.push(("grant_type", "refresh_token"));
+                form.push(("refresh_token", refresh_token));
+            }
+        }
+        if !self.scope.is_empty() {
+            form.push(("scope", &self.scope));
+        }
+
+        let mut request = client.post(&self.token_url);
+        if self.credentials_in_body {
+            form.push(("client_id", &self.client_id));
+            form.push(("client_secret", &self.client_secret));
+        } else {
+            request = request.basic_auth(&self.client_id, Some(&self.client_secret));
+        }
+
+        let started = Instant::now();
+        let response = request
+            .header(http::header::ACCEPT, "application/json")
+            .form(&form)
+            .send()
+            .await?;
+        let status = response.status();
+        let body = response.bytes().await?;
+
+        if !status.is_success() {
+            let reason = match serde_json::from_slice::<TokenErrorResponse>(&body) {
+                Ok(error) => match error.error_description {
+                    Some(description) => format!("{} ({description})", error.error),
+                    None => error.error,
+                },
+                Err(_) => status.to_string(),
+            };
+            return Err(self.error(reason));
+        }
+
+        let token: TokenResponse = serde_json::from_slice(&body)
+            .map_err(|error| self.error(format!("invalid token response: {error}")))?;
+        Ok(Token {
+            access_token: token.access_token,
+            refresh_token: token.refresh_token,
+            expires_at: token
+                .expires_in
+                .map(|seconds| started + Duration::from_secs(seconds)),
+        })
+    }
+
+    fn error(&self, reason: String) -> Error {
+        Error::OAuth2 {
+            token_url: self.token_url.clone(),
+            reason,
+        }
+    }
+}
+
+impl Token {
+    fn is_fresh(&self) -> bool {
+        self.expires_at
+            .is_none_or(|expires_at| Instant::now() + EXPIRY_MARGIN < expires_at)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::auth::Auth;
+    use crate::core::requests::{RequestSpec, Session, send_request};
+    use crate::core::test_server::{StubRequest, StubServer};
+    use http::{Method, StatusCode};
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    fn oauth2(server: &StubServer) -> OAuth2 {
+        OAuth2 {
+            token_url: format!("{}/token", server.url),
+            client_id: "client".to_string(),
+            client_secret: "secret".to_string(),
+            ..OAuth2::default()
+        }
+    }
+
+    fn token(access_token: &str, extra: &str) -> (u16, String) {
+        (
+            200,
+            format!(r#"{{"access_token":"{access_token}"{extra}}}"#),
+        )
+    }
+
+    /// Hands out `t1`, `t2`, ... with the given extra JSON fields.
+    async fn token_server(extra: &'static str) -> StubServer {
+        let issued = AtomicUsize::new(0);
+        StubServer::start(move |_| {
+            let number = issued.fetch_add(1, Ordering::SeqCst) + 1;
+            token(&format!("t{number}"), extra)
+        })
+        .await
+    }
+
+    #[tokio::test]
+    async fn fetches_client_credentials_token() {
+        let server = token_server(r#","expires_in":3600"#).await;
+        let token = oauth2(&server)
+            .access_token(&Client::new(), &TokenCache::default(), None, false)
+            .await
+            .unwrap();
+
+        assert_eq!(token, "t1");
+        let requests = server.requests();
+        assert_eq!(requests.len(), 1);
+        assert_eq!(requests[0].method, "POST");
+        assert_eq!(requests[0].path, "/token");
+        assert_eq!(requests[0].body, "grant_type=client_credentials");
+        // base64("client:secret")
+        assert_eq!(
+            requests[0].header("authorization"),
+            Some("Basic Y2xpZW50OnNlY3JldA==")
+        );
+    }
+
+    #[tokio::test]
+    async fn sends_credentials_in_body() {
+        let server = token_server("").await;
+        let oauth2 = OAuth2 {
+            credentials_in_body: true,
+            scope: "read write".to_string(),
+            grant: OAuth2Grant::Password {
+                username: "user".to_string(),
+                password: "p&ss".to_string(),
+            },
+            ..oauth2(&server)
+        };
+        oauth2
+            .access_token(&Client::new(), &TokenCache::default(), None, false)
+            .await
+            .unwrap();
+
+        let request = &server.requests()[0];
+        assert_eq!(
+            request.body,
+            "grant_type=password&username=user&password=p%26ss&scope=read+write\
+             &client_id=client&client_secret=secret"
+        );
+        assert_eq!(request.header("authorization"), None);
+    }
+
+    #[tokio::test]
+    async fn reuses_cached_token_before_expiry() {
+        let server = token_server(r#","expires_in":3600"#).await;
+        let oauth2 = oauth2(&server);
+        let (client, cache) = (Client::new(), TokenCache::default());
+
+        let first = oauth2.access_token(&client, &cache, None, false).await;
+        let second = oauth2.access_token(&client, &cache, None, false).await;
+
+        assert_eq!(first.unwrap(), "t1");
+        assert_eq!(second.unwrap(), "t1");
+        assert_eq!(server.requests().len(), 1);
+    }
+
+    #[tokio::test]
+    async fn fetches_again_within_expiry_margin() {
+        let server = token_server(r#","expires_in":10"#).await;
+        let oauth2 = oauth2(&server);
+        let (client, cache) = (Client::new(), TokenCache::default());
+
+        oauth2
+            .access_token(&client, &cache, None, false)
+            .await
+            .unwrap();
+        let second = oauth2.access_token(&client, &cache, None, false).await;
+
+        assert_eq!(second.unwrap(), "t2");
+        assert_eq!(server.requests().len(), 2);
+    }
+
+    #[tokio::test]
+    async fn caches_tokens_per_environment_and_secret() {
+        let server = token_server("").await;
+        let oauth2 = oauth2(&server);
+        let other_secret = OAuth2 {
+            client_secret: "other".to_string(),
+            ..oauth2.clone()
+        };
+        let (client, cache) = (Client::new(), TokenCache::default());
+
+        let first = oauth2.access_token(&client, &cache, None, false).await;
+        let secret = other_secret
+            .access_token(&client, &cache, None, false)
+            .await;
+        let environment = oauth2
+            .access_token(&client, &cache, Some("dev"), false)
+            .await;
+        let again = oauth2.access_token(&client, &cache, None, false).await;
+
+        assert_eq!(first.unwrap(), "t1");
+        assert_eq!(secret.unwrap(), "t2");
+        assert_eq!(environment.unwrap(), "t3");
+        assert_eq!(again.unwrap(), "t1");
+    }
+
+    #[tokio::test]
+    async fn refresh_keeps_refresh_token_when_none_is_returned() {
+        let issued = AtomicUsize::new(0);
+        let server = StubServer::start(move |_| match issued.fetch_add(1, Ordering::SeqCst) {
+            0 => token("t1", r#","refresh_token":"r1""#),
+            number => token(&format!("t{}", number + 1), ""),
+        })
+        .await;
+        let oauth2 = oauth2(&server);
+        let (client, cache) = (Client::new(), TokenCache::default());
+
+        for force_refresh in [false, true, true] {
+            oauth2
+                .access_token(&client, &cache, None, force_refresh)
+                .await
+                .unwrap();
+        }
+
+        let bodies: Vec<String> = server.requests().into_iter().map(|r| r.body).collect();
+        assert_eq!(
+            bodies,
+            [
+                "grant_type=client_credentials",
+                "grant_type=refresh_token&refresh_token=r1",
+                "grant_type=refresh_token&refresh_token=r1",
+            ]
+        );
+    }
+
+    #[tokio::test]
+    async fn reports_token_error_response() {
+        let server = StubServer::start(|_| {
+            let body = r#"{"error":"invalid_client","error_description":"bad secret"}"#;
+            (401, body.to_string())
+        })
+        .await;
+        let result = oauth2(&server)
+            .access_token(&Client::new(), &TokenCache::default(), None, false)
+            .await;
+
+        match result {
+            Err(Error::OAuth2 { token_url, reason }) => {
+                assert_eq!(token_url, format!("{}/token", server.url));
+                assert_eq!(reason, "invalid_client (bad secret)");
+            }
+            other => panic!("expected an OAuth 2.0 error, got {other:?}"),
+        }
+    }
+
+    #[tokio::test]
+    async fn send_request_refreshes_token_and_retries_on_401() {
+        let issued = AtomicUsize::new(0);
+        let server = StubServer::start(move |request: &StubRequest| {
+            if request.path == "/token" {
+                let number = issued.fetch_add(1, Ordering::SeqCst) + 1;
+                return token(&format!("t{number}"), r#","expires_in":3600"#);
+            }
+            // The first token was revoked by the server.
+            match request.header("authorization") {
+                Some("Bearer t2") => (200, r#"{"ok":true}"#.to_string()),
+                _ => (401, "{}".to_string()),
+            }
+        })
+        .await;
+        let mut request = RequestSpec::new(Method::GET, format!("{}/api", server.url));
+        request.auth = Auth::OAuth2(oauth2(&server));
+
+        let response = send_request(&Session::default(), &request).await.unwrap();
+
+        assert_eq!(response.status, StatusCode::OK);
+        let paths: Vec<String> = server.requests().into_iter().map(|r| r.path).collect();
+        assert_eq!(paths, ["/token", "/api", "/token", "/api"]);
+    }
+}
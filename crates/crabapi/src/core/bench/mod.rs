@@ -0,0 +1,202 @@
+//! Load testing: sends one request many times through [`send_requests`], either from a
+//! fixed number of concurrent workers or at a target rate, and summarizes the latencies.
+
+mod report;
+
+use crate::core::requests::{
+    Error, RequestSpec, ResponseRecord, Session, send_requests, wait_response,
+};
+use std::sync::Arc;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::time::{Duration, Instant};
+use tokio::task::{JoinHandle, JoinSet};
+
+pub use report::Report;
+
+/// Highest rate of [`Load::Rate`], in requests per second; higher rates are lowered to it.
+pub const MAX_RATE: f64 = 1e6;
+
+/// How requests are sent.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Load {
+    /// `concurrency` workers, each sending its next request as soon as the previous one
+    /// completes, until `requests` were sent in total.
+    Fixed { concurrency: usize, requests: usize },
+    /// `rate` requests per second for `duration`, whatever the response times. `rate` must
+    /// be positive and is at most [`MAX_RATE`].
+    Rate { rate: f64, duration: Duration },
+}
+
+/// Result of one request.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Sample {
+    pub latency: Duration,
+    /// The status code, or the error that prevented a response.
+    pub outcome: Result<u16, String>,
+}
+
+/// A load test of a single request.
+#[derive(Debug, Clone)]
+pub struct Bench {
+    session: Session,
+    request: RequestSpec,
+    load: Load,
+    ramp_up: Duration,
+}
+
+impl Bench {
+    pub fn new(session: Session, request: RequestSpec, load: Load) -> Self {
+        Self {
+            session,
+            request,
+            load,
+            ramp_up: Duration::ZERO,
+        }
+    }
+
+    /// Starts the workers one after the other, or raises the rate linearly, over `ramp_up`.
+    pub fn with_ramp_up(mut self, ramp_up: Duration) -> Self {
+        self.ramp_up = ramp_up;
+        self
+    }
+
+    pub async fn run(&self) -> Report {
+        let started = Instant::now();
+        let samples = match self.load {
+            Load::Fixed {
+                concurrency,
+                requests,
+            } => self.run_fixed(concurrency.max(1), requests).await,
+            Load::Rate { rate, duration } => self.run_rate(rate, duration).await,
+        };
+        Report::new(samples, started.elapsed())
+    }
+
+    async fn run_fixed(&self, concurrency: usize, requests: usize) -> Vec<Sample> {
+        let sent = Arc::new(AtomicUsize::new(0));
+        let mut workers = vec![];
+        for worker in 0..concurrency {
+            let bench = self.clone();
+            let sent = sent.clone();
+            let delay = self.ramp_up.mul_f64(worker as f64 / concurrency as f64);
+            workers.push(tokio::spawn(async move {
+                tokio::time::sleep(delay).await;
+                let mut samples = vec![];
+                while sent.fetch_add(1, Ordering::Relaxed) < requests {
+                    let started = Instant::now();
+                    samples.push(sample(bench.send(), started).await);
+                }
+                samples
+            }));
+        }
+
+        let mut samples = vec![];
+        for worker in workers {
+            samples.extend(worker.await.unwrap_or_default());
+        }
+        samples
+    }
+
+    async fn run_rate(&self, rate: f64, duration: Duration) -> Vec<Sample> {
+        let started = tokio::time::Instant::now();
+        let rate = rate.min(MAX_RATE);
+        let ramp_up = self.ramp_up.as_secs_f64();
+        // During the ramp up the rate starts at a tenth of the target, not at zero.
+        let rate_at = |elapsed: f64| {
+            if elapsed < ramp_up {
+                rate * (elapsed / ramp_up).max(0.1)
+            } else {
+                rate
+            }
+        };
+
+        let mut pending = JoinSet::new();
+        let mut samples = vec![];
+        let mut next = started;
+        while next.duration_since(started) < duration {
+            tokio::time::sleep_until(next).await;
+            let sent = Instant::now();
+            let handle = self.send();
+            pending.spawn(sample(handle, sent));
+            // Long tests would otherwise keep every finished task around until the end.
+            while let Some(finished) = pending.try_join_next() {
+                samples.extend(finished.ok());
+            }
+            let elapsed = next.duration_since(started).as_secs_f64();
+            next += interval(rate_at(elapsed), duration);
+        }
+
+        while let Some(finished) = pending.join_next().await {
+            samples.extend(finished.ok());
+        }
+        samples
+    }
+
+    fn send(&self) -> JoinHandle<Result<ResponseRecord, Error>> {
+        send_requests(&self.session, vec![self.request.clone()]).remove(0)
+    }
+}
+
+/// Time between two requests at `rate`, between a nanosecond and `duration`, so that a rate
+/// too low to express sends a single request.
+fn interval(rate: f64, duration: Duration) -> Duration {
+    let floor = Duration::from_nanos(1);
+    Duration::try_from_secs_f64(1.0 / rate)
+        .unwrap_or(Duration::MAX)
+        .clamp(floor, duration.max(floor))
+}
+
+async fn sample(handle: JoinHandle<Result<ResponseRecord, Error>>, started: Instant) -> Sample {
+    let outcome = wait_response(handle)
+        .await
+        .map(|response| response.status.as_u16())
+        .map_err(|error| error.to_string());
+    Sample {
+        latency: started.elapsed(),
+        outcome,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::requests::Method;
+    use crate::core::test_server::{StubRequest, StubServer};
+    use std::collections::BTreeMap;
+
+    #[test]
+    fn interval_stays_within_bounds() {
+        let duration = Duration::from_secs(10);
+
+        assert_eq!(interval(4.0, duration), Duration::from_millis(250));
+        assert_eq!(interval(1e-20, duration), duration);
+        assert_eq!(interval(f64::MIN_POSITIVE, duration), duration);
+        assert_eq!(interval(1e10, duration), Duration::from_nanos(1));
+        assert_eq!(interval(1.0, Duration::ZERO), Duration::from_nanos(1));
+    }
+
+    async fn bench(rate: f64, duration: Duration) -> (StubServer, Report) {
+        let server = StubServer::start(|_: &StubRequest| (200, "{}".to_string())).await;
+        let request = RequestSpec::new(Method::GET, server.url.clone());
+        let load = Load::Rate { rate, duration };
+        let report = Bench::new(Session::default(), request, load).run().await;
+        (server, report)
+    }
+
+    #[tokio::test]
+    async fn tiny_rate_sends_a_single_request() {
+        let (server, report) = bench(1e-20, Duration::from_millis(50)).await;
+
+        assert_eq!(report.statuses, BTreeMap::from([(200, 1)]));
+        assert_eq!(server.requests().len(), 1);
+    }
+
+    #[tokio::test]
+    async fn rate_paces_requests() {
+        let (server, report) = bench(100.0, Duration::from_millis(100)).await;
+
+        assert_eq!(report.statuses, BTreeMap::from([(200, 10)]));
+        assert!(report.errors.is_empty());
+        assert_eq!(server.requests().len(), 10);
+    }
+}
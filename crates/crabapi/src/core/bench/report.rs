@@ -0,0 +1,301 @@
+use super::Sample;
+use serde_json::{Map, Value, json};
+use std::collections::BTreeMap;
+use std::fmt;
+use std::time::Duration;
+
+const HISTOGRAM_BUCKETS: usize = 10;
+const HISTOGRAM_WIDTH: usize = 40;
+
+/// Latency distribution, throughput, status codes and errors of a load test.
+#[derive(Debug, Clone)]
+pub struct Report {
+    /// Every request's latency, fastest first.
+    latencies: Vec<Duration>,
+    /// Wall time of the whole test.
+    pub duration: Duration,
+    pub statuses: BTreeMap<u16, usize>,
+    /// Error messages and how often they occurred.
+    pub errors: BTreeMap<String, usize>,
+}
+
+impl Report {
+    pub fn new(samples: Vec<Sample>, duration: Duration) -> Self {
+        let mut report = Self {
+            latencies: Vec::with_capacity(samples.len()),
+            duration,
+            statuses: BTreeMap::new(),
+            errors: BTreeMap::new(),
+        };
+        for sample in samples {
+            report.latencies.push(sample.latency);
+            match sample.outcome {
+                Ok(status) => *report.statuses.entry(status).or_default() += 1,
+                Err(error) => *report.errors.entry(error).or_default() += 1,
+            }
+        }
+        report.latencies.sort();
+        report
+    }
+
+    pub fn requests(&self) -> usize {
+        self.latencies.len()
+    }
+
+    /// Completed requests per second.
+    pub fn throughput(&self) -> f64 {
+        if self.duration.is_zero() {
+            return 0.0;
+        }
+        self.requests() as f64 / self.duration.as_secs_f64()
+    }
+
+    /// Latency below which `percentile` percent of the requests completed (nearest rank).
+    pub fn percentile(&self, percentile: f64) -> Duration {
+        if self.latencies.is_empty() {
+            return Duration::ZERO;
+        }
+        let rank = (percentile / 100.0 * self.latencies.len() as f64).ceil() as usize;
+        self.latencies[rank.clamp(1, self.latencies.len()) - 1]
+    }
+
+    pub fn min(&self) -> Duration {
+        self.latencies.first().copied().unwrap_or_default()
+    }
+
+    pub fn max(&self) -> Duration {
+        self.latencies.last().copied().unwrap_or_default()
+    }
+
+    pub fn mean(&self) -> Duration {
+        match self.requests() {
+            0 => Duration::ZERO,
+            requests => self.latencies.iter().sum::<Duration>() / requests as u32,
+        }
+    }
+
+    /// Request counts in equal latency buckets between the fastest and the slowest request,
+    /// each given by its upper bound.
+    pub fn histogram(&self) -> Vec<(Duration, usize)> {
+        if self.latencies.is_empty() {
+            return vec![];
+        }
+        let (min, max) = (self.min(), self.max());
+        let width = (max - min) / HISTOGRAM_BUCKETS as u32;
+        if width.is_zero() {
+            return vec![(max, self.requests())];
+        }
+
+        let mut buckets: Vec<(Duration, usize)> = (1..=HISTOGRAM_BUCKETS)
+            .map(|bucket| (min + width * bucket as u32, 0))
+            .collect();
+        // Rounding can leave the slowest request just above the last bound.
+        buckets[HISTOGRAM_BUCKETS - 1].0 = max;
+        for latency in &self.latencies {
+            let bucket = buckets
+                .iter_mut()
+                .find(|(bound, _)| latency <= bound)
+                .expect("the last bucket ends at the maximum");
+            bucket.1 += 1;
+        }
+        buckets
+    }
+
+    pub fn to_json(&self) -> Result<String, serde_json::Error> {
+        let histogram: Vec<Value> = self
+            .histogram()
+            .into_iter()
+            .map(|(bound, count)| json!({ "le_ms": milliseconds(bound), "count": count }))
+            .collect();
+        let statuses: Map<String, Value> = self
+            .statuses
+            .iter()
+            .map(|(status, count)| (status.to_string(), json!(count)))
+            .collect();
+        let report = json!({
+            "requests": self.requests(),
+            "duration_ms": milliseconds(self.duration),
+            "throughput": self.throughput(),
+            "latency_ms": {
+                "min": milliseconds(self.min()),
+                "mean": milliseconds(self.mean()),
+                "p50": milliseconds(self.percentile(50.0)),
+                "p90": milliseconds(self.percentile(90.0)),
+                "p99": milliseconds(self.percentile(99.0)),
+                "max": milliseconds(self.max()),
+            },
+            "histogram": histogram,
+            "status_codes": statuses,
+            "errors": self.errors,
+        });
+        serde_json::to_string_pretty(&report)
+    }
+}
+
+fn milliseconds(duration: Duration) -> f64 {
+    duration.as_secs_f64() * 1000.0
+}
+
+fn format_ms(duration: Duration) -> String {
+    format!("{:.2} ms", milliseconds(duration))
+}
+
+/// Laid out like `hey`: summary, latencies, histogram, status codes, errors.
+impl fmt::Display for Report {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(f, "Summary:")?;
+        writeln!(f, "\tRequests:\t{}", self.requests())?;
+        writeln!(f, "\tDuration:\t{:.3} s", self.duration.as_secs_f64())?;
+        writeln!(f, "\tThroughput:\t{:.1} req/s", self.throughput())?;
+
+        writeln!(f, "\nLatency:")?;
+        for (name, latency) in [
+            ("min", self.min()),
+            ("mean", self.mean()),
+            ("p50", self.percentile(50.0)),
+            ("p90", self.percentile(90.0)),
+            ("p99", self.percentile(99.0)),
+            ("max", self.max()),
+        ] {
+            writeln!(f, "\t{name}\t{}", format_ms(latency))?;
+        }
+
+        let histogram = self.histogram();
+        let largest = histogram.iter().map(|(_, count)| *count).max().unwrap_or(0);
+        if largest > 0 {
+            writeln!(f, "\nHistogram:")?;
+            for (bound, count) in histogram {
+                let bar = "■".repeat(count * HISTOGRAM_WIDTH / largest);
+                writeln!(f, "\t{:>10} [{count}]\t{bar}", format_ms(bound))?;
+            }
+        }
+
+        if !self.statuses.is_empty() {
+            writeln!(f, "\nStatus codes:")?;
+            for (status, count) in &self.statuses {
+                writeln!(f, "\t{status}: {count}")?;
+            }
+        }
+        if !self.errors.is_empty() {
+            writeln!(f, "\nErrors:")?;
+            for (error, count) in &self.errors {
+                writeln!(f, "\t{count}\t{error}")?;
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn ms(milliseconds: u64) -> Duration {
+        Duration::from_millis(milliseconds)
+    }
+
+    fn from_latencies(latencies: impl IntoIterator<Item = u64>) -> Report {
+        let samples = latencies
+            .into_iter()
+            .map(|latency| Sample {
+                latency: ms(latency),
+                outcome: Ok(200),
+            })
+            .collect();
+        Report::new(samples, Duration::from_secs(2))
+    }
+
+    #[test]
+    fn empty_report_is_all_zero() {
+        let report = Report::new(vec![], Duration::ZERO);
+
+        assert_eq!(report.requests(), 0);
+        assert_eq!(report.throughput(), 0.0);
+        assert_eq!(report.percentile(50.0), Duration::ZERO);
+        assert_eq!(report.mean(), Duration::ZERO);
+        assert_eq!(
+            (report.min(), report.max()),
+            (Duration::ZERO, Duration::ZERO)
+        );
+        assert!(report.histogram().is_empty());
+        assert!(!report.to_string().contains("Histogram"));
+    }
+
+    #[test]
+    fn single_sample_is_every_statistic() {
+        let report = from_latencies([42]);
+
+        for percentile in [0.0, 50.0, 99.0, 100.0] {
+            assert_eq!(report.percentile(percentile), ms(42));
+        }
+        assert_eq!(report.mean(), ms(42));
+        assert_eq!(report.histogram(), [(ms(42), 1)]);
+        assert_eq!(report.throughput(), 0.5);
+    }
+
+    #[test]
+    fn percentiles_use_the_nearest_rank() {
+        // 1 to 100 ms, shuffled: the nth percentile is n ms.
+        let report = from_latencies((1..=100).map(|n| n * 37 % 101));
+
+        assert_eq!(report.min(), ms(1));
+        assert_eq!(report.percentile(50.0), ms(50));
+        assert_eq!(report.percentile(95.0), ms(95));
+        assert_eq!(report.percentile(99.0), ms(99));
+        assert_eq!(report.percentile(100.0), ms(100));
+        assert_eq!(report.mean(), Duration::from_micros(50_500));
+
+        let report = from_latencies([10, 20, 30, 40]);
+        assert_eq!(report.percentile(50.0), ms(20));
+        assert_eq!(report.percentile(51.0), ms(30));
+        assert_eq!(report.percentile(95.0), ms(40));
+        assert_eq!(report.mean(), ms(25));
+    }
+
+    #[test]
+    fn histogram_spreads_latencies_over_equal_buckets() {
+        let report = from_latencies([0, 5, 10, 10, 55, 99, 100]);
+        let histogram = report.histogram();
+
+        assert_eq!(histogram.len(), HISTOGRAM_BUCKETS);
+        let bounds: Vec<Duration> = histogram.iter().map(|(bound, _)| *bound).collect();
+        assert_eq!(bounds, (1..=10).map(|n| ms(n * 10)).collect::<Vec<_>>());
+        let counts: Vec<usize> = histogram.iter().map(|(_, count)| *count).collect();
+        assert_eq!(counts, [4, 0, 0, 0, 0, 1, 0, 0, 0, 2]);
+
+        assert_eq!(from_latencies([7, 7, 7]).histogram(), [(ms(7), 3)]);
+    }
+
+    #[test]
+    fn counts_statuses_and_errors() {
+        let samples = vec![
+            Sample {
+                latency: ms(1),
+                outcome: Ok(200),
+            },
+            Sample {
+                latency: ms(2),
+                outcome: Ok(500),
+            },
+            Sample {
+                latency: ms(3),
+                outcome: Ok(200),
+            },
+            Sample {
+                latency: ms(4),
+                outcome: Err("timed out".to_string()),
+            },
+        ];
+        let report = Report::new(samples, Duration::from_secs(1));
+
+        assert_eq!(report.requests(), 4);
+        assert_eq!(report.statuses, BTreeMap::from([(200, 2), (500, 1)]));
+        assert_eq!(
+            report.errors,
+            BTreeMap::from([("timed out".to_string(), 1)])
+        );
+        let json: Value = serde_json::from_str(&report.to_json().unwrap()).unwrap();
+        assert_eq!(json["status_codes"], json!({"200": 2, "500": 1}));
+        assert_eq!(json["latency_ms"]["p50"], json!(2.0));
+    }
+}
@@ -0,0 +1,86 @@
+use super::{Snippet, shell_quote};
+use crate::core::requests::{BodySpec, Method, PartValue};
+use url::form_urlencoded;
+
+pub(super) fn render(snippet: &Snippet) -> String {
+    let mut first = vec!["curl".to_string()];
+    match (snippet.method, snippet.body) {
+        (&Method::GET, BodySpec::Empty) => {}
+        (&Method::HEAD, BodySpec::Empty) => first.push("--head".to_string()),
+        // With a body and no -X, curl sends a POST.
+        (method, _) => first.push(format!("-X {}", shell_quote(method.as_str()))),
+    }
+    first.push(shell_quote(&snippet.url));
+
+    let mut args = vec![];
+    for (name, value) in &snippet.headers {
+        args.push(format!("-H {}", shell_quote(&format!("{name}: {value}"))));
+    }
+    if let Some(credentials) = &snippet.credentials {
+        if credentials.digest {
+            args.push("--digest".to_string());
+        }
+        let user = format!("{}:{}", credentials.username, credentials.password);
+        args.push(format!("-u {}", shell_quote(&user)));
+    }
+
+    match snippet.body {
+        BodySpec::Empty => {}
+        BodySpec::Text { text } => args.push(format!("--data-raw {}", shell_quote(text))),
+        BodySpec::File { path } => args.push(format!(
+            "--data-binary {}",
+            shell_quote(&format!("@{}", path.display()))
+        )),
+        BodySpec::Multipart { parts } => {
+            for part in parts {
+                let mut value = match &part.value {
+                    // --form-string does not treat a leading @ or < as a file.
+                    PartValue::Text { text } if part.content_type.is_none() => {
+                        args.push(format!(
+                            "--form-string {}",
+                            shell_quote(&format!("{}={text}", part.name))
+                        ));
+                        continue;
+                    }
+                    PartValue::Text { text } => format!("{}={text}", part.name),
+                    PartValue::File { path } => format!("{}=@{}", part.name, path.display()),
+                };
+                if let Some(filename) = &part.filename {
+                    value.push_str(&format!(";filename={filename}"));
+                }
+                if let Some(content_type) = &part.content_type {
+                    value.push_str(&format!(";type={content_type}"));
+                }
+                args.push(format!("-F {}", shell_quote(&value)));
+            }
+        }
+        // --data-urlencode encodes the value only.
+        BodySpec::Form { fields } => {
+            for field in fields.iter().filter(|field| field.enabled) {
+                let key: String = form_urlencoded::byte_serialize(field.key.as_bytes()).collect();
+                args.push(format!(
+                    "--data-urlencode {}",
+                    shell_quote(&format!("{key}={}", field.value))
+                ));
+            }
+        }
+    }
+
+    let options = snippet.options;
+    if options.compressed {
+        args.push("--compressed".to_string());
+    }
+    if options.insecure {
+        args.push("--insecure".to_string());
+    }
+    if options.follow_redirects {
+        args.push("--location".to_string());
+    }
+    if let Some(timeout) = options.timeout() {
+        args.push(format!("--max-time {}", timeout.as_secs_f64()));
+    }
+
+    let mut lines = vec![first.join(" ")];
+    lines.extend(args);
+    lines.join(" \\\n  ")
+}
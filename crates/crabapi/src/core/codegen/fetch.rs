@@ -0,0 +1,129 @@
+use super::{Snippet, string_literal};
+use crate::core::requests::{BodySpec, PartValue};
+
+pub(super) fn render(snippet: &Snippet) -> String {
+    let mut imports = vec![];
+    let mut code = vec![];
+    let mut comments = vec![];
+
+    let mut headers: Vec<String> = snippet
+        .joined_headers()
+        .iter()
+        .map(|(name, value)| format!("    {}: {},", string_literal(name), string_literal(value)))
+        .collect();
+    if let Some(credentials) = &snippet.credentials {
+        if credentials.digest {
+            comments.push(
+                "// fetch has no Digest auth: answer the WWW-Authenticate challenge yourself.",
+            );
+        } else {
+            let user = format!("{}:{}", credentials.username, credentials.password);
+            headers.push(format!(
+                "    \"Authorization\": \"Basic \" + btoa({}),",
+                string_literal(&user)
+            ));
+        }
+    }
+
+    let mut init = vec![format!(
+        "  method: {},",
+        string_literal(snippet.method.as_str())
+    )];
+    if !headers.is_empty() {
+        init.push(format!("  headers: {{\n{}\n  }},", headers.join("\n")));
+    }
+
+    match snippet.body {
+        BodySpec::Empty => {}
+        BodySpec::Text { text } => init.push(format!("  body: {},", string_literal(text))),
+        BodySpec::File { path } => {
+            imports.push("import { readFile } from \"node:fs/promises\";");
+            init.push(format!(
+                "  body: await readFile({}),",
+                string_literal(&path.to_string_lossy())
+            ));
+        }
+        BodySpec::Multipart { parts } => {
+            code.push("const form = new FormData();".to_string());
+            for part in parts {
+                let options = match &part.content_type {
+                    Some(content_type) => format!(", {{ type: {} }}", string_literal(content_type)),
+                    None => String::new(),
+                };
+                let value = match &part.value {
+                    PartValue::Text { text } if part.content_type.is_none() => string_literal(text),
+                    PartValue::Text { text } => {
+                        format!("new Blob([{}]{options})", string_literal(text))
+                    }
+                    PartValue::File { path } => {
+                        if !imports.contains(&"import { openAsBlob } from \"node:fs\";") {
+                            imports.push("import { openAsBlob } from \"node:fs\";");
+                        }
+                        format!(
+                            "await openAsBlob({}{options})",
+                            string_literal(&path.to_string_lossy())
+                        )
+                    }
+                };
+                let filename = match (&part.filename, &part.value) {
+                    (Some(filename), _) => format!(", {}", string_literal(filename)),
+                    (None, PartValue::File { path }) => path
+                        .file_name()
+                        .map(|name| format!(", {}", string_literal(&name.to_string_lossy())))
+                        .unwrap_or_default(),
+                    (None, PartValue::Text { .. }) => String::new(),
+                };
+                code.push(format!(
+                    "form.append({}, {value}{filename});",
+                    string_literal(&part.name)
+                ));
+            }
+            init.push("  body: form,".to_string());
+        }
+        BodySpec::Form { fields } => {
+            code.push("const form = new URLSearchParams();".to_string());
+            for field in fields.iter().filter(|field| field.enabled) {
+                code.push(format!(
+                    "form.append({}, {});",
+                    string_literal(&field.key),
+                    string_literal(&field.value)
+                ));
+            }
+            init.push("  body: form,".to_string());
+        }
+    }
+
+    let options = snippet.options;
+    if !options.follow_redirects {
+        init.push("  redirect: \"manual\",".to_string());
+    }
+    if let Some(timeout) = options.timeout() {
+        init.push(format!(
+            "  signal: AbortSignal.timeout({}),",
+            timeout.as_millis()
+        ));
+    }
+    if options.insecure {
+        comments.push("// fetch cannot skip TLS certificate checks.");
+    }
+
+    let mut sections = vec![];
+    if !imports.is_empty() {
+        sections.push(imports.join("\n"));
+    }
+    if !code.is_empty() {
+        sections.push(code.join("\n"));
+    }
+    let mut request = comments.join("\n");
+    if !request.is_empty() {
+        request.push('\n');
+    }
+    request.push_str(&format!(
+        "const response = await fetch({}, {{\n{}\n}});",
+        string_literal(&snippet.url),
+        init.join("\n")
+    ));
+    sections.push(request);
+    sections.push("console.log(response.status);\nconsole.log(await response.text());".to_string());
+    sections.join("\n\n")
+}
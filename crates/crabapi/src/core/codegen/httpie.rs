@@ -0,0 +1,68 @@
+use super::{Snippet, shell_quote};
+use crate::core::requests::{BodySpec, PartValue};
+
+pub(super) fn render(snippet: &Snippet) -> String {
+    let options = snippet.options;
+    let mut flags = vec!["http".to_string()];
+    if options.follow_redirects {
+        flags.push("--follow".to_string());
+    }
+    if options.insecure {
+        flags.push("--verify=no".to_string());
+    }
+    if let Some(timeout) = options.timeout() {
+        flags.push(format!("--timeout={}", timeout.as_secs_f64()));
+    }
+    if let Some(credentials) = &snippet.credentials {
+        if credentials.digest {
+            flags.push("--auth-type=digest".to_string());
+        }
+        let user = format!("{}:{}", credentials.username, credentials.password);
+        flags.push(format!("--auth={}", shell_quote(&user)));
+    }
+
+    let mut items = vec![];
+    let mut redirect = None;
+    match snippet.body {
+        BodySpec::Empty => {}
+        BodySpec::Text { text } => flags.push(format!("--raw={}", shell_quote(text))),
+        BodySpec::File { path } => redirect = Some(shell_quote(&path.to_string_lossy())),
+        BodySpec::Multipart { parts } => {
+            flags.push("--multipart".to_string());
+            for part in parts {
+                let mut item = match &part.value {
+                    PartValue::Text { text } => format!("{}={text}", part.name),
+                    PartValue::File { path } => format!("{}@{}", part.name, path.display()),
+                };
+                if let Some(content_type) = &part.content_type {
+                    item.push_str(&format!(";type={content_type}"));
+                }
+                items.push(shell_quote(&item));
+            }
+        }
+        BodySpec::Form { fields } => {
+            flags.push("--form".to_string());
+            for field in fields.iter().filter(|field| field.enabled) {
+                items.push(shell_quote(&format!("{}={}", field.key, field.value)));
+            }
+        }
+    }
+
+    flags.push(snippet.method.to_string());
+    flags.push(shell_quote(&snippet.url));
+
+    let mut lines = vec![flags.join(" ")];
+    for (name, value) in &snippet.headers {
+        // `Name;` sends a header with an empty value.
+        let header = match value.is_empty() {
+            true => format!("{name};"),
+            false => format!("{name}:{value}"),
+        };
+        lines.push(shell_quote(&header));
+    }
+    lines.extend(items);
+    if let Some(path) = redirect {
+        lines.push(format!("< {path}"));
+    }
+    lines.join(" \\\n  ")
+}
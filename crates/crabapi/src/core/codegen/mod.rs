@@ -0,0 +1,507 @@
+//! Renders a request as a snippet for another tool or language.
+
+mod curl;
+mod fetch;
+mod httpie;
+mod python;
+mod rust;
+
+use crate::core::auth::{ApiKeyLocation, Auth};
+use crate::core::requests::{BodySpec, Method, RequestOptions, RequestSpec, Url};
+use std::fmt;
+use std::str::FromStr;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Format {
+    Curl,
+    Httpie,
+    Reqwest,
+    Python,
+    Fetch,
+}
+
+impl Format {
+    pub const ALL: [Format; 5] = [
+        Format::Curl,
+        Format::Httpie,
+        Format::Reqwest,
+        Format::Python,
+        Format::Fetch,
+    ];
+
+    /// Short name used on the command line.
+    pub fn name(&self) -> &'static str {
+        match self {
+            Format::Curl => "curl",
+            Format::Httpie => "httpie",
+            Format::Reqwest => "reqwest",
+            Format::Python => "python",
+            Format::Fetch => "fetch",
+        }
+    }
+}
+
+impl fmt::Display for Format {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let name = match self {
+            Format::Curl => "curl",
+            Format::Httpie => "HTTPie",
+            Format::Reqwest => "Rust (reqwest)",
+            Format::Python => "Python (requests)",
+            Format::Fetch => "JavaScript (fetch)",
+        };
+        write!(f, "{name}")
+    }
+}
+
+impl FromStr for Format {
+    type Err = String;
+
+    fn from_str(name: &str) -> Result<Self, Self::Err> {
+        Format::ALL
+            .into_iter()
+            .find(|format| format.name().eq_ignore_ascii_case(name))
+            .ok_or_else(|| format!("unknown format '{name}'"))
+    }
+}
+
+/// Renders `request` as it would be sent. Placeholders are rendered as they are, so resolve
+/// the request first to get a runnable snippet.
+pub fn render(request: &RequestSpec, format: Format) -> String {
+    let snippet = Snippet::new(request);
+    match format {
+        Format::Curl => curl::render(&snippet),
+        Format::Httpie => httpie::render(&snippet),
+        Format::Reqwest => rust::render(&snippet),
+        Format::Python => python::render(&snippet),
+        Format::Fetch => fetch::render(&snippet),
+    }
+}
+
+/// The request reduced to what every generator needs: queries folded into the URL and
+/// credentials that are plain headers turned into headers.
+struct Snippet<'a> {
+    method: &'a Method,
+    url: String,
+    headers: Vec<(String, String)>,
+    credentials: Option<Credentials<'a>>,
+    body: &'a BodySpec,
+    options: &'a RequestOptions,
+}
+
+/// Username and password auth, which most tools support natively.
+struct Credentials<'a> {
+    username: &'a str,
+    password: &'a str,
+    digest: bool,
+}
+
+impl<'a> Snippet<'a> {
+    fn new(request: &'a RequestSpec) -> Self {
+        let mut query: Vec<(&str, &str)> = request
+            .enabled_query()
+            .map(|pair| (pair.key.as_str(), pair.value.as_str()))
+            .collect();
+        let mut headers: Vec<(String, String)> = request
+            .enabled_headers()
+            .map(|header| (header.key.clone(), header.value.clone()))
+            .collect();
+        let mut credentials = None;
+
+        match &request.auth {
+            Auth::None => {}
+            Auth::Basic { username, password } | Auth::Digest { username, password } => {
+                credentials = Some(Credentials {
+                    username,
+                    password,
+                    digest: matches!(request.auth, Auth::Digest { .. }),
+                })
+            }
+            Auth::Bearer { token } => {
+                headers.push(("Authorization".to_string(), format!("Bearer {token}")))
+            }
+            Auth::ApiKey {
+                key,
+                value,
+                location: ApiKeyLocation::Header,
+            } => headers.push((key.clone(), value.clone())),
+            Auth::ApiKey {
+                key,
+                value,
+                location: ApiKeyLocation::Query,
+            } => query.push((key, value)),
+            Auth::OAuth2(oauth2) => headers.push((
+                "Authorization".to_string(),
+                format!("Bearer <access token from {}>", oauth2.token_url),
+            )),
+        }
+
+        Self {
+            method: &request.method,
+            url: full_url(&request.url, &query),
+            headers,
+            credentials,
+            body: &request.body,
+            options: &request.options,
+        }
+    }
+
+    /// Headers with repeated names joined with `, `, for targets that take a map.
+    fn joined_headers(&self) -> Vec<(String, String)> {
+        let mut headers: Vec<(String, String)> = vec![];
+        for (name, value) in &self.headers {
+            match headers
+                .iter_mut()
+                .find(|(existing, _)| existing.eq_ignore_ascii_case(name))
+            {
+                Some((_, existing)) => {
+                    existing.push_str(", ");
+                    existing.push_str(value);
+                }
+                None => headers.push((name.clone(), value.clone())),
+            }
+        }
+        headers
+    }
+}
+
+fn full_url(url: &str, query: &[(&str, &str)]) -> String {
+    if query.is_empty() {
+        return url.to_string();
+    }
+    match Url::parse(url) {
+        Ok(mut url) => {
+            url.query_pairs_mut().extend_pairs(query);
+            url.to_string()
+        }
+        Err(_) => {
+            let encoded = url::form_urlencoded::Serializer::new(String::new())
+                .extend_pairs(query)
+                .finish();
+            let separator = if url.contains('?') { '&' } else { '?' };
+            format!("{url}{separator}{encoded}")
+        }
+    }
+}
+
+/// Quotes `value` for a POSIX shell, leaving it bare when that is safe.
+fn shell_quote(value: &str) -> String {
+    let safe = !value.is_empty()
+        && value
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || "-_./:@=,+%".contains(c));
+    if safe {
+        value.to_string()
+    } else {
+        format!("'{}'", value.replace('\'', r"'\''"))
+    }
+}
+
+/// Double quoted string literal valid in both Python and JavaScript.
+fn string_literal(value: &str) -> String {
+    serde_json::to_string(value).unwrap_or_default()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::requests::{KeyValue, MultipartPart};
+
+    fn json_post() -> RequestSpec {
+        let mut request = RequestSpec::new(Method::POST, "https://api.example.com/items")
+            .with_query("q", "a b")
+            .with_header("Content-Type", "application/json")
+            .with_header("X-Tag", "one")
+            .with_header("X-Tag", "two")
+            .with_auth(Auth::Bearer {
+                token: "t0k'en".to_string(),
+            })
+            .with_body(BodySpec::Text {
+                text: r#"{"name":"it's"}"#.to_string(),
+            });
+        request.options.timeout_ms = Some(5000);
+        request
+    }
+
+    fn multipart_put() -> RequestSpec {
+        let mut part = MultipartPart::file("file", "/tmp/a.png");
+        part.content_type = Some("image/png".to_string());
+        let mut request = RequestSpec::new(Method::PUT, "https://api.example.com/upload")
+            .with_auth(Auth::Basic {
+                username: "user".to_string(),
+                password: "pass".to_string(),
+            })
+            .with_body(BodySpec::Multipart {
+                parts: vec![MultipartPart::text("note", "hello"), part],
+            });
+        request.options.insecure = true;
+        request.options.follow_redirects = false;
+        request
+    }
+
+    fn form_post() -> RequestSpec {
+        let mut disabled = KeyValue::new("off", "x");
+        disabled.enabled = false;
+        RequestSpec::new(Method::POST, "https://api.example.com/search").with_body(BodySpec::Form {
+            fields: vec![KeyValue::new("a b", "x&y"), disabled],
+        })
+    }
+
+    #[test]
+    fn renders_curl() {
+        assert_eq!(
+            render(&json_post(), Format::Curl),
+            r##"curl -X POST 'https://api.example.com/items?q=a+b' \
+  -H 'Content-Type: application/json' \
+  -H 'X-Tag: one' \
+  -H 'X-Tag: two' \
+  -H 'Authorization: Bearer t0k'\''en' \
+  --data-raw '{"name":"it'\''s"}' \
+  --location \
+  --max-time 5"##
+        );
+        assert_eq!(
+            render(&multipart_put(), Format::Curl),
+            r##"curl -X PUT https://api.example.com/upload \
+  -u user:pass \
+  --form-string note=hello \
+  -F 'file=@/tmp/a.png;type=image/png' \
+  --insecure"##
+        );
+        assert_eq!(
+            render(&form_post(), Format::Curl),
+            r##"curl -X POST https://api.example.com/search \
+  --data-urlencode 'a+b=x&y' \
+  --location"##
+        );
+    }
+
+    #[test]
+    fn renders_httpie() {
+        assert_eq!(
+            render(&json_post(), Format::Httpie),
+            r##"http --follow --timeout=5 --raw='{"name":"it'\''s"}' POST 'https://api.example.com/items?q=a+b' \
+  Content-Type:application/json \
+  X-Tag:one \
+  X-Tag:two \
+  'Authorization:Bearer t0k'\''en'"##
+        );
+        assert_eq!(
+            render(&multipart_put(), Format::Httpie),
+            r##"http --verify=no --auth=user:pass --multipart PUT https://api.example.com/upload \
+  note=hello \
+  'file@/tmp/a.png;type=image/png'"##
+        );
+        assert_eq!(
+            render(&form_post(), Format::Httpie),
+            r##"http --follow --form POST https://api.example.com/search \
+  'a b=x&y'"##
+        );
+    }
+
+    #[test]
+    fn renders_reqwest() {
+        assert_eq!(
+            render(&json_post(), Format::Reqwest),
+            r##"#[tokio::main]
+async fn main() -> Result<(), Box<dyn std::error::Error>> {
+    let client = reqwest::Client::new();
+
+    let response = client
+        .request(reqwest::Method::POST, "https://api.example.com/items?q=a+b")
+        .header("Content-Type", "application/json")
+        .header("X-Tag", "one")
+        .header("X-Tag", "two")
+        .header("Authorization", "Bearer t0k'en")
+        .body("{\"name\":\"it's\"}")
+        .timeout(std::time::Duration::from_millis(5000))
+        .send()
+        .await?;
+
+    println!("{}", response.status());
+    println!("{}", response.text().await?);
+
+    Ok(())
+}"##
+        );
+        assert_eq!(
+            render(&multipart_put(), Format::Reqwest),
+            r##"#[tokio::main]
+async fn main() -> Result<(), Box<dyn std::error::Error>> {
+    let client = reqwest::Client::builder()
+        .danger_accept_invalid_certs(true)
+        .redirect(reqwest::redirect::Policy::none())
+        .build()?;
+
+    let form = reqwest::multipart::Form::new()
+        .part("note", reqwest::multipart::Part::text("hello"))
+        .part("file", reqwest::multipart::Part::bytes(std::fs::read("/tmp/a.png")?).file_name("a.png").mime_str("image/png")?);
+
+    let response = client
+        .request(reqwest::Method::PUT, "https://api.example.com/upload")
+        .basic_auth("user", Some("pass"))
+        .multipart(form)
+        .send()
+        .await?;
+
+    println!("{}", response.status());
+    println!("{}", response.text().await?);
+
+    Ok(())
+}"##
+        );
+        assert_eq!(
+            render(&form_post(), Format::Reqwest),
+            r##"#[tokio::main]
+async fn main() -> Result<(), Box<dyn std::error::Error>> {
+    let client = reqwest::Client::new();
+
+    let response = client
+        .request(reqwest::Method::POST, "https://api.example.com/search")
+        .form(&[("a b", "x&y")])
+        .send()
+        .await?;
+
+    println!("{}", response.status());
+    println!("{}", response.text().await?);
+
+    Ok(())
+}"##
+        );
+    }
+
+    #[test]
+    fn renders_python() {
+        assert_eq!(
+            render(&json_post(), Format::Python),
+            r##"import requests
+
+url = "https://api.example.com/items?q=a+b"
+headers = {
+    "Content-Type": "application/json",
+    "X-Tag": "one, two",
+    "Authorization": "Bearer t0k'en",
+}
+data = "{\"name\":\"it's\"}"
+
+response = requests.request(
+    "POST",
+    url,
+    headers=headers,
+    data=data,
+    timeout=5.0,
+)
+
+print(response.status_code)
+print(response.text)"##
+        );
+        assert_eq!(
+            render(&multipart_put(), Format::Python),
+            r##"import requests
+
+url = "https://api.example.com/upload"
+files = [
+    ("note", (None, "hello")),
+    ("file", ("a.png", open("/tmp/a.png", "rb"), "image/png")),
+]
+
+response = requests.request(
+    "PUT",
+    url,
+    files=files,
+    auth=("user", "pass"),
+    verify=False,
+    allow_redirects=False,
+)
+
+print(response.status_code)
+print(response.text)"##
+        );
+        assert_eq!(
+            render(&form_post(), Format::Python),
+            r##"import requests
+
+url = "https://api.example.com/search"
+data = [
+    ("a b", "x&y"),
+]
+
+response = requests.request(
+    "POST",
+    url,
+    data=data,
+)
+
+print(response.status_code)
+print(response.text)"##
+        );
+    }
+
+    #[test]
+    fn renders_fetch() {
+        assert_eq!(
+            render(&json_post(), Format::Fetch),
+            r##"const response = await fetch("https://api.example.com/items?q=a+b", {
+  method: "POST",
+  headers: {
+    "Content-Type": "application/json",
+    "X-Tag": "one, two",
+    "Authorization": "Bearer t0k'en",
+  },
+  body: "{\"name\":\"it's\"}",
+  signal: AbortSignal.timeout(5000),
+});
+
+console.log(response.status);
+console.log(await response.text());"##
+        );
+        assert_eq!(
+            render(&multipart_put(), Format::Fetch),
+            r##"import { openAsBlob } from "node:fs";
+
+const form = new FormData();
+form.append("note", "hello");
+form.append("file", await openAsBlob("/tmp/a.png", { type: "image/png" }), "a.png");
+
+// fetch cannot skip TLS certificate checks.
+const response = await fetch("https://api.example.com/upload", {
+  method: "PUT",
+  headers: {
+    "Authorization": "Basic " + btoa("user:pass"),
+  },
+  body: form,
+  redirect: "manual",
+});
+
+console.log(response.status);
+console.log(await response.text());"##
+        );
+        assert_eq!(
+            render(&form_post(), Format::Fetch),
+            r##"const form = new URLSearchParams();
+form.append("a b", "x&y");
+
+const response = await fetch("https://api.example.com/search", {
+  method: "POST",
+  body: form,
+});
+
+console.log(response.status);
+console.log(await response.text());"##
+        );
+    }
+
+    #[test]
+    fn curl_sets_get_and_head_when_sending_a_body() {
+        let mut request = form_post();
+        request.method = Method::GET;
+        assert!(render(&request, Format::Curl).starts_with("curl -X GET https://"));
+        request.method = Method::HEAD;
+        assert!(render(&request, Format::Curl).starts_with("curl -X HEAD https://"));
+
+        request.body = BodySpec::Empty;
+        assert!(render(&request, Format::Curl).starts_with("curl --head https://"));
+        request.method = Method::GET;
+        assert!(render(&request, Format::Curl).starts_with("curl https://"));
+    }
+}
@@ -0,0 +1,110 @@
+use super::{Snippet, string_literal};
+use crate::core::requests::{BodySpec, PartValue};
+
+pub(super) fn render(snippet: &Snippet) -> String {
+    let mut imports = vec!["import requests".to_string()];
+    let mut code = vec![format!("url = {}", string_literal(&snippet.url))];
+    let mut arguments = vec![string_literal(snippet.method.as_str()), "url".to_string()];
+
+    if !snippet.headers.is_empty() {
+        code.push(format!("headers = {}", dict(&snippet.joined_headers())));
+        arguments.push("headers=headers".to_string());
+    }
+
+    match snippet.body {
+        BodySpec::Empty => {}
+        BodySpec::Text { text } => {
+            code.push(format!("data = {}", string_literal(text)));
+            arguments.push("data=data".to_string());
+        }
+        BodySpec::File { path } => {
+            code.push(format!(
+                "data = open({}, \"rb\")",
+                string_literal(&path.to_string_lossy())
+            ));
+            arguments.push("data=data".to_string());
+        }
+        BodySpec::Multipart { parts } => {
+            let mut files = vec![];
+            for part in parts {
+                let filename = match (&part.filename, &part.value) {
+                    (Some(filename), _) => string_literal(filename),
+                    (None, PartValue::File { path }) => path
+                        .file_name()
+                        .map(|name| string_literal(&name.to_string_lossy()))
+                        .unwrap_or_else(|| "None".to_string()),
+                    (None, PartValue::Text { .. }) => "None".to_string(),
+                };
+                let content = match &part.value {
+                    PartValue::Text { text } => string_literal(text),
+                    PartValue::File { path } => {
+                        format!("open({}, \"rb\")", string_literal(&path.to_string_lossy()))
+                    }
+                };
+                let mut value = format!("{filename}, {content}");
+                if let Some(content_type) = &part.content_type {
+                    value.push_str(&format!(", {}", string_literal(content_type)));
+                }
+                files.push(format!("    ({}, ({value})),", string_literal(&part.name)));
+            }
+            code.push(format!("files = [\n{}\n]", files.join("\n")));
+            arguments.push("files=files".to_string());
+        }
+        BodySpec::Form { fields } => {
+            let fields: Vec<String> = fields
+                .iter()
+                .filter(|field| field.enabled)
+                .map(|field| {
+                    format!(
+                        "    ({}, {}),",
+                        string_literal(&field.key),
+                        string_literal(&field.value)
+                    )
+                })
+                .collect();
+            code.push(format!("data = [\n{}\n]", fields.join("\n")));
+            arguments.push("data=data".to_string());
+        }
+    }
+
+    if let Some(credentials) = &snippet.credentials {
+        let username = string_literal(credentials.username);
+        let password = string_literal(credentials.password);
+        if credentials.digest {
+            imports.push("from requests.auth import HTTPDigestAuth".to_string());
+            arguments.push(format!("auth=HTTPDigestAuth({username}, {password})"));
+        } else {
+            arguments.push(format!("auth=({username}, {password})"));
+        }
+    }
+
+    let options = snippet.options;
+    if let Some(timeout) = options.timeout() {
+        arguments.push(format!("timeout={:?}", timeout.as_secs_f64()));
+    }
+    if options.insecure {
+        arguments.push("verify=False".to_string());
+    }
+    if !options.follow_redirects {
+        arguments.push("allow_redirects=False".to_string());
+    }
+
+    let arguments: Vec<String> = arguments
+        .iter()
+        .map(|argument| format!("    {argument},"))
+        .collect();
+    format!(
+        "{}\n\n{}\n\nresponse = requests.request(\n{}\n)\n\nprint(response.status_code)\nprint(response.text)",
+        imports.join("\n"),
+        code.join("\n"),
+        arguments.join("\n")
+    )
+}
+
+fn dict(entries: &[(String, String)]) -> String {
+    let entries: Vec<String> = entries
+        .iter()
+        .map(|(key, value)| format!("    {}: {},", string_literal(key), string_literal(value)))
+        .collect();
+    format!("{{\n{}\n}}", entries.join("\n"))
+}
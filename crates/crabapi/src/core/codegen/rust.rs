@@ -0,0 +1,148 @@
+use super::Snippet;
+use crate::core::requests::{BodySpec, Method, PartValue};
+
+pub(super) fn render(snippet: &Snippet) -> String {
+    let options = snippet.options;
+    let mut statements = vec![];
+
+    let mut client = vec![];
+    if options.insecure {
+        client.push("    .danger_accept_invalid_certs(true)".to_string());
+    }
+    if !options.follow_redirects {
+        client.push("    .redirect(reqwest::redirect::Policy::none())".to_string());
+    }
+    if client.is_empty() {
+        statements.push("let client = reqwest::Client::new();".to_string());
+    } else {
+        statements.push(format!(
+            "let client = reqwest::Client::builder()\n{}\n    .build()?;",
+            client.join("\n")
+        ));
+    }
+
+    let method = match snippet.method {
+        &Method::GET
+        | &Method::POST
+        | &Method::PUT
+        | &Method::DELETE
+        | &Method::HEAD
+        | &Method::OPTIONS
+        | &Method::CONNECT
+        | &Method::PATCH
+        | &Method::TRACE => format!("reqwest::Method::{}", snippet.method),
+        // Method names are tokens, which never need escaping.
+        method => format!(
+            "reqwest::Method::from_bytes(b{})?",
+            literal(method.as_str())
+        ),
+    };
+    let mut calls = vec![format!(".request({method}, {})", literal(&snippet.url))];
+
+    for (name, value) in &snippet.headers {
+        calls.push(format!(".header({}, {})", literal(name), literal(value)));
+    }
+    if let Some(credentials) = &snippet.credentials {
+        if credentials.digest {
+            statements.push(
+                "// reqwest has no Digest auth: answer the WWW-Authenticate challenge yourself."
+                    .to_string(),
+            );
+        } else {
+            calls.push(format!(
+                ".basic_auth({}, Some({}))",
+                literal(credentials.username),
+                literal(credentials.password)
+            ));
+        }
+    }
+
+    match snippet.body {
+        BodySpec::Empty => {}
+        BodySpec::Text { text } => calls.push(format!(".body({})", literal(text))),
+        BodySpec::File { path } => calls.push(format!(
+            ".body(std::fs::read({})?)",
+            literal(&path.to_string_lossy())
+        )),
+        BodySpec::Multipart { parts } => {
+            let mut form = vec!["let form = reqwest::multipart::Form::new()".to_string()];
+            for part in parts {
+                let mut body = match &part.value {
+                    PartValue::Text { text } => {
+                        format!("reqwest::multipart::Part::text({})", literal(text))
+                    }
+                    PartValue::File { path } => format!(
+                        "reqwest::multipart::Part::bytes(std::fs::read({})?)",
+                        literal(&path.to_string_lossy())
+                    ),
+                };
+                let filename = part.filename.clone().or_else(|| match &part.value {
+                    PartValue::File { path } => path
+                        .file_name()
+                        .map(|name| name.to_string_lossy().into_owned()),
+                    PartValue::Text { .. } => None,
+                });
+                if let Some(filename) = filename {
+                    body.push_str(&format!(".file_name({})", literal(&filename)));
+                }
+                if let Some(content_type) = &part.content_type {
+                    body.push_str(&format!(".mime_str({})?", literal(content_type)));
+                }
+                form.push(format!("    .part({}, {body})", literal(&part.name)));
+            }
+            statements.push(form.join("\n") + ";");
+            calls.push(".multipart(form)".to_string());
+        }
+        BodySpec::Form { fields } => {
+            let fields: Vec<String> = fields
+                .iter()
+                .filter(|field| field.enabled)
+                .map(|field| format!("({}, {})", literal(&field.key), literal(&field.value)))
+                .collect();
+            if !fields.is_empty() {
+                calls.push(format!(".form(&[{}])", fields.join(", ")));
+            }
+        }
+    }
+
+    if let Some(timeout) = options.timeout() {
+        calls.push(format!(
+            ".timeout(std::time::Duration::from_millis({}))",
+            timeout.as_millis()
+        ));
+    }
+    calls.push(".send()".to_string());
+    calls.push(".await?;".to_string());
+
+    statements.push(format!(
+        "let response = client\n{}",
+        calls
+            .iter()
+            .map(|call| format!("    {call}"))
+            .collect::<Vec<_>>()
+            .join("\n")
+    ));
+    statements.push(
+        "println!(\"{}\", response.status());\nprintln!(\"{}\", response.text().await?);"
+            .to_string(),
+    );
+    statements.push("Ok(())".to_string());
+
+    let body = statements
+        .join("\n\n")
+        .lines()
+        .map(|line| match line.is_empty() {
+            true => String::new(),
+            false => format!("    {line}"),
+        })
+        .collect::<Vec<_>>()
+        .join("\n");
+    format!(
+        "#[tokio::main]\nasync fn main() -> Result<(), Box<dyn std::error::Error>> {{\n{body}\n}}"
+    )
+}
+
+/// Rust string literal; `Debug` for `str` escapes exactly what Rust needs.
+fn literal(value: &str) -> String {
+    format!("{value:?}")
+}
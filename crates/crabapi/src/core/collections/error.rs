@@ -0,0 +1,50 @@
+use std::fmt;
+use std::path::{Path, PathBuf};
+
+#[derive(Debug)]
+pub enum Error {
+    Io {
+        path: PathBuf,
+        source: std::io::Error,
+    },
+    Parse {
+        path: PathBuf,
+        source: serde_json::Error,
+    },
+    InvalidPath(String),
+    NotFound(String),
+    AlreadyExists(String),
+}
+
+impl Error {
+    pub(super) fn io(path: &Path, source: std::io::Error) -> Self {
+        Error::Io {
+            path: path.to_path_buf(),
+            source,
+        }
+    }
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Error::Io { path, source } => write!(f, "{}: {source}", path.display()),
+            Error::Parse { path, source } => {
+                write!(f, "could not parse '{}': {source}", path.display())
+            }
+            Error::InvalidPath(path) => write!(f, "invalid collection path '{path}'"),
+            Error::NotFound(path) => write!(f, "'{path}' not found in collection"),
+            Error::AlreadyExists(path) => write!(f, "'{path}' already exists in collection"),
+        }
+    }
+}
+
+impl std::error::Error for Error {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Error::Io { source, .. } => Some(source),
+            Error::Parse { source, .. } => Some(source),
+            _ => None,
+        }
+    }
+}
@@ -0,0 +1,751 @@
+mod error;
+
+use crate::core::assertions::Assertion;
+use crate::core::requests::{KeyValue, RequestSpec};
+use crate::core::runner::Extraction;
+use crate::core::scripts::{Hooks, Scripts};
+use serde::{Deserialize, Serialize};
+use std::cmp::Ordering;
+use std::fmt;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+pub use error::Error;
+
+pub mod constants {
+    pub const REQUEST_EXTENSION: &str = "json";
+    pub const COLLECTIONS_DIR: &str = "collections";
+    /// Scripts of a folder, stored inside it; hidden, so it is not listed as a request.
+    pub const FOLDER_SCRIPTS_FILE: &str = ".scripts.json";
+}
+
+/// A request as stored in a collection, one JSON file per request.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SavedRequest {
+    #[serde(flatten)]
+    pub request: RequestSpec,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub response: Option<ReferenceResponse>,
+    /// Checked by `crabapi test`, and shown whenever the request is sent from the CLI.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub assertions: Vec<Assertion>,
+    /// Variables set from the response for the requests run after it.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub extract: Vec<Extraction>,
+    #[serde(default, skip_serializing_if = "Scripts::is_empty")]
+    pub scripts: Scripts,
+}
+
+/// A response recorded elsewhere (e.g. in a HAR capture), kept next to the request to
+/// compare against.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ReferenceResponse {
+    pub status: u16,
+    #[serde(default, skip_serializing_if = "String::is_empty")]
+    pub status_text: String,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub headers: Vec<KeyValue>,
+    #[serde(default, skip_serializing_if = "String::is_empty")]
+    pub body: String,
+    /// How `body` is encoded when the content is binary, e.g. `base64`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub encoding: Option<String>,
+}
+
+/// A node of the collection tree. Paths are `/` separated and relative to the collection
+/// root, without the file extension (`users/get-by-id`).
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Entry {
+    Folder {
+        name: String,
+        path: String,
+        children: Vec<Entry>,
+    },
+    Request {
+        name: String,
+        path: String,
+    },
+}
+
+/// Folders and requests stored under a root directory.
+#[derive(Debug, Clone)]
+pub struct Collection {
+    root: PathBuf,
+}
+
+impl SavedRequest {
+    pub fn new(request: RequestSpec) -> Self {
+        Self {
+            request,
+            response: None,
+            assertions: vec![],
+            extract: vec![],
+            scripts: Scripts::default(),
+        }
+    }
+
+    pub fn with_response(mut self, response: Option<ReferenceResponse>) -> Self {
+        self.response = response;
+        self
+    }
+
+    pub fn with_assertions(mut self, assertions: Vec<Assertion>) -> Self {
+        self.assertions = assertions;
+        self
+    }
+
+    pub fn with_extract(mut self, extract: Vec<Extraction>) -> Self {
+        self.extract = extract;
+        self
+    }
+
+    pub fn with_scripts(mut self, scripts: Scripts) -> Self {
+        self.scripts = scripts;
+        self
+    }
+}
+
+/// Rendered like an HTTP response: status line, headers, blank line, body.
+impl fmt::Display for ReferenceResponse {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} {}", self.status, self.status_text)?;
+        for header in &self.headers {
+            write!(f, "\n{}: {}", header.key, header.value)?;
+        }
+        write!(f, "\n\n")?;
+        match &self.encoding {
+            Some(encoding) => write!(
+                f,
+                "({} bytes of {encoding} encoded content)",
+                self.body.len()
+            ),
+            None => write!(f, "{}", self.body),
+        }
+    }
+}
+
+impl Entry {
+    pub fn name(&self) -> &str {
+        match self {
+            Entry::Folder { name, .. } | Entry::Request { name, .. } => name,
+        }
+    }
+
+    pub fn path(&self) -> &str {
+        match self {
+            Entry::Folder { path, .. } | Entry::Request { path, .. } => path,
+        }
+    }
+}
+
+impl Collection {
+    pub fn open(root: impl Into<PathBuf>) -> Self {
+        Self { root: root.into() }
+    }
+
+    /// Collection stored in the application data directory.
+    pub fn open_default() -> Self {
+        Self::open(crate::core::app::data_dir().join(constants::COLLECTIONS_DIR))
+    }
+
+    pub fn root(&self) -> &Path {
+        &self.root
+    }
+
+    /// The whole tree, folders first, each level sorted by name with numbers compared by
+    /// value, so `2-get` comes before `10-delete`.
+    pub fn tree(&self) -> Result<Vec<Entry>, Error> {
+        if !self.root.exists() {
+            return Ok(vec![]);
+        }
+        Self::read_folder(&self.root, "")
+    }
+
+    /// Request paths in run order, optionally limited to a folder: the requests of a folder
+    /// come before those of its subfolders, and a numeric prefix such as `01-login` sets the
+    /// order within each level.
+    pub fn request_paths(&self, folder: &str) -> Result<Vec<String>, Error> {
+        let entries = if folder.is_empty() {
+            self.tree()?
+        } else {
+            let dir = self.folder_path(folder)?;
+            if !dir.is_dir() {
+                return Err(Error::NotFound(folder.to_string()));
+            }
+            Self::read_folder(&dir, folder)?
+        };
+
+        let mut paths = vec![];
+        Self::collect_requests(&entries, &mut paths);
+        Ok(paths)
+    }
+
+    pub fn exists(&self, path: &str) -> bool {
+        self.request_file(path).is_ok_and(|file| file.is_file())
+    }
+
+    pub fn load(&self, path: &str) -> Result<SavedRequest, Error> {
+        let file = self.request_file(path)?;
+        if !file.is_file() {
+            return Err(Error::NotFound(path.to_string()));
+        }
+        let content = fs::read_to_string(&file).map_err(|source| Error::io(&file, source))?;
+        serde_json::from_str(&content).map_err(|source| Error::Parse { path: file, source })
+    }
+
+    /// Creates or overwrites the request at `path`, creating parent folders as needed.
+    pub fn save(&self, path: &str, request: &SavedRequest) -> Result<(), Error> {
+        let file = self.request_file(path)?;
+        if let Some(parent) = file.parent() {
+            fs::create_dir_all(parent).map_err(|source| Error::io(parent, source))?;
+        }
+        let mut content = serde_json::to_string_pretty(request).map_err(|source| Error::Parse {
+            path: file.clone(),
+            source,
+        })?;
+        content.push('\n');
+        fs::write(&file, content).map_err(|source| Error::io(&file, source))
+    }
+
+    /// Scripts of the request or folder at `path`, or of the whole collection when empty.
+    pub fn scripts(&self, path: &str) -> Result<Scripts, Error> {
+        if self.exists(path) {
+            return Ok(self.load(path)?.scripts);
+        }
+        let file = self.folder_scripts_file(path)?;
+        if !file.is_file() {
+            return Ok(Scripts::default());
+        }
+        let content = fs::read_to_string(&file).map_err(|source| Error::io(&file, source))?;
+        serde_json::from_str(&content).map_err(|source| Error::Parse { path: file, source })
+    }
+
+    /// Replaces the scripts of the request or folder at `path`, or of the whole collection
+    /// when empty.
+    pub fn save_scripts(&self, path: &str, scripts: &Scripts) -> Result<(), Error> {
+        if self.exists(path) {
+            let request = self.load(path)?.with_scripts(scripts.clone());
+            return self.save(path, &request);
+        }
+        let file = self.folder_scripts_file(path)?;
+        if scripts.is_empty() {
+            if file.is_file() {
+                fs::remove_file(&file).map_err(|source| Error::io(&file, source))?;
+            }
+            return Ok(());
+        }
+        if let Some(dir) = file.parent() {
+            fs::create_dir_all(dir).map_err(|source| Error::io(dir, source))?;
+        }
+        let mut content = serde_json::to_string_pretty(scripts).map_err(|source| Error::Parse {
+            path: file.clone(),
+            source,
+        })?;
+        content.push('\n');
+        fs::write(&file, content).map_err(|source| Error::io(&file, source))
+    }
+
+    /// Scripts that run around the request saved at `path`: the collection's, those of each
+    /// enclosing folder, then its own.
+    pub fn hooks(&self, path: &str, request: &SavedRequest) -> Result<Hooks, Error> {
+        Ok(self
+            .folder_hooks(parent_of(path))?
+            .with_request(&request.scripts))
+    }
+
+    /// Scripts of the collection and of each folder down to `folder`, which is included.
+    pub fn folder_hooks(&self, folder: &str) -> Result<Hooks, Error> {
+        let mut hooks = Hooks::new().with_folder("", &self.scripts("")?);
+        let mut path = String::new();
+        for component in folder.split('/').filter(|name| !name.is_empty()) {
+            path = join(&path, component);
+            hooks = hooks.with_folder(&path, &self.scripts(&path)?);
+        }
+        Ok(hooks)
+    }
+
+    pub fn create_folder(&self, path: &str) -> Result<(), Error> {
+        let dir = self.folder_path(path)?;
+        fs::create_dir_all(&dir).map_err(|source| Error::io(&dir, source))
+    }
+
+    /// Renames a request or folder in place and returns its new path.
+    pub fn rename(&self, path: &str, new_name: &str) -> Result<String, Error> {
+        validate_name(new_name)?;
+        let new_path = match parent_of(path) {
+            "" => new_name.to_string(),
+            parent => format!("{parent}/{new_name}"),
+        };
+        self.relocate(path, &new_path)
+    }
+
+    /// Moves a request or folder into `folder` (empty for the root) and returns its new path.
+    pub fn move_entry(&self, path: &str, folder: &str) -> Result<String, Error> {
+        let name = name_of(path);
+        let new_path = match folder {
+            "" => name.to_string(),
+            folder => format!("{folder}/{name}"),
+        };
+        if new_path == path {
+            return Ok(new_path);
+        }
+        if new_path.starts_with(&format!("{path}/")) {
+            return Err(Error::InvalidPath(new_path));
+        }
+        self.relocate(path, &new_path)
+    }
+
+    /// Copies a request next to the original as `<name> copy` and returns the copy's path.
+    pub fn duplicate(&self, path: &str) -> Result<String, Error> {
+        let request = self.load(path)?;
+        let mut copy_path = format!("{path} copy");
+        let mut counter = 2;
+        while self.exists(&copy_path) {
+            copy_path = format!("{path} copy {counter}");
+            counter += 1;
+        }
+        self.save(&copy_path, &request)?;
+        Ok(copy_path)
+    }
+
+    /// Deletes a request, or a folder together with everything in it.
+    pub fn delete(&self, path: &str) -> Result<(), Error> {
+        let file = self.request_file(path)?;
+        if file.is_file() {
+            return fs::remove_file(&file).map_err(|source| Error::io(&file, source));
+        }
+        let dir = self.folder_path(path)?;
+        if dir.is_dir() {
+            return fs::remove_dir_all(&dir).map_err(|source| Error::io(&dir, source));
+        }
+        Err(Error::NotFound(path.to_string()))
+    }
+
+    fn relocate(&self, from: &str, to: &str) -> Result<String, Error> {
+        let (source, target) = if self.request_file(from)?.is_file() {
+            (self.request_file(from)?, self.request_file(to)?)
+        } else if self.folder_path(from)?.is_dir() {
+            (self.folder_path(from)?, self.folder_path(to)?)
+        } else {
+            return Err(Error::NotFound(from.to_string()));
+        };
+
+        if target.exists() {
+            return Err(Error::AlreadyExists(to.to_string()));
+        }
+        if let Some(parent) = target.parent() {
+            fs::create_dir_all(parent).map_err(|source| Error::io(parent, source))?;
+        }
+        fs::rename(&source, &target).map_err(|source| Error::io(&target, source))?;
+        Ok(to.to_string())
+    }
+
+    fn read_folder(dir: &Path, prefix: &str) -> Result<Vec<Entry>, Error> {
+        let mut folders = vec![];
+        let mut requests = vec![];
+
+        for dir_entry in fs::read_dir(dir).map_err(|source| Error::io(dir, source))? {
+            let dir_entry = dir_entry.map_err(|source| Error::io(dir, source))?;
+            let file_path = dir_entry.path();
+            let Some(file_name) = file_path.file_name().and_then(|name| name.to_str()) else {
+                continue;
+            };
+            if file_name.starts_with('.') {
+                continue;
+            }
+
+            if file_path.is_dir() {
+                let path = join(prefix, file_name);
+                let children = Self::read_folder(&file_path, &path)?;
+                folders.push(Entry::Folder {
+                    name: file_name.to_string(),
+                    path,
+                    children,
+                });
+            } else if let Some(name) = file_name
+                .strip_suffix(constants::REQUEST_EXTENSION)
+                .and_then(|name| name.strip_suffix('.'))
+            {
+                requests.push(Entry::Request {
+                    name: name.to_string(),
+                    path: join(prefix, name),
+                });
+            }
+        }
+
+        folders.sort_by(|a, b| natural_cmp(a.name(), b.name()));
+        requests.sort_by(|a, b| natural_cmp(a.name(), b.name()));
+        folders.extend(requests);
+        Ok(folders)
+    }
+
+    fn collect_requests(entries: &[Entry], paths: &mut Vec<String>) {
+        for entry in entries {
+            if let Entry::Request { path, .. } = entry {
+                paths.push(path.clone());
+            }
+        }
+        for entry in entries {
+            if let Entry::Folder { children, .. } = entry {
+                Self::collect_requests(children, paths);
+            }
+        }
+    }
+
+    fn folder_path(&self, path: &str) -> Result<PathBuf, Error> {
+        let mut dir = self.root.clone();
+        for component in components(path)? {
+            dir.push(component);
+        }
+        Ok(dir)
+    }
+
+    /// A folder that does not exist is an error, except for the collection root.
+    fn folder_scripts_file(&self, path: &str) -> Result<PathBuf, Error> {
+        let dir = match path {
+            "" => self.root.clone(),
+            path => self.folder_path(path)?,
+        };
+        if !path.is_empty() && !dir.is_dir() {
+            return Err(Error::NotFound(path.to_string()));
+        }
+        Ok(dir.join(constants::FOLDER_SCRIPTS_FILE))
+    }
+
+    fn request_file(&self, path: &str) -> Result<PathBuf, Error> {
+        let mut file = self.folder_path(path)?;
+        let name = format!("{}.{}", name_of(path), constants::REQUEST_EXTENSION);
+        file.set_file_name(name);
+        Ok(file)
+    }
+}
+
+fn components(path: &str) -> Result<Vec<&str>, Error> {
+    let components: Vec<&str> = path.split('/').collect();
+    for component in &components {
+        validate_name(component).map_err(|_| Error::InvalidPath(path.to_string()))?;
+    }
+    Ok(components)
+}
+
+fn validate_name(name: &str) -> Result<(), Error> {
+    let invalid = name.trim().is_empty()
+        || name == "."
+        || name == ".."
+        || name.starts_with('.')
+        || name.contains(['/', '\\']);
+    if invalid {
+        return Err(Error::InvalidPath(name.to_string()));
+    }
+    Ok(())
+}
+
+/// Compares runs of digits by their value and everything else character by character.
+fn natural_cmp(a: &str, b: &str) -> Ordering {
+    let (mut a, mut b) = (a.chars().peekable(), b.chars().peekable());
+    loop {
+        match (a.peek().copied(), b.peek().copied()) {
+            (None, None) => return Ordering::Equal,
+            (None, Some(_)) => return Ordering::Less,
+            (Some(_), None) => return Ordering::Greater,
+            (Some(x), Some(y)) if x.is_ascii_digit() && y.is_ascii_digit() => {
+                let number = |chars: &mut std::iter::Peekable<std::str::Chars>| {
+                    let mut digits = String::new();
+                    while let Some(digit) = chars.next_if(char::is_ascii_digit) {
+                        digits.push(digit);
+                    }
+                    digits.trim_start_matches('0').to_string()
+                };
+                let (x, y) = (number(&mut a), number(&mut b));
+                let ordering = x.len().cmp(&y.len()).then_with(|| x.cmp(&y));
+                if ordering != Ordering::Equal {
+                    return ordering;
+                }
+            }
+            (Some(x), Some(y)) => {
+                if x != y {
+                    return x.cmp(&y);
+                }
+                a.next();
+                b.next();
+            }
+        }
+    }
+}
+
+fn join(prefix: &str, name: &str) -> String {
+    if prefix.is_empty() {
+        name.to_string()
+    } else {
+        format!("{prefix}/{name}")
+    }
+}
+
+/// The folder `path` is in, empty at the root.
+pub fn parent_of(path: &str) -> &str {
+    path.rsplit_once('/')
+        .map(|(parent, _)| parent)
+        .unwrap_or("")
+}
+
+fn name_of(path: &str) -> &str {
+    path.rsplit_once('/').map(|(_, name)| name).unwrap_or(path)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::requests::Method;
+
+    fn collection() -> (tempfile::TempDir, Collection) {
+        let dir = tempfile::tempdir().unwrap();
+        let collection = Collection::open(dir.path().join("collection"));
+        (dir, collection)
+    }
+
+    fn request(url: &str) -> SavedRequest {
+        SavedRequest::new(RequestSpec::new(Method::GET, url))
+    }
+
+    fn paths(collection: &Collection) -> Vec<String> {
+        collection.request_paths("").unwrap()
+    }
+
+    #[test]
+    fn saves_and_loads_requests_in_folders() {
+        let (_dir, collection) = collection();
+        collection.save("users/get", &request("/users/1")).unwrap();
+        collection.create_folder("empty/nested").unwrap();
+
+        assert!(collection.exists("users/get"));
+        assert!(collection.root().join("users/get.json").is_file());
+        assert_eq!(collection.load("users/get").unwrap(), request("/users/1"));
+        assert_eq!(
+            collection.tree().unwrap(),
+            [
+                Entry::Folder {
+                    name: "empty".to_string(),
+                    path: "empty".to_string(),
+                    children: vec![Entry::Folder {
+                        name: "nested".to_string(),
+                        path: "empty/nested".to_string(),
+                        children: vec![],
+                    }],
+                },
+                Entry::Folder {
+                    name: "users".to_string(),
+                    path: "users".to_string(),
+                    children: vec![Entry::Request {
+                        name: "get".to_string(),
+                        path: "users/get".to_string(),
+                    }],
+                },
+            ]
+        );
+        assert!(matches!(
+            collection.load("users/missing"),
+            Err(Error::NotFound(_))
+        ));
+    }
+
+    #[test]
+    fn lists_nothing_without_a_root() {
+        let (_dir, collection) = collection();
+
+        assert!(collection.tree().unwrap().is_empty());
+        assert!(matches!(
+            collection.request_paths("users"),
+            Err(Error::NotFound(_))
+        ));
+    }
+
+    #[test]
+    fn renames_requests_and_folders() {
+        let (_dir, collection) = collection();
+        collection.save("users/get", &request("/users/1")).unwrap();
+        collection.save("users/list", &request("/users")).unwrap();
+
+        assert_eq!(
+            collection.rename("users/get", "show").unwrap(),
+            "users/show"
+        );
+        assert_eq!(collection.rename("users", "people").unwrap(), "people");
+        assert_eq!(paths(&collection), ["people/list", "people/show"]);
+        assert_eq!(collection.load("people/show").unwrap(), request("/users/1"));
+
+        assert!(matches!(
+            collection.rename("people/show", "list"),
+            Err(Error::AlreadyExists(path)) if path == "people/list"
+        ));
+        assert!(matches!(
+            collection.rename("people/nope", "other"),
+            Err(Error::NotFound(_))
+        ));
+        assert!(matches!(
+            collection.rename("people/list", "../escape"),
+            Err(Error::InvalidPath(_))
+        ));
+    }
+
+    #[test]
+    fn moves_entries_between_folders() {
+        let (_dir, collection) = collection();
+        collection.save("login", &request("/login")).unwrap();
+        collection.save("users/get", &request("/users/1")).unwrap();
+        collection.create_folder("admin").unwrap();
+
+        assert_eq!(
+            collection.move_entry("login", "admin").unwrap(),
+            "admin/login"
+        );
+        assert_eq!(collection.move_entry("users/get", "").unwrap(), "get");
+        assert_eq!(
+            collection.move_entry("users", "admin").unwrap(),
+            "admin/users"
+        );
+        assert_eq!(collection.move_entry("get", "").unwrap(), "get");
+        assert_eq!(paths(&collection), ["get", "admin/login"]);
+        assert!(collection.root().join("admin/users").is_dir());
+
+        // A folder cannot go inside itself.
+        assert!(matches!(
+            collection.move_entry("admin", "admin/users"),
+            Err(Error::InvalidPath(_))
+        ));
+    }
+
+    #[test]
+    fn duplicates_with_a_free_name() {
+        let (_dir, collection) = collection();
+        collection.save("users/get", &request("/users/1")).unwrap();
+
+        assert_eq!(collection.duplicate("users/get").unwrap(), "users/get copy");
+        assert_eq!(
+            collection.duplicate("users/get").unwrap(),
+            "users/get copy 2"
+        );
+        assert_eq!(
+            collection.load("users/get copy 2").unwrap(),
+            request("/users/1")
+        );
+        assert!(matches!(
+            collection.duplicate("users/nope"),
+            Err(Error::NotFound(_))
+        ));
+    }
+
+    #[test]
+    fn deletes_requests_and_folders() {
+        let (_dir, collection) = collection();
+        collection.save("login", &request("/login")).unwrap();
+        collection.save("users/get", &request("/users/1")).unwrap();
+        collection
+            .save("users/admins/list", &request("/admins"))
+            .unwrap();
+
+        collection.delete("login").unwrap();
+        assert_eq!(paths(&collection), ["users/get", "users/admins/list"]);
+        collection.delete("users").unwrap();
+        assert!(paths(&collection).is_empty());
+        assert!(!collection.root().join("users").exists());
+        assert!(matches!(
+            collection.delete("users"),
+            Err(Error::NotFound(_))
+        ));
+    }
+
+    #[test]
+    fn rejects_paths_outside_the_collection() {
+        let (dir, collection) = collection();
+        let outside = dir.path().join("outside.json");
+        std::fs::write(&outside, "{}").unwrap();
+
+        for path in [
+            "../outside",
+            "users/../../outside",
+            "/etc/passwd",
+            "./login",
+            "users//get",
+            "users/.hidden",
+            "users\\..\\outside",
+            "",
+        ] {
+            assert!(
+                matches!(
+                    collection.save(path, &request("/")),
+                    Err(Error::InvalidPath(_))
+                ),
+                "{path}"
+            );
+            assert!(
+                matches!(collection.delete(path), Err(Error::InvalidPath(_))),
+                "{path}"
+            );
+        }
+        assert!(matches!(
+            collection.create_folder("../outside-folder"),
+            Err(Error::InvalidPath(_))
+        ));
+        assert!(matches!(
+            collection.move_entry("../outside", ""),
+            Err(Error::InvalidPath(_))
+        ));
+        assert!(outside.is_file());
+        assert!(!dir.path().join("outside-folder").exists());
+    }
+
+    #[test]
+    fn runs_a_folder_before_its_subfolders() {
+        let (_dir, collection) = collection();
+        for path in [
+            "10-cleanup",
+            "2-users/2-delete",
+            "2-users/10-audit/1-list",
+            "2-users/1-create",
+            "1-login",
+            "1-setup/seed",
+        ] {
+            collection.save(path, &request("/")).unwrap();
+        }
+
+        assert_eq!(
+            paths(&collection),
+            [
+                "1-login",
+                "10-cleanup",
+                "1-setup/seed",
+                "2-users/1-create",
+                "2-users/2-delete",
+                "2-users/10-audit/1-list",
+            ]
+        );
+        assert_eq!(
+            collection.request_paths("2-users").unwrap(),
+            [
+                "2-users/1-create",
+                "2-users/2-delete",
+                "2-users/10-audit/1-list"
+            ]
+        );
+        let tree = collection.tree().unwrap();
+        let names: Vec<&str> = tree.iter().map(Entry::name).collect();
+        assert_eq!(names, ["1-setup", "2-users", "1-login", "10-cleanup"]);
+    }
+
+    #[test]
+    fn compares_numbers_by_value() {
+        assert_eq!(natural_cmp("2-get", "10-delete"), Ordering::Less);
+        assert_eq!(natural_cmp("v10", "v9"), Ordering::Greater);
+        assert_eq!(natural_cmp("a1b2", "a1b10"), Ordering::Less);
+        assert_eq!(natural_cmp("007", "7"), Ordering::Equal);
+        assert_eq!(natural_cmp("12345678901234567890", "9"), Ordering::Greater);
+        assert_eq!(natural_cmp("item", "item2"), Ordering::Less);
+        assert_eq!(natural_cmp("1", "a"), Ordering::Less);
+        assert_eq!(natural_cmp("B", "a"), Ordering::Less);
+        assert_eq!(natural_cmp("same", "same"), Ordering::Equal);
+
+        let mut names = vec!["v10", "v2", "v1.10", "v1.9", "v", "v01"];
+        names.sort_by(|a, b| natural_cmp(a, b));
+        assert_eq!(names, ["v", "v01", "v1.9", "v1.10", "v2", "v10"]);
+    }
+}
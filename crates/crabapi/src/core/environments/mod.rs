@@ -0,0 +1,142 @@
+mod error;
+mod variables;
+
+use crate::core::requests::KeyValue;
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::path::{Path, PathBuf};
+
+pub use error::Error;
+pub use variables::{Variables, placeholders};
+
+pub mod constants {
+    pub const ENVIRONMENTS_FILE: &str = "environments.json";
+}
+
+/// A named set of variables, e.g. `local`, `staging` or `prod`.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Environment {
+    pub name: String,
+    #[serde(default)]
+    pub variables: Vec<KeyValue>,
+}
+
+/// All environments and the one currently in use, stored in a single JSON file.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Environments {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub active: Option<String>,
+    #[serde(default)]
+    pub environments: Vec<Environment>,
+}
+
+impl Environment {
+    pub fn new(name: impl Into<String>) -> Self {
+        Self {
+            name: name.into(),
+            variables: vec![],
+        }
+    }
+
+    /// Sets a variable, replacing the first row with the same name.
+    pub fn set(&mut self, name: &str, value: &str) {
+        match self.variables.iter_mut().find(|row| row.key == name) {
+            Some(row) => row.value = value.to_string(),
+            None => self.variables.push(KeyValue::new(name, value)),
+        }
+    }
+}
+
+impl Environments {
+    pub fn default_path() -> PathBuf {
+        crate::core::app::data_dir().join(constants::ENVIRONMENTS_FILE)
+    }
+
+    /// Loads environments from `path`; a missing file means no environments yet.
+    pub fn load(path: &Path) -> Result<Self, Error> {
+        if !path.exists() {
+            return Ok(Self::default());
+        }
+        let content = fs::read_to_string(path).map_err(|source| Error::io(path, source))?;
+        serde_json::from_str(&content).map_err(|source| Error::Parse {
+            path: path.to_path_buf(),
+            source,
+        })
+    }
+
+    pub fn load_default() -> Result<Self, Error> {
+        Self::load(&Self::default_path())
+    }
+
+    pub fn save(&self, path: &Path) -> Result<(), Error> {
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent).map_err(|source| Error::io(parent, source))?;
+        }
+        let mut content = serde_json::to_string_pretty(self).map_err(|source| Error::Parse {
+            path: path.to_path_buf(),
+            source,
+        })?;
+        content.push('\n');
+        fs::write(path, content).map_err(|source| Error::io(path, source))
+    }
+
+    pub fn save_default(&self) -> Result<(), Error> {
+        self.save(&Self::default_path())
+    }
+
+    pub fn names(&self) -> Vec<String> {
+        self.environments
+            .iter()
+            .map(|environment| environment.name.clone())
+            .collect()
+    }
+
+    pub fn get(&self, name: &str) -> Option<&Environment> {
+        self.environments
+            .iter()
+            .find(|environment| environment.name == name)
+    }
+
+    /// Returns the environment called `name`, creating it if needed.
+    pub fn get_or_insert(&mut self, name: &str) -> &mut Environment {
+        let index = match self.environments.iter().position(|env| env.name == name) {
+            Some(index) => index,
+            None => {
+                self.environments.push(Environment::new(name));
+                self.environments.len() - 1
+            }
+        };
+        &mut self.environments[index]
+    }
+
+    pub fn active_environment(&self) -> Option<&Environment> {
+        self.active.as_deref().and_then(|name| self.get(name))
+    }
+
+    pub fn set_active(&mut self, name: Option<&str>) -> Result<(), Error> {
+        if let Some(name) = name {
+            if self.get(name).is_none() {
+                return Err(Error::NotFound(name.to_string()));
+            }
+        }
+        self.active = name.map(str::to_string);
+        Ok(())
+    }
+
+    /// Variables of the environment called `name`, or of the active one when `name` is `None`.
+    pub fn variables(&self, name: Option<&str>) -> Result<Variables, Error> {
+        let environment = match name {
+            Some(name) => Some(
+                self.get(name)
+                    .ok_or_else(|| Error::NotFound(name.to_string()))?,
+            ),
+            None => self.active_environment(),
+        };
+
+        let mut variables = Variables::new();
+        if let Some(environment) = environment {
+            variables.extend_from(&environment.variables);
+        }
+        Ok(variables)
+    }
+}
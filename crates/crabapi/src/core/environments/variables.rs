@@ -0,0 +1,200 @@
+use crate::core::requests::KeyValue;
+use std::collections::BTreeMap;
+use std::ops::Range;
+
+/// How deep variables may refer to other variables before giving up.
+const MAX_DEPTH: usize = 8;
+
+/// Values available to `{{name}}` placeholders.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Variables(BTreeMap<String, String>);
+
+impl Variables {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn insert(&mut self, name: impl Into<String>, value: impl Into<String>) {
+        self.0.insert(name.into(), value.into());
+    }
+
+    pub fn get(&self, name: &str) -> Option<&str> {
+        self.0.get(name).map(String::as_str)
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = (&str, &str)> {
+        self.0
+            .iter()
+            .map(|(name, value)| (name.as_str(), value.as_str()))
+    }
+
+    /// Adds the enabled rows, overriding variables with the same name.
+    pub fn extend_from(&mut self, rows: &[KeyValue]) {
+        for row in rows.iter().filter(|row| row.enabled) {
+            self.insert(row.key.clone(), row.value.clone());
+        }
+    }
+
+    /// Replaces every `{{name}}` in `input`. Values may themselves contain placeholders.
+    /// On failure, returns the names that could not be resolved.
+    pub fn expand(&self, input: &str) -> Result<String, Vec<String>> {
+        let mut unresolved = vec![];
+        let output = self.expand_inner(input, 0, &mut unresolved);
+        if unresolved.is_empty() {
+            Ok(output)
+        } else {
+            Err(unresolved)
+        }
+    }
+
+    /// Names used in `input` that have no value.
+    pub fn unresolved(&self, input: &str) -> Vec<String> {
+        self.expand(input).err().unwrap_or_default()
+    }
+
+    fn expand_inner(&self, input: &str, depth: usize, unresolved: &mut Vec<String>) -> String {
+        let mut output = String::with_capacity(input.len());
+        let mut last = 0;
+        for (range, name) in placeholders(input) {
+            output.push_str(&input[last..range.start]);
+            match self.get(name) {
+                Some(value) if depth < MAX_DEPTH => {
+                    output.push_str(&self.expand_inner(value, depth + 1, unresolved));
+                }
+                _ => {
+                    if !unresolved.iter().any(|known| known == name) {
+                        unresolved.push(name.to_string());
+                    }
+                    output.push_str(&input[range.clone()]);
+                }
+            }
+            last = range.end;
+        }
+        output.push_str(&input[last..]);
+        output
+    }
+}
+
+/// Every `{{name}}` placeholder in `input`, with its byte range. Names may be surrounded by
+/// spaces and are made of letters, digits, `_`, `-` and `.`; anything else is left as is, so
+/// JSON such as `{{"a": 1}}` is not mistaken for a placeholder.
+pub fn placeholders(input: &str) -> Vec<(Range<usize>, &str)> {
+    let mut found = vec![];
+    let mut offset = 0;
+    while let Some(start) = input[offset..].find("{{").map(|start| start + offset) {
+        let Some(end) = input[start + 2..].find("}}").map(|end| end + start + 2) else {
+            break;
+        };
+        let name = input[start + 2..end].trim();
+        let valid = !name.is_empty()
+            && name
+                .chars()
+                .all(|c| c.is_alphanumeric() || matches!(c, '_' | '-' | '.'));
+        if valid {
+            found.push((start..end + 2, name));
+            offset = end + 2;
+        } else {
+            offset = start + 1;
+        }
+    }
+    found
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn variables(pairs: &[(&str, &str)]) -> Variables {
+        let mut variables = Variables::new();
+        for (name, value) in pairs {
+            variables.insert(*name, *value);
+        }
+        variables
+    }
+
+    #[test]
+    fn finds_placeholders() {
+        let input = "{{base}}/users/{{ id }}?q={{a.b-c_d}}";
+        let found: Vec<(Range<usize>, &str)> = placeholders(input);
+
+        assert_eq!(found, [(0..8, "base"), (15..23, "id"), (26..37, "a.b-c_d")]);
+        assert_eq!(&input[15..23], "{{ id }}");
+    }
+
+    #[test]
+    fn ignores_what_is_not_a_placeholder() {
+        assert!(placeholders(r#"{{"a": 1}}"#).is_empty());
+        assert!(placeholders("{{}} {{ }} {{a b}} {{open").is_empty());
+        assert_eq!(placeholders("{{{name}}}"), [(1..9, "name")]);
+        assert_eq!(placeholders("{{{{x}}"), [(2..7, "x")]);
+    }
+
+    #[test]
+    fn expands_nested_values() {
+        let variables = variables(&[
+            ("host", "127.0.0.1"),
+            ("base", "http://{{host}}:{{port}}"),
+            ("port", "7878"),
+            ("url", "{{base}}/v1"),
+        ]);
+
+        assert_eq!(
+            variables.expand("{{url}}/users").unwrap(),
+            "http://127.0.0.1:7878/v1/users"
+        );
+        assert_eq!(
+            variables.expand("no placeholders").unwrap(),
+            "no placeholders"
+        );
+    }
+
+    #[test]
+    fn collects_unresolved_names_once_in_order() {
+        let variables = variables(&[("base", "{{scheme}}://{{host}}")]);
+
+        assert_eq!(
+            variables.expand("{{base}}/{{id}}/{{host}}"),
+            Err(vec![
+                "scheme".to_string(),
+                "host".to_string(),
+                "id".to_string()
+            ])
+        );
+        assert_eq!(variables.unresolved("{{base}}"), ["scheme", "host"]);
+    }
+
+    #[test]
+    fn stops_cycles_at_the_depth_limit() {
+        let variables = variables(&[("a", "<{{b}}>"), ("b", "{{a}}"), ("self", "{{self}}")]);
+
+        assert_eq!(variables.unresolved("{{self}}"), ["self"]);
+        assert_eq!(variables.unresolved("{{a}}"), ["a"]);
+
+        // A chain of eight variables still expands, a ninth is left unresolved.
+        let mut chain = Variables::new();
+        for level in 1..MAX_DEPTH {
+            chain.insert(format!("v{level}"), format!("{{{{v{}}}}}", level + 1));
+        }
+        chain.insert(format!("v{MAX_DEPTH}"), "end");
+        assert_eq!(chain.expand("{{v1}}").unwrap(), "end");
+        chain.insert(format!("v{MAX_DEPTH}"), "{{too_deep}}");
+        chain.insert("too_deep", "end");
+        assert_eq!(chain.expand("{{v1}}"), Err(vec!["too_deep".to_string()]));
+    }
+
+    #[test]
+    fn extends_from_enabled_rows() {
+        let mut variables = variables(&[("token", "old")]);
+        let mut disabled = KeyValue::new("debug", "1");
+        disabled.enabled = false;
+
+        variables.extend_from(&[KeyValue::new("token", "new"), disabled]);
+
+        assert_eq!(variables.get("token"), Some("new"));
+        assert_eq!(variables.get("debug"), None);
+    }
+}
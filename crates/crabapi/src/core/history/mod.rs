@@ -0,0 +1,456 @@
+mod error;
+
+use crate::core::requests::{Error as RequestError, RequestSpec, ResponseRecord};
+use serde::{Deserialize, Serialize};
+use std::fmt;
+use std::fs::{self, OpenOptions};
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+pub use error::Error;
+
+pub mod constants {
+    use std::time::Duration;
+
+    pub const HISTORY_FILE: &str = "history.jsonl";
+    pub const MAX_ENTRIES: usize = 1000;
+    pub const MAX_AGE: Duration = Duration::from_secs(30 * 24 * 60 * 60);
+}
+
+/// A sent request and what came back, stored as one line of the history file.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct HistoryEntry {
+    pub id: u64,
+    /// Seconds since the Unix epoch.
+    pub timestamp: u64,
+    pub duration_ms: u64,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub environment: Option<String>,
+    pub request: RequestSpec,
+    pub outcome: Outcome,
+}
+
+/// Summary of the response, or the error that prevented one.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum Outcome {
+    Response {
+        status: u16,
+        size: usize,
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        content_type: Option<String>,
+    },
+    Error {
+        message: String,
+    },
+}
+
+/// Entries beyond `max_entries`, or older than `max_age`, are dropped when recording.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Retention {
+    pub max_entries: usize,
+    pub max_age: Duration,
+}
+
+/// Append-only log of sent requests, one JSON object per line, oldest first.
+#[derive(Debug, Clone)]
+pub struct History {
+    path: PathBuf,
+    retention: Retention,
+    // Serializes the read-modify-write of `record` between tasks of the same process.
+    lock: Arc<Mutex<()>>,
+}
+
+impl HistoryEntry {
+    pub fn time(&self) -> SystemTime {
+        UNIX_EPOCH + Duration::from_secs(self.timestamp)
+    }
+
+    /// UTC timestamp, e.g. `2025-03-01T12:00:00Z`.
+    pub fn time_string(&self) -> String {
+        humantime::format_rfc3339_seconds(self.time()).to_string()
+    }
+}
+
+impl Outcome {
+    pub fn new(result: &Result<ResponseRecord, RequestError>) -> Self {
+        match result {
+            Ok(response) => Outcome::Response {
+                status: response.status.as_u16(),
+                size: response.size(),
+                content_type: response.content_type().map(str::to_string),
+            },
+            Err(error) => Outcome::Error {
+                message: error.to_string(),
+            },
+        }
+    }
+}
+
+impl fmt::Display for Outcome {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Outcome::Response { status, size, .. } => write!(f, "{status} - {size} bytes"),
+            Outcome::Error { message } => write!(f, "error: {message}"),
+        }
+    }
+}
+
+impl Default for Retention {
+    fn default() -> Self {
+        Self {
+            max_entries: constants::MAX_ENTRIES,
+            max_age: constants::MAX_AGE,
+        }
+    }
+}
+
+impl History {
+    pub fn open(path: impl Into<PathBuf>) -> Self {
+        Self {
+            path: path.into(),
+            retention: Retention::default(),
+            lock: Arc::default(),
+        }
+    }
+
+    /// History stored in the application data directory.
+    pub fn open_default() -> Self {
+        Self::open(crate::core::app::data_dir().join(constants::HISTORY_FILE))
+    }
+
+    pub fn with_retention(mut self, retention: Retention) -> Self {
+        self.retention = retention;
+        self
+    }
+
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    /// Every entry, oldest first. A missing file means an empty history, and lines that cannot
+    /// be parsed, such as one cut short by a crash, are skipped.
+    pub fn entries(&self) -> Result<Vec<HistoryEntry>, Error> {
+        self.read().map(|(entries, _)| entries)
+    }
+
+    pub fn get(&self, id: u64) -> Result<HistoryEntry, Error> {
+        self.entries()?
+            .into_iter()
+            .find(|entry| entry.id == id)
+            .ok_or(Error::NotFound(id))
+    }
+
+    /// Appends a request and its result, then applies the retention limits.
+    pub fn record(
+        &self,
+        request: &RequestSpec,
+        environment: Option<&str>,
+        outcome: Outcome,
+        duration: Duration,
+    ) -> Result<HistoryEntry, Error> {
+        let _guard = self
+            .lock
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner());
+
+        let (mut entries, skipped) = self.read()?;
+        let last_id = entries.last().map_or(0, |last| last.id);
+        let now = SystemTime::now();
+        let mut request = request.clone();
+        request.auth = request.auth.redacted();
+        let entry = HistoryEntry {
+            id: last_id.max(self.cleared_id()) + 1,
+            timestamp: now.duration_since(UNIX_EPOCH).unwrap_or_default().as_secs(),
+            duration_ms: duration.as_millis() as u64,
+            environment: environment.map(str::to_string),
+            request,
+            outcome,
+        };
+
+        let count = entries.len();
+        entries.retain(|entry| {
+            now.duration_since(entry.time())
+                .is_ok_and(|age| age <= self.retention.max_age)
+        });
+        let overflow = (entries.len() + 1).saturating_sub(self.retention.max_entries);
+        entries.drain(..overflow.min(entries.len()));
+
+        // Rewriting also drops the lines that could not be parsed.
+        if entries.len() == count && skipped == 0 {
+            self.append(&entry)?;
+        } else {
+            entries.push(entry.clone());
+            self.write(&entries)?;
+        }
+        Ok(entry)
+    }
+
+    pub fn clear(&self) -> Result<(), Error> {
+        let _guard = self
+            .lock
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner());
+
+        let (entries, _) = self.read()?;
+        let last_id = entries.last().map_or(0, |last| last.id);
+        let id = last_id.max(self.cleared_id());
+        self.create_parent()?;
+        let id_path = self.cleared_id_path();
+        fs::write(&id_path, id.to_string()).map_err(|source| Error::io(&id_path, source))?;
+        self.write(&[])
+    }
+
+    /// The entries that could be parsed, and how many lines could not.
+    fn read(&self) -> Result<(Vec<HistoryEntry>, usize), Error> {
+        if !self.path.exists() {
+            return Ok((vec![], 0));
+        }
+        let content =
+            fs::read_to_string(&self.path).map_err(|source| Error::io(&self.path, source))?;
+
+        let mut entries = vec![];
+        let mut skipped = 0;
+        for line in content.lines().filter(|line| !line.trim().is_empty()) {
+            match serde_json::from_str(line) {
+                Ok(entry) => entries.push(entry),
+                Err(_) => skipped += 1,
+            }
+        }
+        Ok((entries, skipped))
+    }
+
+    /// The last ID given out before the history was cleared, so that IDs are not reused.
+    fn cleared_id(&self) -> u64 {
+        fs::read_to_string(self.cleared_id_path())
+            .ok()
+            .and_then(|content| content.trim().parse().ok())
+            .unwrap_or(0)
+    }
+
+    fn cleared_id_path(&self) -> PathBuf {
+        self.path.with_extension("last-id")
+    }
+
+    fn append(&self, entry: &HistoryEntry) -> Result<(), Error> {
+        let line = self.line(entry)?;
+        self.create_parent()?;
+        OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(&self.path)
+            .and_then(|mut file| file.write_all(line.as_bytes()))
+            .map_err(|source| Error::io(&self.path, source))
+    }
+
+    fn write(&self, entries: &[HistoryEntry]) -> Result<(), Error> {
+        let mut content = String::new();
+        for entry in entries {
+            content.push_str(&self.line(entry)?);
+        }
+        self.create_parent()?;
+        fs::write(&self.path, content).map_err(|source| Error::io(&self.path, source))
+    }
+
+    fn line(&self, entry: &HistoryEntry) -> Result<String, Error> {
+        let mut line = serde_json::to_string(entry)
+            .map_err(|source| Error::io(&self.path, std::io::Error::other(source)))?;
+        line.push('\n');
+        Ok(line)
+    }
+
+    fn create_parent(&self) -> Result<(), Error> {
+        match self.path.parent() {
+            Some(parent) => fs::create_dir_all(parent).map_err(|source| Error::io(parent, source)),
+            None => Ok(()),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::auth::{Auth, OAuth2, OAuth2Grant};
+
+    const DAY: Duration = Duration::from_secs(24 * 60 * 60);
+
+    fn outcome() -> Outcome {
+        Outcome::Response {
+            status: 200,
+            size: 2,
+            content_type: None,
+        }
+    }
+
+    fn record(history: &History, url: &str) -> HistoryEntry {
+        let request = RequestSpec {
+            url: url.to_string(),
+            ..RequestSpec::default()
+        };
+        history
+            .record(&request, None, outcome(), Duration::from_millis(5))
+            .unwrap()
+    }
+
+    fn urls(history: &History) -> Vec<String> {
+        let entries = history.entries().unwrap();
+        entries.into_iter().map(|entry| entry.request.url).collect()
+    }
+
+    #[test]
+    fn records_entries_with_increasing_ids() {
+        let dir = tempfile::tempdir().unwrap();
+        let history = History::open(dir.path().join("history.jsonl"));
+        assert!(history.entries().unwrap().is_empty());
+
+        assert_eq!(record(&history, "http://a").id, 1);
+        assert_eq!(record(&history, "http://b").id, 2);
+        assert_eq!(urls(&history), ["http://a", "http://b"]);
+        assert_eq!(history.get(2).unwrap().request.url, "http://b");
+        assert!(matches!(history.get(3), Err(Error::NotFound(3))));
+    }
+
+    #[test]
+    fn keeps_the_newest_entries_up_to_the_count_limit() {
+        let dir = tempfile::tempdir().unwrap();
+        let history = History::open(dir.path().join("history.jsonl")).with_retention(Retention {
+            max_entries: 3,
+            ..Retention::default()
+        });
+
+        for index in 1..=5 {
+            record(&history, &format!("http://{index}"));
+        }
+        assert_eq!(urls(&history), ["http://3", "http://4", "http://5"]);
+        assert_eq!(record(&history, "http://6").id, 6);
+    }
+
+    #[test]
+    fn drops_entries_older_than_the_age_limit() {
+        let dir = tempfile::tempdir().unwrap();
+        let history = History::open(dir.path().join("history.jsonl")).with_retention(Retention {
+            max_age: 7 * DAY,
+            ..Retention::default()
+        });
+        let now = SystemTime::now().duration_since(UNIX_EPOCH).unwrap();
+        let old = |id: u64, age: Duration| HistoryEntry {
+            id,
+            timestamp: (now - age).as_secs(),
+            duration_ms: 1,
+            environment: None,
+            request: RequestSpec {
+                url: format!("http://{id}"),
+                ..RequestSpec::default()
+            },
+            outcome: outcome(),
+        };
+        history.write(&[old(1, 8 * DAY), old(2, 6 * DAY)]).unwrap();
+
+        assert_eq!(record(&history, "http://3").id, 3);
+        assert_eq!(urls(&history), ["http://2", "http://3"]);
+    }
+
+    #[test]
+    fn skips_corrupted_lines_and_drops_them_on_rewrite() {
+        let dir = tempfile::tempdir().unwrap();
+        let history = History::open(dir.path().join("history.jsonl")).with_retention(Retention {
+            max_entries: 2,
+            ..Retention::default()
+        });
+        record(&history, "http://1");
+        let mut file = OpenOptions::new()
+            .append(true)
+            .open(history.path())
+            .unwrap();
+        file.write_all(b"{\"id\": 2, \"timest\n").unwrap();
+        drop(file);
+
+        assert_eq!(urls(&history), ["http://1"]);
+        assert_eq!(record(&history, "http://2").id, 2);
+        assert_eq!(urls(&history), ["http://1", "http://2"]);
+        let content = fs::read_to_string(history.path()).unwrap();
+        assert_eq!(content.lines().count(), 2);
+        assert!(!content.contains("timest\n"));
+    }
+
+    #[test]
+    fn does_not_reuse_ids_after_clear() {
+        let dir = tempfile::tempdir().unwrap();
+        let history = History::open(dir.path().join("history.jsonl"));
+        record(&history, "http://1");
+        record(&history, "http://2");
+
+        history.clear().unwrap();
+        assert!(history.entries().unwrap().is_empty());
+        assert_eq!(record(&history, "http://3").id, 3);
+
+        history.clear().unwrap();
+        history.clear().unwrap();
+        assert_eq!(record(&history, "http://4").id, 4);
+    }
+
+    #[test]
+    fn leaves_literal_secrets_out() {
+        let dir = tempfile::tempdir().unwrap();
+        let history = History::open(dir.path().join("history.jsonl"));
+        let recorded = |auth: Auth| {
+            let request = RequestSpec {
+                auth,
+                ..RequestSpec::default()
+            };
+            let entry = history
+                .record(&request, None, outcome(), Duration::ZERO)
+                .unwrap();
+            history.get(entry.id).unwrap().request.auth
+        };
+
+        let basic = recorded(Auth::Basic {
+            username: "alice".to_string(),
+            password: "hunter2".to_string(),
+        });
+        assert_eq!(
+            basic,
+            Auth::Basic {
+                username: "alice".to_string(),
+                password: String::new(),
+            }
+        );
+
+        let bearer = recorded(Auth::Bearer {
+            token: "{{token}}".to_string(),
+        });
+        assert_eq!(
+            bearer,
+            Auth::Bearer {
+                token: "{{token}}".to_string(),
+            }
+        );
+
+        let oauth2 = recorded(Auth::OAuth2(OAuth2 {
+            client_id: "app".to_string(),
+            client_secret: "s3cret-{{suffix}}".to_string(),
+            grant: OAuth2Grant::RefreshToken {
+                refresh_token: "{{ refresh }}".to_string(),
+            },
+            ..OAuth2::default()
+        }));
+        let Auth::OAuth2(oauth2) = oauth2 else {
+            panic!("expected OAuth 2.0, got {oauth2:?}");
+        };
+        assert_eq!(oauth2.client_id, "app");
+        assert_eq!(oauth2.client_secret, "");
+        assert_eq!(
+            oauth2.grant,
+            OAuth2Grant::RefreshToken {
+                refresh_token: "{{ refresh }}".to_string(),
+            }
+        );
+        assert!(
+            !fs::read_to_string(history.path())
+                .unwrap()
+                .contains("hunter2")
+        );
+    }
+}
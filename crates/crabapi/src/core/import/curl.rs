@@ -0,0 +1,756 @@
+use super::Error;
+use crate::core::auth::Auth;
+use crate::core::requests::{
+    BodySpec, KeyValue, Method, MultipartPart, RequestOptions, RequestSpec,
+};
+use std::iter::Peekable;
+use std::path::PathBuf;
+use std::str::Chars;
+
+/// Options we understand: long name, short name, and whether they take a value.
+const OPTIONS: &[(&str, Option<char>, bool)] = &[
+    ("request", Some('X'), true),
+    ("header", Some('H'), true),
+    ("data", Some('d'), true),
+    ("data-ascii", None, true),
+    ("data-raw", None, true),
+    ("data-binary", None, true),
+    ("data-urlencode", None, true),
+    ("json", None, true),
+    ("form", Some('F'), true),
+    ("form-string", None, true),
+    ("user", Some('u'), true),
+    ("basic", None, false),
+    ("digest", None, false),
+    ("oauth2-bearer", None, true),
+    ("get", Some('G'), false),
+    ("head", Some('I'), false),
+    ("compressed", None, false),
+    ("insecure", Some('k'), false),
+    ("location", Some('L'), false),
+    ("user-agent", Some('A'), true),
+    ("referer", Some('e'), true),
+    ("cookie", Some('b'), true),
+    ("url", None, true),
+    ("max-time", Some('m'), true),
+];
+
+/// Options that only change curl's own output or retries, so they are skipped.
+const IGNORED_OPTIONS: &[(&str, Option<char>, bool)] = &[
+    ("silent", Some('s'), false),
+    ("show-error", Some('S'), false),
+    ("verbose", Some('v'), false),
+    ("include", Some('i'), false),
+    ("fail", Some('f'), false),
+    ("fail-with-body", None, false),
+    ("progress-bar", Some('#'), false),
+    ("no-progress-meter", None, false),
+    ("no-buffer", Some('N'), false),
+    ("globoff", Some('g'), false),
+    ("path-as-is", None, false),
+    ("http1.1", None, false),
+    ("http2", None, false),
+    ("http2-prior-knowledge", None, false),
+    ("output", Some('o'), true),
+    ("write-out", Some('w'), true),
+    ("dump-header", Some('D'), true),
+    ("cookie-jar", Some('c'), true),
+    ("connect-timeout", None, true),
+    ("max-redirs", None, true),
+    ("retry", None, true),
+    ("retry-delay", None, true),
+    ("retry-max-time", None, true),
+    ("limit-rate", None, true),
+];
+
+enum Data {
+    Text(String),
+    File(PathBuf),
+}
+
+#[derive(Default)]
+struct Command {
+    method: Option<String>,
+    url: Option<String>,
+    headers: Vec<KeyValue>,
+    data: Vec<Data>,
+    json: bool,
+    parts: Vec<MultipartPart>,
+    user: Option<String>,
+    digest: bool,
+    bearer: Option<String>,
+    get: bool,
+    head: bool,
+    options: RequestOptions,
+}
+
+/// Parses a curl command line, as copied from docs or a browser's "Copy as cURL". The
+/// leading `curl` is optional and lines may be continued with a trailing `\`.
+pub fn parse(command_line: &str) -> Result<RequestSpec, Error> {
+    let mut words = split(command_line)?;
+    if words
+        .first()
+        .is_some_and(|word| word == "curl" || word.ends_with("/curl"))
+    {
+        words.remove(0);
+    }
+    parse_args(&words)
+}
+
+/// Parses curl's arguments, without the program name.
+pub fn parse_args(args: &[String]) -> Result<RequestSpec, Error> {
+    let mut command = Command::default();
+    // Unlike our own default, curl only follows redirects with -L.
+    command.options.follow_redirects = false;
+    let mut args = args.iter();
+
+    while let Some(arg) = args.next() {
+        if let Some(name) = arg.strip_prefix("--").filter(|name| !name.is_empty()) {
+            let (name, takes_value) = lookup(name, None).ok_or_else(|| unsupported(arg))?;
+            let value = match takes_value {
+                true => Some(
+                    args.next()
+                        .ok_or_else(|| Error::MissingValue(arg.clone()))?,
+                ),
+                false => None,
+            };
+            command.apply(name, value.map(String::as_str))?;
+        } else if let Some(flags) = arg.strip_prefix('-').filter(|flags| !flags.is_empty()) {
+            // Short options can be grouped (`-sSL`) and take their value attached (`-XPOST`).
+            for (index, short) in flags.char_indices() {
+                let option = format!("-{short}");
+                let (name, takes_value) =
+                    lookup("", Some(short)).ok_or_else(|| unsupported(&option))?;
+                if !takes_value {
+                    command.apply(name, None)?;
+                    continue;
+                }
+                let attached = &flags[index + short.len_utf8()..];
+                let value = match attached.is_empty() {
+                    true => args.next().ok_or(Error::MissingValue(option))?,
+                    false => attached,
+                };
+                command.apply(name, Some(value))?;
+                break;
+            }
+        } else if command.url.is_none() {
+            command.url = Some(arg.clone());
+        } else {
+            return Err(Error::UnsupportedOption(format!("second URL '{arg}'")));
+        }
+    }
+
+    command.into_request()
+}
+
+/// Parses a curl `-F` value: `name=value`, `name=@path` for a file or `name=<path` for a
+/// text field read from a file, optionally followed by `;type=...` and `;filename=...`.
+pub fn form_part(value: &str) -> Result<MultipartPart, Error> {
+    let (name, content) = value
+        .split_once('=')
+        .ok_or_else(|| invalid_value("--form", value))?;
+
+    let (content, params) = if content.starts_with(['@', '<']) {
+        content.split_once(';').unwrap_or((content, ""))
+    } else {
+        // Text values may contain `;`, only `;type=` starts a parameter.
+        match content.find(";type=") {
+            Some(index) => (&content[..index], &content[index + 1..]),
+            None => (content, ""),
+        }
+    };
+
+    let mut part = if let Some(path) = content.strip_prefix('@') {
+        MultipartPart::file(name, path)
+    } else if let Some(path) = content.strip_prefix('<') {
+        MultipartPart::text(name, read_file(path)?)
+    } else {
+        MultipartPart::text(name, content)
+    };
+
+    for param in params.split(';').filter(|param| !param.trim().is_empty()) {
+        match param.trim().split_once('=') {
+            Some(("type", content_type)) => part.content_type = Some(content_type.to_string()),
+            Some(("filename", filename)) => {
+                part.filename = Some(filename.trim_matches('"').to_string())
+            }
+            _ => return Err(invalid_value("--form", value)),
+        }
+    }
+    Ok(part)
+}
+
+impl Command {
+    fn apply(&mut self, name: &str, value: Option<&str>) -> Result<(), Error> {
+        let value = value.unwrap_or_default();
+        match name {
+            "request" => self.method = Some(value.to_string()),
+            "header" => self.headers.push(header(value)?),
+            "data" | "data-ascii" => match value.strip_prefix('@') {
+                // Like curl, newlines are dropped from files given to -d.
+                Some(path) => self
+                    .data
+                    .push(Data::Text(read_file(path)?.replace(['\r', '\n'], ""))),
+                None => self.data.push(Data::Text(value.to_string())),
+            },
+            "data-raw" => self.data.push(Data::Text(value.to_string())),
+            "data-binary" => match value.strip_prefix('@') {
+                Some(path) => self.data.push(Data::File(PathBuf::from(path))),
+                None => self.data.push(Data::Text(value.to_string())),
+            },
+            "data-urlencode" => self.data.push(Data::Text(url_encoded(value)?)),
+            "json" => {
+                self.json = true;
+                match value.strip_prefix('@') {
+                    Some(path) => self.data.push(Data::Text(read_file(path)?)),
+                    None => self.data.push(Data::Text(value.to_string())),
+                }
+            }
+            "form" => self.parts.push(form_part(value)?),
+            "form-string" => {
+                let (name, text) = value
+                    .split_once('=')
+                    .ok_or_else(|| invalid_value("--form-string", value))?;
+                self.parts.push(MultipartPart::text(name, text));
+            }
+            "user" => self.user = Some(value.to_string()),
+            "basic" => self.digest = false,
+            "digest" => self.digest = true,
+            "oauth2-bearer" => self.bearer = Some(value.to_string()),
+            "get" => self.get = true,
+            "head" => self.head = true,
+            "compressed" => self.options.compressed = true,
+            "insecure" => self.options.insecure = true,
+            "location" => self.options.follow_redirects = true,
+            "user-agent" => self.headers.push(KeyValue::new("User-Agent", value)),
+            "referer" => {
+                let referer = value.strip_suffix(";auto").unwrap_or(value);
+                if !referer.is_empty() {
+                    self.headers.push(KeyValue::new("Referer", referer));
+                }
+            }
+            "cookie" => {
+                if !value.contains('=') {
+                    return Err(Error::UnsupportedOption(format!(
+                        "--cookie with a cookie file '{value}'"
+                    )));
+                }
+                self.headers.push(KeyValue::new("Cookie", value));
+            }
+            "url" => self.url = Some(value.to_string()),
+            "max-time" => {
+                let seconds: f64 = value
+                    .parse()
+                    .map_err(|_| invalid_value("--max-time", value))?;
+                self.options.timeout_ms = Some((seconds * 1000.0) as u64);
+            }
+            _ => {}
+        }
+        Ok(())
+    }
+
+    fn into_request(mut self) -> Result<RequestSpec, Error> {
+        let url = self.url.take().ok_or(Error::MissingUrl)?;
+        let url = match url.contains("://") {
+            true => url,
+            false => format!("http://{url}"),
+        };
+
+        if !self.data.is_empty() && !self.parts.is_empty() {
+            return Err(Error::UnsupportedOption(
+                "--data together with --form".to_string(),
+            ));
+        }
+
+        let method = match &self.method {
+            Some(method) => method
+                .parse::<Method>()
+                .map_err(|_| invalid_value("--request", method))?,
+            None if self.head => Method::HEAD,
+            None if self.get => Method::GET,
+            None if !self.data.is_empty() || !self.parts.is_empty() => Method::POST,
+            None => Method::GET,
+        };
+
+        let mut request = RequestSpec::new(method, url);
+        request.headers = std::mem::take(&mut self.headers);
+        request.options = self.options.clone();
+
+        if self.get {
+            let data = self.joined_data()?;
+            request.query = url::form_urlencoded::parse(data.as_bytes())
+                .map(|(key, value)| KeyValue::new(key, value))
+                .collect();
+        } else if !self.parts.is_empty() {
+            request.body = BodySpec::Multipart {
+                parts: std::mem::take(&mut self.parts),
+            };
+        } else if !self.data.is_empty() {
+            if self.json {
+                set_default_header(&mut request, "Content-Type", "application/json");
+                set_default_header(&mut request, "Accept", "application/json");
+            } else {
+                set_default_header(
+                    &mut request,
+                    "Content-Type",
+                    "application/x-www-form-urlencoded",
+                );
+            }
+            request.body = match self.data.as_slice() {
+                [Data::File(path)] => BodySpec::File { path: path.clone() },
+                _ => BodySpec::Text {
+                    text: self.joined_data()?,
+                },
+            };
+        }
+
+        if let Some(token) = self.bearer {
+            request.auth = Auth::Bearer { token };
+        } else if let Some(user) = self.user {
+            let (username, password) = user.split_once(':').unwrap_or((&user, ""));
+            let (username, password) = (username.to_string(), password.to_string());
+            request.auth = match self.digest {
+                true => Auth::Digest { username, password },
+                false => Auth::Basic { username, password },
+            };
+        }
+
+        Ok(request)
+    }
+
+    /// Every data argument joined with `&`, as curl sends them.
+    fn joined_data(&self) -> Result<String, Error> {
+        let mut parts = vec![];
+        for data in &self.data {
+            match data {
+                Data::Text(text) => parts.push(text.clone()),
+                Data::File(path) => parts.push(read_file(&path.to_string_lossy())?),
+            }
+        }
+        Ok(parts.join("&"))
+    }
+}
+
+fn lookup(long: &str, short: Option<char>) -> Option<(&'static str, bool)> {
+    OPTIONS
+        .iter()
+        .chain(IGNORED_OPTIONS)
+        .find(|(name, flag, _)| match short {
+            Some(short) => *flag == Some(short),
+            None => *name == long,
+        })
+        .map(|(name, _, takes_value)| (*name, *takes_value))
+}
+
+fn header(value: &str) -> Result<KeyValue, Error> {
+    match value.split_once(':') {
+        Some((name, value)) if !name.trim().is_empty() => {
+            Ok(KeyValue::new(name.trim(), value.trim()))
+        }
+        // `-H 'Name;'` sends a header with an empty value.
+        _ => match value.strip_suffix(';') {
+            Some(name) if !name.trim().is_empty() => Ok(KeyValue::new(name.trim(), "")),
+            _ => Err(Error::InvalidHeader(value.to_string())),
+        },
+    }
+}
+
+fn set_default_header(request: &mut RequestSpec, name: &str, value: &str) {
+    let present = request
+        .headers
+        .iter()
+        .any(|header| header.key.eq_ignore_ascii_case(name));
+    if !present {
+        request.headers.push(KeyValue::new(name, value));
+    }
+}
+
+/// `--data-urlencode` forms: `content`, `=content`, `name=content`, `@file`, `name@file`.
+fn url_encoded(value: &str) -> Result<String, Error> {
+    let encode = |content: &str| url::form_urlencoded::byte_serialize(content.as_bytes()).collect();
+
+    if let Some((name, content)) = value.split_once('=') {
+        return Ok(match name.is_empty() {
+            true => encode(content),
+            false => format!("{name}={}", encode(content)),
+        });
+    }
+    match value.split_once('@') {
+        Some(("", path)) => Ok(encode(&read_file(path)?)),
+        Some((name, path)) => Ok(format!("{name}={}", encode(&read_file(path)?))),
+        None => Ok(encode(value)),
+    }
+}
+
+fn read_file(path: &str) -> Result<String, Error> {
+    if path == "-" {
+        return Err(Error::UnsupportedOption(
+            "reading data from stdin".to_string(),
+        ));
+    }
+    std::fs::read_to_string(path).map_err(|source| Error::Io {
+        path: PathBuf::from(path),
+        source,
+    })
+}
+
+fn unsupported(option: &str) -> Error {
+    Error::UnsupportedOption(option.to_string())
+}
+
+fn invalid_value(option: &str, value: &str) -> Error {
+    Error::InvalidValue {
+        option: option.to_string(),
+        value: value.to_string(),
+    }
+}
+
+/// Splits a command line into words the way a POSIX shell would, handling single, double
+/// and `$'...'` quotes and backslash-newline continuations.
+fn split(command_line: &str) -> Result<Vec<String>, Error> {
+    let mut words = vec![];
+    let mut word = String::new();
+    let mut in_word = false;
+    let mut chars = command_line.chars().peekable();
+
+    while let Some(c) = chars.next() {
+        match c {
+            '\\' => match chars.next() {
+                Some('\n') => {}
+                Some('\r') => {
+                    chars.next_if_eq(&'\n');
+                }
+                Some(c) => {
+                    word.push(c);
+                    in_word = true;
+                }
+                None => {}
+            },
+            '\'' => {
+                in_word = true;
+                loop {
+                    match chars.next() {
+                        Some('\'') => break,
+                        Some(c) => word.push(c),
+                        None => return Err(Error::Syntax("unterminated ' quote".to_string())),
+                    }
+                }
+            }
+            '"' => {
+                in_word = true;
+                loop {
+                    match chars.next() {
+                        Some('"') => break,
+                        Some('\\') => match chars.next() {
+                            Some(c @ ('"' | '\\' | '$' | '`')) => word.push(c),
+                            Some('\n') => {}
+                            Some(c) => {
+                                word.push('\\');
+                                word.push(c);
+                            }
+                            None => break,
+                        },
+                        Some(c) => word.push(c),
+                        None => return Err(Error::Syntax("unterminated \" quote".to_string())),
+                    }
+                }
+            }
+            '$' if chars.peek() == Some(&'\'') => {
+                chars.next();
+                in_word = true;
+                ansi_c_quoted(&mut chars, &mut word)?;
+            }
+            c if c.is_whitespace() => {
+                if in_word {
+                    words.push(std::mem::take(&mut word));
+                    in_word = false;
+                }
+            }
+            c => {
+                word.push(c);
+                in_word = true;
+            }
+        }
+    }
+
+    if in_word {
+        words.push(word);
+    }
+    Ok(words)
+}
+
+/// Reads the rest of a `$'...'` string, as produced by browsers for bodies with newlines.
+fn ansi_c_quoted(chars: &mut Peekable<Chars>, word: &mut String) -> Result<(), Error> {
+    loop {
+        match chars.next() {
+            Some('\'') => return Ok(()),
+            Some('\\') => match chars.next() {
+                Some('n') => word.push('\n'),
+                Some('t') => word.push('\t'),
+                Some('r') => word.push('\r'),
+                Some('e' | 'E') => word.push('\x1b'),
+                Some('0') => word.push('\0'),
+                Some('x') => word.push(hex_escape(chars, 2)),
+                Some('u') => word.push(hex_escape(chars, 4)),
+                Some('U') => word.push(hex_escape(chars, 8)),
+                Some(c) => word.push(c),
+                None => break,
+            },
+            Some(c) => word.push(c),
+            None => break,
+        }
+    }
+    Err(Error::Syntax("unterminated $' quote".to_string()))
+}
+
+fn hex_escape(chars: &mut Peekable<Chars>, max_digits: usize) -> char {
+    let mut code = 0;
+    for _ in 0..max_digits {
+        match chars.peek().and_then(|c| c.to_digit(16)) {
+            Some(digit) => {
+                code = code * 16 + digit;
+                chars.next();
+            }
+            None => break,
+        }
+    }
+    char::from_u32(code).unwrap_or(char::REPLACEMENT_CHARACTER)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::requests::PartValue;
+
+    fn words(command_line: &str) -> Vec<String> {
+        split(command_line).unwrap()
+    }
+
+    fn header_pairs(request: &RequestSpec) -> Vec<(&str, &str)> {
+        request
+            .headers
+            .iter()
+            .map(|header| (header.key.as_str(), header.value.as_str()))
+            .collect()
+    }
+
+    #[test]
+    fn splits_single_and_double_quotes() {
+        assert_eq!(
+            words(r#"curl 'a b' "c \"d\" \$e \x" f'g'"h" '' x"#),
+            ["curl", "a b", r#"c "d" $e \x"#, "fgh", "", "x"]
+        );
+        assert_eq!(words(r"a\ b \'c"), ["a b", "'c"]);
+    }
+
+    #[test]
+    fn splits_ansi_c_quotes() {
+        assert_eq!(
+            words(r"$'line\none\ttab \'q\' \x41é'"),
+            ["line\none\ttab 'q' Aé"]
+        );
+    }
+
+    #[test]
+    fn joins_continuation_lines() {
+        assert_eq!(
+            words("curl \\\n  -X POST \\\r\n  https://example.com"),
+            ["curl", "-X", "POST", "https://example.com"]
+        );
+        assert_eq!(words("\"a\\\nb\""), ["ab"]);
+    }
+
+    #[test]
+    fn rejects_unterminated_quotes() {
+        for command_line in ["curl 'a", "curl \"a", "curl $'a"] {
+            assert!(matches!(split(command_line), Err(Error::Syntax(_))));
+        }
+    }
+
+    #[test]
+    fn parses_method_headers_and_url() {
+        let request = parse(
+            "curl -XPUT example.com/items -H 'Accept: application/json' -H 'X-Empty;' \
+             --header 'X-Id:  7 '",
+        )
+        .unwrap();
+
+        assert_eq!(request.method, Method::PUT);
+        assert_eq!(request.url, "http://example.com/items");
+        assert_eq!(
+            header_pairs(&request),
+            [
+                ("Accept", "application/json"),
+                ("X-Empty", ""),
+                ("X-Id", "7")
+            ]
+        );
+        assert!(!request.options.follow_redirects);
+    }
+
+    #[test]
+    fn parses_grouped_short_flags() {
+        let request = parse("curl -sSLk -m 2.5 https://example.com").unwrap();
+
+        assert_eq!(request.method, Method::GET);
+        assert!(request.options.follow_redirects);
+        assert!(request.options.insecure);
+        assert_eq!(request.options.timeout_ms, Some(2500));
+    }
+
+    #[test]
+    fn grouped_flags_take_the_rest_as_value() {
+        let request = parse("curl -sXDELETE https://example.com").unwrap();
+        assert_eq!(request.method, Method::DELETE);
+    }
+
+    #[test]
+    fn data_makes_a_form_post() {
+        let request = parse("curl https://example.com -d a=1 --data-raw 'b=@2'").unwrap();
+
+        assert_eq!(request.method, Method::POST);
+        assert_eq!(
+            header_pairs(&request),
+            [("Content-Type", "application/x-www-form-urlencoded")]
+        );
+        assert_eq!(
+            request.body,
+            BodySpec::Text {
+                text: "a=1&b=@2".to_string()
+            }
+        );
+    }
+
+    #[test]
+    fn get_moves_data_to_the_query() {
+        let request = parse("curl -G https://example.com -d 'q=a b' -d page=2").unwrap();
+
+        assert_eq!(request.method, Method::GET);
+        assert_eq!(request.body, BodySpec::Empty);
+        assert_eq!(
+            request.query,
+            [KeyValue::new("q", "a b"), KeyValue::new("page", "2")]
+        );
+    }
+
+    #[test]
+    fn url_encodes_data() {
+        let request = parse(
+            "curl https://example.com --data-urlencode 'q=a&b c' --data-urlencode '=x=y' \
+             --data-urlencode plain",
+        )
+        .unwrap();
+
+        assert_eq!(
+            request.body,
+            BodySpec::Text {
+                text: "q=a%26b+c&x%3Dy&plain".to_string()
+            }
+        );
+    }
+
+    #[test]
+    fn json_sets_content_type_and_accept() {
+        let request =
+            parse(r#"curl https://example.com --json '{"a":1}' -H 'accept: */*'"#).unwrap();
+
+        assert_eq!(
+            header_pairs(&request),
+            [("accept", "*/*"), ("Content-Type", "application/json")]
+        );
+        assert_eq!(
+            request.body,
+            BodySpec::Text {
+                text: r#"{"a":1}"#.to_string()
+            }
+        );
+    }
+
+    #[test]
+    fn parses_form_parts() {
+        let request = parse(
+            "curl https://example.com -F 'file=@/tmp/a b.png;type=image/png;filename=\"c.png\"' \
+             -F 'note=x;y' -F 'kind=a;type=text/plain' --form-string 'raw=@not-a-file'",
+        )
+        .unwrap();
+
+        assert_eq!(request.method, Method::POST);
+        let BodySpec::Multipart { parts } = request.body else {
+            panic!("expected a multipart body, got {:?}", request.body);
+        };
+        assert_eq!(parts.len(), 4);
+        assert_eq!(parts[0].name, "file");
+        assert_eq!(
+            parts[0].value,
+            PartValue::File {
+                path: PathBuf::from("/tmp/a b.png")
+            }
+        );
+        assert_eq!(parts[0].content_type.as_deref(), Some("image/png"));
+        assert_eq!(parts[0].filename.as_deref(), Some("c.png"));
+        assert_eq!(parts[1], MultipartPart::text("note", "x;y"));
+        assert_eq!(
+            parts[2].value,
+            PartValue::Text {
+                text: "a".to_string()
+            }
+        );
+        assert_eq!(parts[2].content_type.as_deref(), Some("text/plain"));
+        assert_eq!(parts[3], MultipartPart::text("raw", "@not-a-file"));
+    }
+
+    #[test]
+    fn maps_auth_options() {
+        let basic = parse("curl -u user:pa:ss https://example.com").unwrap();
+        assert_eq!(
+            basic.auth,
+            Auth::Basic {
+                username: "user".to_string(),
+                password: "pa:ss".to_string()
+            }
+        );
+
+        let digest = parse("curl --digest -u user https://example.com").unwrap();
+        assert_eq!(
+            digest.auth,
+            Auth::Digest {
+                username: "user".to_string(),
+                password: String::new()
+            }
+        );
+
+        let bearer = parse("curl --oauth2-bearer abc https://example.com").unwrap();
+        assert_eq!(
+            bearer.auth,
+            Auth::Bearer {
+                token: "abc".to_string()
+            }
+        );
+    }
+
+    #[test]
+    fn rejects_unknown_options() {
+        for (command_line, option) in [
+            ("curl --proxy http://p https://example.com", "--proxy"),
+            ("curl -sz https://example.com", "-z"),
+        ] {
+            match parse(command_line) {
+                Err(Error::UnsupportedOption(name)) => assert_eq!(name, option),
+                other => panic!("expected '{option}' to be rejected, got {other:?}"),
+            }
+        }
+    }
+
+    #[test]
+    fn reports_missing_values_and_urls() {
+        assert!(matches!(
+            parse("curl https://example.com -H"),
+            Err(Error::MissingValue(option)) if option == "-H"
+        ));
+        assert!(matches!(parse("curl -s"), Err(Error::MissingUrl)));
+        assert!(matches!(
+            parse("curl a.com b.com"),
+            Err(Error::UnsupportedOption(_))
+        ));
+    }
+}
@@ -0,0 +1,80 @@
+use std::fmt;
+use std::path::PathBuf;
+
+#[derive(Debug)]
+pub enum Error {
+    /// The command line could not be split into words (e.g. an unterminated quote).
+    Syntax(String),
+    MissingValue(String),
+    UnsupportedOption(String),
+    MissingUrl,
+    InvalidHeader(String),
+    InvalidValue {
+        option: String,
+        value: String,
+    },
+    Io {
+        path: PathBuf,
+        source: std::io::Error,
+    },
+    Parse(serde_json::Error),
+    ParseYaml(serde_yaml::Error),
+    /// The document parsed but is not in the expected format.
+    UnsupportedFormat(String),
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Error::Syntax(reason) => write!(f, "invalid command line: {reason}"),
+            Error::MissingValue(option) => write!(f, "option '{option}' needs a value"),
+            Error::UnsupportedOption(option) => write!(f, "unsupported option '{option}'"),
+            Error::MissingUrl => write!(f, "no URL given"),
+            Error::InvalidHeader(header) => {
+                write!(f, "invalid header '{header}', expected 'Name: value'")
+            }
+            Error::InvalidValue { option, value } => {
+                write!(f, "invalid value '{value}' for option '{option}'")
+            }
+            Error::Io { path, source } => write!(f, "{}: {source}", path.display()),
+            Error::Parse(source) => write!(f, "invalid JSON: {source}"),
+            Error::ParseYaml(source) => write!(f, "invalid YAML: {source}"),
+            Error::UnsupportedFormat(reason) => write!(f, "unsupported file: {reason}"),
+        }
+    }
+}
+
+impl std::error::Error for Error {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Error::Io { source, .. } => Some(source),
+            Error::Parse(source) => Some(source),
+            Error::ParseYaml(source) => Some(source),
+            _ => None,
+        }
+    }
+}
+
+impl From<serde_json::Error> for Error {
+    fn from(error: serde_json::Error) -> Self {
+        Error::Parse(error)
+    }
+}
+
+impl From<serde_yaml::Error> for Error {
+    fn from(error: serde_yaml::Error) -> Self {
+        Error::ParseYaml(error)
+    }
+}
+
+impl From<crate::core::openapi::Error> for Error {
+    fn from(error: crate::core::openapi::Error) -> Self {
+        use crate::core::openapi::Error as OpenApiError;
+        match error {
+            OpenApiError::Io { path, source } => Error::Io { path, source },
+            OpenApiError::Parse(source) => Error::Parse(source),
+            OpenApiError::ParseYaml(source) => Error::ParseYaml(source),
+            OpenApiError::NotOpenApi => Error::UnsupportedFormat(error.to_string()),
+        }
+    }
+}
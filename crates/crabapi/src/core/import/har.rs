@@ -0,0 +1,285 @@
+use super::{Error, Import, ImportedRequest, entry_name, read_file, text, unique_name, value_text};
+use crate::core::collections::ReferenceResponse;
+use crate::core::requests::constants::FORM_CONTENT_TYPE;
+use crate::core::requests::{BodySpec, KeyValue, Method, MultipartPart, RequestSpec};
+use serde_json::Value;
+use std::path::Path;
+
+/// Headers the client computes itself; replaying the recorded ones would be wrong.
+const SKIPPED_HEADERS: [&str; 3] = ["content-length", "host", "connection"];
+
+/// Reads the `log.entries` of an HTTP Archive, keeping each recorded response as the
+/// request's reference response.
+pub fn parse(json: &str) -> Result<Import, Error> {
+    let document: Value = serde_json::from_str(json)?;
+    if !detect(&document) {
+        return Err(Error::UnsupportedFormat("not a HAR file".to_string()));
+    }
+
+    let mut import = Import::default();
+    let mut taken = vec![];
+    for entry in document["log"]["entries"].as_array().into_iter().flatten() {
+        let source = &entry["request"];
+        let url = text(&source["url"]);
+        let name = unique_name(entry_name(&name(text(&source["method"]), url)), &mut taken);
+        let request = request(&mut import, source, &name);
+        let response = response(&entry["response"]);
+        import.requests.push(ImportedRequest {
+            path: name,
+            request,
+            response,
+        });
+    }
+    Ok(import)
+}
+
+pub fn parse_file(path: &Path) -> Result<Import, Error> {
+    parse(&read_file(path)?)
+}
+
+/// Whether `document` looks like something [`parse`] understands.
+pub fn detect(document: &Value) -> bool {
+    document["log"]["entries"].is_array()
+}
+
+/// `GET users-42` for `GET https://example.com/users/42?page=1`.
+fn name(method: &str, url: &str) -> String {
+    let path = match url::Url::parse(url) {
+        Ok(url) => match url.path().trim_matches('/') {
+            "" => url.host_str().unwrap_or_default().to_string(),
+            path => path.to_string(),
+        },
+        Err(_) => url.to_string(),
+    };
+    format!("{method} {path}")
+}
+
+fn request(import: &mut Import, source: &Value, path: &str) -> RequestSpec {
+    let method = text(&source["method"]).parse().unwrap_or_else(|_| {
+        import.warn(format!("{path}: invalid method, using GET"));
+        Method::GET
+    });
+
+    // The query is recorded both in the URL and, decoded, in `queryString`.
+    let url = text(&source["url"]);
+    let mut request = match source["queryString"].as_array() {
+        Some(query) if !query.is_empty() => {
+            let base = url.split(['?', '#']).next().unwrap_or_default();
+            let mut request = RequestSpec::new(method, base);
+            request.query = pairs(&source["queryString"]);
+            request
+        }
+        _ => RequestSpec::new(method, url.split('#').next().unwrap_or_default()),
+    };
+
+    request.headers = pairs(&source["headers"])
+        .into_iter()
+        .filter(|header| {
+            // HTTP/2 pseudo headers such as `:authority` are not real headers.
+            !header.key.starts_with(':')
+                && !SKIPPED_HEADERS.contains(&header.key.to_lowercase().as_str())
+        })
+        .collect();
+
+    let post_data = &source["postData"];
+    if !text(&post_data["text"]).is_empty() {
+        request.body = BodySpec::Text {
+            text: text(&post_data["text"]).to_string(),
+        };
+    } else if let Some(params) = post_data["params"].as_array().filter(|p| !p.is_empty()) {
+        request.body = match text(&post_data["mimeType"])
+            .to_ascii_lowercase()
+            .starts_with(FORM_CONTENT_TYPE)
+        {
+            true => BodySpec::Form {
+                fields: params
+                    .iter()
+                    .map(|param| KeyValue::new(text(&param["name"]), value_text(&param["value"])))
+                    .collect(),
+            },
+            false => multipart(import, params, path),
+        };
+        // The recorded header may carry the boundary of the original request.
+        request
+            .headers
+            .retain(|header| !header.key.eq_ignore_ascii_case("content-type"));
+    }
+    request
+}
+
+fn multipart(import: &mut Import, params: &[Value], path: &str) -> BodySpec {
+    let mut parts = vec![];
+    for param in params {
+        let name = text(&param["name"]);
+        let mut part = match text(&param["fileName"]) {
+            "" => MultipartPart::text(name, value_text(&param["value"])),
+            file_name => {
+                import.warn(format!(
+                    "{path}: HAR files do not contain uploaded files, set the path of '{file_name}' in part '{name}'"
+                ));
+                MultipartPart::file(name, file_name)
+            }
+        };
+        if !text(&param["contentType"]).is_empty() {
+            part.content_type = Some(text(&param["contentType"]).to_string());
+        }
+        parts.push(part);
+    }
+    BodySpec::Multipart { parts }
+}
+
+fn response(source: &Value) -> Option<ReferenceResponse> {
+    // Requests that never got an answer are recorded with status 0.
+    let status = source["status"]
+        .as_u64()
+        .and_then(|status| u16::try_from(status).ok())
+        .filter(|status| *status != 0)?;
+    let content = &source["content"];
+    Some(ReferenceResponse {
+        status,
+        status_text: text(&source["statusText"]).to_string(),
+        headers: pairs(&source["headers"]),
+        body: text(&content["text"]).to_string(),
+        encoding: content["encoding"].as_str().map(str::to_string),
+    })
+}
+
+/// `[{"name": ..., "value": ...}]` rows, as used for headers and query strings.
+fn pairs(rows: &Value) -> Vec<KeyValue> {
+    rows.as_array()
+        .into_iter()
+        .flatten()
+        .map(|row| KeyValue::new(text(&row["name"]), value_text(&row["value"])))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::import::fixture;
+    use crate::core::requests::PartValue;
+
+    fn capture() -> Import {
+        parse_file(&fixture("har/capture.har")).unwrap()
+    }
+
+    #[test]
+    fn names_requests_after_method_and_path() {
+        let import = capture();
+        let paths: Vec<&str> = import.requests.iter().map(|r| r.path.as_str()).collect();
+
+        assert_eq!(
+            paths,
+            ["GET users-42", "POST users", "POST 127.0.0.1", "POST login"]
+        );
+    }
+
+    #[test]
+    fn splits_query_and_skips_computed_headers() {
+        let import = capture();
+        let get = &import.requests[0].request;
+
+        assert_eq!(get.method, Method::GET);
+        assert_eq!(get.url, "http://127.0.0.1:7878/users/42");
+        assert_eq!(
+            get.query,
+            [KeyValue::new("expand", "roles"), KeyValue::new("q", "a b")]
+        );
+        assert_eq!(
+            get.headers,
+            [
+                KeyValue::new("Accept", "application/json"),
+                KeyValue::new("Cookie", "session=abc"),
+            ]
+        );
+
+        // HTTP/2 pseudo headers and content-length are dropped.
+        let post = &import.requests[1].request;
+        assert_eq!(
+            post.headers,
+            [KeyValue::new("content-type", "application/json")]
+        );
+        assert_eq!(
+            post.body,
+            BodySpec::Text {
+                text: r#"{"name":"Ferris"}"#.to_string()
+            }
+        );
+    }
+
+    #[test]
+    fn keeps_recorded_responses() {
+        let import = capture();
+
+        let json = import.requests[0].response.as_ref().unwrap();
+        assert_eq!(json.status, 200);
+        assert_eq!(json.status_text, "OK");
+        assert_eq!(json.body, r#"{"id": 42, "name": "Ferris"}"#);
+        assert_eq!(json.encoding, None);
+
+        let png = import.requests[1].response.as_ref().unwrap();
+        assert_eq!(png.status, 201);
+        assert_eq!(png.body, "iVBORw==");
+        assert_eq!(png.encoding.as_deref(), Some("base64"));
+
+        // Status 0 means the request never got a response.
+        assert_eq!(import.requests[2].response, None);
+    }
+
+    #[test]
+    fn maps_params_by_mime_type() {
+        let import = capture();
+
+        let upload = &import.requests[2].request;
+        assert!(upload.headers.is_empty());
+        let BodySpec::Multipart { parts } = &upload.body else {
+            panic!("expected a multipart body, got {:?}", upload.body);
+        };
+        assert_eq!(parts[0].name, "caption");
+        assert_eq!(
+            parts[0].value,
+            PartValue::Text {
+                text: "Me".to_string()
+            }
+        );
+        assert_eq!(parts[1].name, "avatar");
+        assert_eq!(
+            parts[1].value,
+            PartValue::File {
+                path: "avatar.png".into()
+            }
+        );
+        assert_eq!(parts[1].content_type.as_deref(), Some("image/png"));
+
+        let login = &import.requests[3].request;
+        assert!(login.headers.is_empty());
+        assert_eq!(
+            login.body,
+            BodySpec::Form {
+                fields: vec![
+                    KeyValue::new("username", "ferris"),
+                    KeyValue::new("password", "p@ss word"),
+                ]
+            }
+        );
+    }
+
+    #[test]
+    fn warns_about_missing_uploads() {
+        assert_eq!(
+            capture().warnings,
+            [
+                "POST 127.0.0.1: HAR files do not contain uploaded files, set the path of \
+                 'avatar.png' in part 'avatar'"
+            ]
+        );
+    }
+
+    #[test]
+    fn rejects_other_documents() {
+        assert!(matches!(
+            parse(r#"{"info": {}}"#),
+            Err(Error::UnsupportedFormat(_))
+        ));
+    }
+}
@@ -0,0 +1,554 @@
+use super::{
+    Error, Import, ImportedRequest, default_header, entry_name, read_file, text, unique_name,
+    value_text,
+};
+use crate::core::auth::{ApiKeyLocation, Auth, OAuth2, OAuth2Grant};
+use crate::core::environments::{Environment, placeholders};
+use crate::core::requests::{BodySpec, KeyValue, Method, MultipartPart, RequestSpec};
+use serde_json::Value;
+use std::collections::HashMap;
+use std::path::Path;
+
+const EXPORT_FORMAT: u64 = 4;
+
+/// Reads an Insomnia v4 JSON export: workspaces, folders, requests and environments.
+pub fn parse(json: &str) -> Result<Import, Error> {
+    let document: Value = serde_json::from_str(json)?;
+    if !detect(&document) {
+        return Err(Error::UnsupportedFormat(
+            "not an Insomnia export".to_string(),
+        ));
+    }
+    match document["__export_format"].as_u64() {
+        Some(EXPORT_FORMAT) => Ok(Export::new(&document).import()),
+        format => Err(Error::UnsupportedFormat(format!(
+            "Insomnia export format {}, expected {EXPORT_FORMAT}",
+            format.map_or("unknown".to_string(), |format| format.to_string())
+        ))),
+    }
+}
+
+pub fn parse_file(path: &Path) -> Result<Import, Error> {
+    parse(&read_file(path)?)
+}
+
+/// Whether `document` looks like something [`parse`] understands.
+pub fn detect(document: &Value) -> bool {
+    document["_type"] == "export" && document["resources"].is_array()
+}
+
+/// Resources of the export indexed by their parent.
+struct Export<'a> {
+    children: HashMap<&'a str, Vec<&'a Value>>,
+    workspaces: Vec<&'a Value>,
+}
+
+impl<'a> Export<'a> {
+    fn new(document: &'a Value) -> Self {
+        let mut children: HashMap<&str, Vec<&Value>> = HashMap::new();
+        let mut workspaces = vec![];
+        for resource in document["resources"].as_array().into_iter().flatten() {
+            if resource["_type"] == "workspace" {
+                workspaces.push(resource);
+            }
+            children
+                .entry(text(&resource["parentId"]))
+                .or_default()
+                .push(resource);
+        }
+        // Keep the order shown in Insomnia's sidebar.
+        for siblings in children.values_mut() {
+            siblings.sort_by(|a, b| {
+                let key = |resource: &Value| resource["metaSortKey"].as_f64().unwrap_or_default();
+                key(a).total_cmp(&key(b))
+            });
+        }
+        Self {
+            children,
+            workspaces,
+        }
+    }
+
+    fn children(&self, parent: &Value) -> impl Iterator<Item = &'a Value> + use<'a, '_> {
+        self.children
+            .get(text(&parent["_id"]))
+            .into_iter()
+            .flatten()
+            .copied()
+    }
+
+    fn import(&self) -> Import {
+        let mut import = Import::default();
+        // A single workspace becomes the import's folder, several get one folder each.
+        let single = self.workspaces.len() == 1;
+        let mut taken = vec![];
+        for workspace in &self.workspaces {
+            let name = match text(&workspace["name"]) {
+                "" => "Insomnia workspace",
+                name => name,
+            };
+            let folder = match single {
+                true => {
+                    import.name = Some(entry_name(name));
+                    String::new()
+                }
+                false => unique_name(entry_name(name), &mut taken),
+            };
+            self.environments(&mut import, workspace, name);
+            self.items(&mut import, workspace, &folder, None);
+        }
+        import
+    }
+
+    /// The base environment, merged into each of its sub environments when there are any.
+    fn environments(&self, import: &mut Import, workspace: &Value, name: &str) {
+        for base in self.children(workspace) {
+            if base["_type"] != "environment" {
+                continue;
+            }
+            let variables = variables(&base["data"]);
+            let mut subs = self
+                .children(base)
+                .filter(|sub| sub["_type"] == "environment")
+                .peekable();
+
+            if subs.peek().is_none() {
+                if !variables.is_empty() {
+                    import.environments.push(Environment {
+                        name: name.to_string(),
+                        variables,
+                    });
+                }
+                continue;
+            }
+            for sub in subs {
+                let mut merged = variables.clone();
+                for variable in self::variables(&sub["data"]) {
+                    match merged.iter_mut().find(|row| row.key == variable.key) {
+                        Some(row) => *row = variable,
+                        None => merged.push(variable),
+                    }
+                }
+                import.environments.push(Environment {
+                    name: text(&sub["name"]).to_string(),
+                    variables: merged,
+                });
+            }
+        }
+    }
+
+    fn items(&self, import: &mut Import, parent: &Value, folder: &str, auth: Option<&Value>) {
+        let mut taken = vec![];
+        for item in self.children(parent) {
+            let kind = text(&item["_type"]);
+            if !matches!(
+                kind,
+                "request_group" | "request" | "grpc_request" | "websocket_request"
+            ) {
+                continue;
+            }
+            let name = unique_name(entry_name(text(&item["name"])), &mut taken);
+            let path = match folder {
+                "" => name,
+                folder => format!("{folder}/{name}"),
+            };
+
+            match kind {
+                "request_group" => {
+                    if item["environment"]
+                        .as_object()
+                        .is_some_and(|data| !data.is_empty())
+                    {
+                        import.warn(format!("{path}: folder environment was not imported"));
+                    }
+                    let auth = auth_block(item).or(auth);
+                    self.items(import, item, &path, auth);
+                }
+                "request" => {
+                    let request = request(import, item, auth_block(item).or(auth), &path);
+                    import.requests.push(ImportedRequest {
+                        path,
+                        request,
+                        response: None,
+                    });
+                }
+                _ => import.warn(format!("{path}: {kind} is not supported")),
+            }
+        }
+    }
+}
+
+/// The resource's own auth, unless it is empty and so falls back to its folder's.
+fn auth_block(resource: &Value) -> Option<&Value> {
+    let auth = &resource["authentication"];
+    auth.as_object()
+        .is_some_and(|auth| !auth.is_empty())
+        .then_some(auth)
+}
+
+fn request(import: &mut Import, item: &Value, auth: Option<&Value>, path: &str) -> RequestSpec {
+    let method = match text(&item["method"]) {
+        "" => Method::GET,
+        method => method.to_uppercase().parse().unwrap_or_else(|_| {
+            import.warn(format!("{path}: invalid method '{method}', using GET"));
+            Method::GET
+        }),
+    };
+    let mut request = RequestSpec::new(method, template(text(&item["url"])));
+    request.query = pairs(&item["parameters"]);
+    request.headers = pairs(&item["headers"]);
+
+    body(import, &item["body"], &mut request, path);
+    if let Some(auth) = auth {
+        request.auth = self::auth(import, auth, &mut request, path);
+    }
+    match text(&item["settingFollowRedirects"]) {
+        "on" => request.options.follow_redirects = true,
+        "off" => request.options.follow_redirects = false,
+        _ => {}
+    }
+
+    if serde_json::to_string(&request).is_ok_and(|json| json.contains("{%")) {
+        import.warn(format!(
+            "{path}: Insomnia template tags ({{% ... %}}) are not supported"
+        ));
+    }
+    request
+}
+
+fn body(import: &mut Import, body: &Value, request: &mut RequestSpec, path: &str) {
+    let mime_type = text(&body["mimeType"]);
+    match mime_type {
+        "application/x-www-form-urlencoded" => {
+            let fields = body["params"]
+                .as_array()
+                .into_iter()
+                .flatten()
+                .map(|field| KeyValue {
+                    key: template(text(&field["name"])),
+                    value: template(&value_text(&field["value"])),
+                    enabled: field["disabled"].as_bool() != Some(true),
+                })
+                .collect();
+            request.body = BodySpec::Form { fields };
+        }
+        "multipart/form-data" => {
+            let mut parts = vec![];
+            for field in body["params"].as_array().into_iter().flatten() {
+                let name = template(text(&field["name"]));
+                if field["disabled"].as_bool() == Some(true) {
+                    import.warn(format!("{path}: disabled form field '{name}' skipped"));
+                    continue;
+                }
+                parts.push(match text(&field["type"]) {
+                    "file" => MultipartPart::file(name, text(&field["fileName"])),
+                    _ => MultipartPart::text(name, template(&value_text(&field["value"]))),
+                });
+            }
+            request.body = BodySpec::Multipart { parts };
+        }
+        "application/graphql" => {
+            // The text already is the JSON payload, `{"query": ..., "variables": ...}`.
+            default_header(request, "Content-Type", "application/json");
+            request.body = BodySpec::Text {
+                text: template(text(&body["text"])),
+            };
+        }
+        _ if !text(&body["fileName"]).is_empty() => {
+            request.body = BodySpec::File {
+                path: text(&body["fileName"]).into(),
+            };
+        }
+        _ if !text(&body["text"]).is_empty() => {
+            if !mime_type.is_empty() {
+                default_header(request, "Content-Type", mime_type);
+            }
+            request.body = BodySpec::Text {
+                text: template(text(&body["text"])),
+            };
+        }
+        _ => {}
+    }
+}
+
+fn auth(import: &mut Import, auth: &Value, request: &mut RequestSpec, path: &str) -> Auth {
+    if auth["disabled"].as_bool() == Some(true) {
+        return Auth::None;
+    }
+    let field = |key: &str| template(&value_text(&auth[key]));
+
+    match text(&auth["type"]) {
+        "none" | "" => Auth::None,
+        "basic" => Auth::Basic {
+            username: field("username"),
+            password: field("password"),
+        },
+        "digest" => Auth::Digest {
+            username: field("username"),
+            password: field("password"),
+        },
+        "bearer" => match field("prefix").as_str() {
+            "" | "Bearer" => Auth::Bearer {
+                token: field("token"),
+            },
+            prefix => {
+                // A custom prefix cannot be expressed as bearer auth, send the header as is.
+                default_header(
+                    request,
+                    "Authorization",
+                    &format!("{prefix} {}", field("token")),
+                );
+                Auth::None
+            }
+        },
+        "apikey" => match text(&auth["addTo"]) {
+            "cookie" => {
+                default_header(
+                    request,
+                    "Cookie",
+                    &format!("{}={}", field("key"), field("value")),
+                );
+                Auth::None
+            }
+            add_to => Auth::ApiKey {
+                key: field("key"),
+                value: field("value"),
+                location: match add_to {
+                    "queryParams" => ApiKeyLocation::Query,
+                    _ => ApiKeyLocation::Header,
+                },
+            },
+        },
+        "oauth2" => {
+            let grant = match text(&auth["grantType"]) {
+                "" | "client_credentials" => OAuth2Grant::ClientCredentials,
+                "password" => OAuth2Grant::Password {
+                    username: field("username"),
+                    password: field("password"),
+                },
+                grant => {
+                    import.warn(format!(
+                        "{path}: OAuth 2.0 grant '{grant}' is not supported"
+                    ));
+                    return Auth::None;
+                }
+            };
+            Auth::OAuth2(OAuth2 {
+                token_url: field("accessTokenUrl"),
+                client_id: field("clientId"),
+                client_secret: field("clientSecret"),
+                scope: field("scope"),
+                credentials_in_body: auth["credentialsInBody"].as_bool().unwrap_or(false),
+                grant,
+            })
+        }
+        kind => {
+            import.warn(format!("{path}: auth type '{kind}' is not supported"));
+            Auth::None
+        }
+    }
+}
+
+/// `[{"name": ..., "value": ..., "disabled": ...}]` rows, as used for headers and parameters.
+fn pairs(rows: &Value) -> Vec<KeyValue> {
+    rows.as_array()
+        .into_iter()
+        .flatten()
+        .filter(|row| !text(&row["name"]).is_empty())
+        .map(|row| KeyValue {
+            key: template(text(&row["name"])),
+            value: template(&value_text(&row["value"])),
+            enabled: !row["disabled"].as_bool().unwrap_or(false),
+        })
+        .collect()
+}
+
+/// Environment data, with nested objects flattened to dotted names (`auth.token`) the way
+/// Insomnia templates refer to them.
+fn variables(data: &Value) -> Vec<KeyValue> {
+    fn flatten(prefix: &str, data: &Value, variables: &mut Vec<KeyValue>) {
+        match data {
+            Value::Object(fields) => {
+                for (key, value) in fields {
+                    let name = match prefix {
+                        "" => key.clone(),
+                        prefix => format!("{prefix}.{key}"),
+                    };
+                    flatten(&name, value, variables);
+                }
+            }
+            value if !prefix.is_empty() => {
+                variables.push(KeyValue::new(prefix, template(&value_text(value))))
+            }
+            _ => {}
+        }
+    }
+
+    let mut variables = vec![];
+    flatten("", data, &mut variables);
+    variables
+}
+
+/// Rewrites Insomnia's `{{ _.name }}` placeholders as `{{name}}`.
+fn template(input: &str) -> String {
+    let mut output = String::with_capacity(input.len());
+    let mut last = 0;
+    for (range, name) in placeholders(input) {
+        output.push_str(&input[last..range.start]);
+        output.push_str(&format!(
+            "{{{{{}}}}}",
+            name.strip_prefix("_.").unwrap_or(name)
+        ));
+        last = range.end;
+    }
+    output.push_str(&input[last..]);
+    output
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::import::fixture;
+    use crate::core::requests::PartValue;
+
+    fn export() -> Import {
+        parse_file(&fixture("insomnia/export.json")).unwrap()
+    }
+
+    fn request<'a>(import: &'a Import, path: &str) -> &'a RequestSpec {
+        &import
+            .requests
+            .iter()
+            .find(|imported| imported.path == path)
+            .unwrap_or_else(|| panic!("no request at {path}"))
+            .request
+    }
+
+    #[test]
+    fn keeps_folder_structure_and_order() {
+        let import = export();
+        let paths: Vec<&str> = import.requests.iter().map(|r| r.path.as_str()).collect();
+
+        assert_eq!(import.name.as_deref(), Some("Echo API"));
+        assert_eq!(
+            paths,
+            [
+                "Users/Get user",
+                "Users/Create user",
+                "Login",
+                "Upload avatar",
+                "Search",
+            ]
+        );
+    }
+
+    #[test]
+    fn maps_templates_parameters_and_bodies() {
+        let import = export();
+
+        let get = request(&import, "Users/Get user");
+        assert_eq!(get.url, "{{base_url}}/users/42");
+        assert_eq!(get.query[0], KeyValue::new("expand", "roles"));
+        assert_eq!(get.query[1].key, "debug");
+        assert!(!get.query[1].enabled);
+
+        let login = request(&import, "Login");
+        assert!(!login.options.follow_redirects);
+        let BodySpec::Form { fields } = &login.body else {
+            panic!("expected a form body, got {:?}", login.body);
+        };
+        assert_eq!(fields[1], KeyValue::new("password", "p@ss word"));
+        assert!(!fields[2].enabled);
+
+        let upload = request(&import, "Upload avatar");
+        let BodySpec::Multipart { parts } = &upload.body else {
+            panic!("expected a multipart body, got {:?}", upload.body);
+        };
+        assert_eq!(
+            parts[1].value,
+            PartValue::File {
+                path: "/tmp/avatar.png".into()
+            }
+        );
+
+        // GraphQL bodies are sent as JSON.
+        let search = request(&import, "Search");
+        assert_eq!(
+            search.headers,
+            [KeyValue::new("Content-Type", "application/json")]
+        );
+    }
+
+    #[test]
+    fn maps_auth_with_inheritance() {
+        let import = export();
+
+        assert_eq!(
+            request(&import, "Users/Get user").auth,
+            Auth::Bearer {
+                token: "{{auth.token}}".to_string()
+            }
+        );
+        assert_eq!(
+            request(&import, "Users/Create user").auth,
+            Auth::Basic {
+                username: "admin".to_string(),
+                password: "hunter2".to_string(),
+            }
+        );
+        assert_eq!(request(&import, "Login").auth, Auth::None);
+        assert_eq!(
+            request(&import, "Upload avatar").auth,
+            Auth::ApiKey {
+                key: "api_key".to_string(),
+                value: "abc123".to_string(),
+                location: ApiKeyLocation::Query,
+            }
+        );
+        assert_eq!(
+            request(&import, "Search").auth,
+            Auth::OAuth2(OAuth2 {
+                token_url: "http://127.0.0.1:7881/token".to_string(),
+                client_id: "app".to_string(),
+                client_secret: "s3cret".to_string(),
+                scope: "read".to_string(),
+                credentials_in_body: false,
+                grant: OAuth2Grant::ClientCredentials,
+            })
+        );
+    }
+
+    #[test]
+    fn merges_sub_environments_over_the_base() {
+        let import = export();
+
+        assert_eq!(import.environments.len(), 2);
+        assert_eq!(import.environments[0].name, "Local");
+        assert_eq!(
+            import.environments[0].variables,
+            [
+                KeyValue::new("base_url", "http://127.0.0.1:7878"),
+                KeyValue::new("auth.token", "local-token"),
+            ]
+        );
+        assert_eq!(import.environments[1].name, "Production");
+        assert_eq!(
+            import.environments[1].variables,
+            [
+                KeyValue::new("base_url", "https://api.example.com"),
+                KeyValue::new("auth.token", "base-token"),
+            ]
+        );
+    }
+
+    #[test]
+    fn warns_about_unsupported_features() {
+        assert_eq!(
+            export().warnings,
+            [
+                "Users/Create user: Insomnia template tags ({% ... %}) are not supported",
+                "Stream: grpc_request is not supported",
+            ]
+        );
+    }
+}
@@ -0,0 +1,170 @@
+//! Turns requests written for other tools into [`crate::core::requests::RequestSpec`]s.
+
+mod error;
+
+pub mod curl;
+pub mod har;
+pub mod insomnia;
+pub mod openapi;
+pub mod postman;
+
+use crate::core::collections::{self, Collection, ReferenceResponse, SavedRequest};
+use crate::core::environments::{Environment, Environments};
+use crate::core::requests::{KeyValue, RequestSpec};
+use serde_json::Value;
+use std::path::Path;
+
+pub use error::Error;
+
+/// Requests and environments read from another tool's export. Everything that could not
+/// be mapped is described in `warnings` instead of being dropped silently.
+#[derive(Debug, Clone, Default)]
+pub struct Import {
+    /// Name of the imported collection, used as the default folder.
+    pub name: Option<String>,
+    pub requests: Vec<ImportedRequest>,
+    pub environments: Vec<Environment>,
+    pub warnings: Vec<String>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct ImportedRequest {
+    /// `/` separated path relative to the import's folder, made of valid entry names.
+    pub path: String,
+    pub request: RequestSpec,
+    /// Response recorded with the request, when the export has one.
+    pub response: Option<ReferenceResponse>,
+}
+
+impl Import {
+    /// Saves every request under `folder` (the collection root when empty), overwriting
+    /// requests with the same path, and returns the paths written.
+    pub fn save_requests(
+        &self,
+        collection: &Collection,
+        folder: &str,
+    ) -> Result<Vec<String>, collections::Error> {
+        let mut paths = vec![];
+        for imported in &self.requests {
+            let path = match folder {
+                "" => imported.path.clone(),
+                folder => format!("{folder}/{}", imported.path),
+            };
+            let saved = SavedRequest::new(imported.request.clone())
+                .with_response(imported.response.clone());
+            collection.save(&path, &saved)?;
+            paths.push(path);
+        }
+        Ok(paths)
+    }
+
+    /// Folder to import into by default: the collection's name, or else the file's.
+    pub fn default_folder(&self, file: &Path) -> String {
+        match &self.name {
+            Some(name) => name.clone(),
+            None => entry_name(&file.file_stem().unwrap_or_default().to_string_lossy()),
+        }
+    }
+
+    /// Adds the imported environments, replacing variables that already exist.
+    pub fn merge_environments(&self, environments: &mut Environments) {
+        for imported in &self.environments {
+            let environment = environments.get_or_insert(&imported.name);
+            for variable in &imported.variables {
+                match environment
+                    .variables
+                    .iter_mut()
+                    .find(|row| row.key == variable.key)
+                {
+                    Some(row) => *row = variable.clone(),
+                    None => environment.variables.push(variable.clone()),
+                }
+            }
+        }
+    }
+
+    fn warn(&mut self, warning: impl Into<String>) {
+        self.warnings.push(warning.into());
+    }
+}
+
+/// Reads an export file, detecting which tool wrote it.
+pub fn from_file(path: &Path) -> Result<Import, Error> {
+    let content = read_file(path)?;
+    let document = crate::core::openapi::document(&content)?;
+    if postman::detect(&document) {
+        postman::parse(&content)
+    } else if insomnia::detect(&document) {
+        insomnia::parse(&content)
+    } else if har::detect(&document) {
+        har::parse(&content)
+    } else if openapi::detect(&document) {
+        openapi::parse(&content)
+    } else {
+        Err(Error::UnsupportedFormat(format!(
+            "{} is not a Postman, Insomnia, HAR or OpenAPI file",
+            path.display()
+        )))
+    }
+}
+
+/// Path of a file in the crate's `fixtures` folder.
+#[cfg(test)]
+fn fixture(name: &str) -> std::path::PathBuf {
+    Path::new(env!("CARGO_MANIFEST_DIR"))
+        .join("fixtures")
+        .join(name)
+}
+
+fn read_file(path: &Path) -> Result<String, Error> {
+    std::fs::read_to_string(path).map_err(|source| Error::Io {
+        path: path.to_path_buf(),
+        source,
+    })
+}
+
+fn default_header(request: &mut RequestSpec, name: &str, value: &str) {
+    if !request
+        .headers
+        .iter()
+        .any(|header| header.key.eq_ignore_ascii_case(name))
+    {
+        request.headers.push(KeyValue::new(name, value));
+    }
+}
+
+fn text(value: &Value) -> &str {
+    value.as_str().unwrap_or_default()
+}
+
+/// Strings as they are, other scalars in their JSON form, null as empty.
+fn value_text(value: &Value) -> String {
+    match value {
+        Value::Null => String::new(),
+        Value::String(text) => text.clone(),
+        value => value.to_string(),
+    }
+}
+
+/// Turns a name from another tool into a valid collection entry name.
+fn entry_name(name: &str) -> String {
+    let name = name.replace(['/', '\\'], "-");
+    let name = name.trim().trim_start_matches('.').trim();
+    if name.is_empty() {
+        "request".to_string()
+    } else {
+        name.to_string()
+    }
+}
+
+/// `name`, or `name 2`, `name 3`... when it is already in `taken`.
+fn unique_name(name: String, taken: &mut Vec<String>) -> String {
+    let mut unique = name.clone();
+    let mut counter = 2;
+    while taken.contains(&unique) {
+        unique = format!("{name} {counter}");
+        counter += 1;
+    }
+    taken.push(unique.clone());
+    unique
+}
@@ -0,0 +1,845 @@
+use super::{
+    Error, Import, ImportedRequest, default_header, entry_name, read_file, text, unique_name,
+    value_text,
+};
+use crate::core::auth::{ApiKeyLocation, Auth, OAuth2, OAuth2Grant};
+use crate::core::environments::Environment;
+use crate::core::openapi;
+use crate::core::requests::{BodySpec, KeyValue, Method, MultipartPart, RequestSpec};
+use serde_json::{Map, Value, json};
+use std::collections::HashMap;
+use std::path::Path;
+
+const BASE_URL: &str = "baseUrl";
+const METHODS: [&str; 8] = [
+    "get", "put", "post", "delete", "options", "head", "patch", "trace",
+];
+/// Deepest nesting of references followed when building an example from a schema.
+const MAX_DEPTH: usize = 8;
+
+static NULL: Value = Value::Null;
+
+/// Generates one request per operation of an OpenAPI 3 or Swagger 2 document (JSON or YAML),
+/// grouped in a folder per tag. The server URL, path parameters and credentials become
+/// variables of an environment named after the API.
+pub fn parse(content: &str) -> Result<Import, Error> {
+    let document = openapi::document(content)?;
+    if !detect(&document) {
+        return Err(Error::UnsupportedFormat(
+            "not an OpenAPI 3 or Swagger 2 document".to_string(),
+        ));
+    }
+    Ok(Spec::new(&document).import())
+}
+
+pub fn parse_file(path: &Path) -> Result<Import, Error> {
+    parse(&read_file(path)?)
+}
+
+/// Whether `document` looks like something [`parse`] understands.
+pub fn detect(document: &Value) -> bool {
+    openapi::detect(document)
+}
+
+struct Spec<'a> {
+    document: &'a Value,
+    /// Swagger 2 rather than OpenAPI 3.
+    swagger: bool,
+    /// Variables of the generated environment, in order of first use.
+    variables: Vec<KeyValue>,
+}
+
+impl<'a> Spec<'a> {
+    fn new(document: &'a Value) -> Self {
+        Self {
+            document,
+            swagger: document["swagger"].is_string(),
+            variables: vec![],
+        }
+    }
+
+    fn import(mut self) -> Import {
+        let title = match text(&self.document["info"]["title"]) {
+            "" => "OpenAPI",
+            title => title,
+        };
+        let mut import = Import {
+            name: Some(entry_name(title)),
+            ..Import::default()
+        };
+        if has_external_ref(self.document) {
+            import.warn("references to other files are not followed");
+        }
+
+        let base_url = self.base_url(&mut import);
+        self.variable(BASE_URL, &base_url);
+
+        let mut taken: HashMap<String, Vec<String>> = HashMap::new();
+        for (path, item) in self.document["paths"].as_object().into_iter().flatten() {
+            let item = self.resolve(item);
+            for method in METHODS {
+                let Some(operation) = item.get(method) else {
+                    continue;
+                };
+                let folder = operation["tags"]
+                    .get(0)
+                    .map(|tag| entry_name(text(tag)))
+                    .unwrap_or_default();
+                let name = match (text(&operation["summary"]), text(&operation["operationId"])) {
+                    ("", "") => format!("{} {path}", method.to_uppercase()),
+                    ("", id) => id.to_string(),
+                    (summary, _) => summary.to_string(),
+                };
+                let name = unique_name(entry_name(&name), taken.entry(folder.clone()).or_default());
+                let path_name = match folder.as_str() {
+                    "" => name,
+                    folder => format!("{folder}/{name}"),
+                };
+
+                let request =
+                    self.operation(&mut import, method, path, item, operation, &path_name);
+                import.requests.push(ImportedRequest {
+                    path: path_name,
+                    request,
+                    response: None,
+                });
+            }
+        }
+
+        import.environments.push(Environment {
+            name: title.to_string(),
+            variables: self.variables,
+        });
+        import
+    }
+
+    fn base_url(&self, import: &mut Import) -> String {
+        let url = if self.swagger {
+            let host = text(&self.document["host"]);
+            if host.is_empty() {
+                import.warn(format!("no host given, set the '{BASE_URL}' variable"));
+            }
+            let schemes = self.document["schemes"].as_array();
+            let scheme = match schemes {
+                Some(schemes) if schemes.iter().any(|scheme| scheme == "https") => "https",
+                Some(schemes) if !schemes.is_empty() => text(&schemes[0]),
+                _ => "https",
+            };
+            format!("{scheme}://{host}{}", text(&self.document["basePath"]))
+        } else {
+            let server = &self.document["servers"][0];
+            let mut url = text(&server["url"]).to_string();
+            if url.is_empty() {
+                import.warn(format!(
+                    "no server URL given, set the '{BASE_URL}' variable"
+                ));
+            }
+            for (name, variable) in server["variables"].as_object().into_iter().flatten() {
+                url = url.replace(&format!("{{{name}}}"), &value_text(&variable["default"]));
+            }
+            url
+        };
+        url.trim_end_matches('/').to_string()
+    }
+
+    fn operation(
+        &mut self,
+        import: &mut Import,
+        method: &str,
+        path: &str,
+        item: &Value,
+        operation: &Value,
+        name: &str,
+    ) -> RequestSpec {
+        let method: Method = method.to_uppercase().parse().unwrap_or(Method::GET);
+        let url = format!(
+            "{{{{{BASE_URL}}}}}{}",
+            path.replace('{', "{{").replace('}', "}}")
+        );
+        let mut request = RequestSpec::new(method, url);
+
+        let mut form = vec![];
+        for parameter in self.parameters(item, operation) {
+            let parameter_name = text(&parameter["name"]).to_string();
+            let required = parameter["required"].as_bool().unwrap_or(false);
+            match text(&parameter["in"]) {
+                "path" => {
+                    let value = self.parameter_value(parameter);
+                    self.variable(&parameter_name, &value);
+                }
+                "query" => request.query.push(KeyValue {
+                    key: parameter_name,
+                    value: self.parameter_value(parameter),
+                    enabled: required,
+                }),
+                "header" => {
+                    // OpenAPI ignores these as parameters, they come from the body and auth.
+                    if ["accept", "content-type", "authorization"]
+                        .contains(&parameter_name.to_lowercase().as_str())
+                    {
+                        continue;
+                    }
+                    request.headers.push(KeyValue {
+                        key: parameter_name,
+                        value: self.parameter_value(parameter),
+                        enabled: required,
+                    });
+                }
+                "cookie" if required => {
+                    import.warn(format!(
+                        "{name}: cookie parameter '{parameter_name}' was not added"
+                    ));
+                }
+                "body" => {
+                    let example = self.example(&parameter["schema"], &[]);
+                    self.json_body(&mut request, "application/json", &example);
+                }
+                "formData" => form.push(parameter),
+                _ => {}
+            }
+        }
+
+        if !form.is_empty() {
+            self.swagger_form(import, &mut request, operation, &form, name);
+        }
+        let body = self.resolve(&operation["requestBody"]);
+        if let Some(content) = body["content"].as_object() {
+            self.body(import, &mut request, content, name);
+        }
+
+        let security = match operation.get("security") {
+            Some(security) => security,
+            None => &self.document["security"],
+        };
+        request.auth = self.auth(import, security, name);
+        request
+    }
+
+    /// Path item parameters, overridden by the operation's own with the same name and location.
+    fn parameters(&self, item: &'a Value, operation: &'a Value) -> Vec<&'a Value> {
+        let mut parameters: Vec<&Value> = vec![];
+        for parameter in [&item["parameters"], &operation["parameters"]]
+            .into_iter()
+            .filter_map(Value::as_array)
+            .flatten()
+        {
+            let parameter = self.resolve(parameter);
+            parameters.retain(|known| {
+                known["name"] != parameter["name"] || known["in"] != parameter["in"]
+            });
+            parameters.push(parameter);
+        }
+        parameters
+    }
+
+    fn parameter_value(&self, parameter: &Value) -> String {
+        let example = if let Some(example) = parameter.get("example") {
+            example.clone()
+        } else if let Some(example) = parameter["examples"]
+            .as_object()
+            .and_then(|examples| examples.values().next())
+        {
+            self.resolve(example)["value"].clone()
+        } else if let Some(example) = parameter.get("x-example") {
+            example.clone()
+        } else if parameter.get("schema").is_some() {
+            self.example(&parameter["schema"], &[])
+        } else {
+            // Swagger 2 parameters carry their schema inline.
+            self.example(parameter, &[])
+        };
+
+        match example {
+            Value::Array(items) => items.iter().map(value_text).collect::<Vec<_>>().join(","),
+            example => value_text(&example),
+        }
+    }
+
+    fn body(
+        &self,
+        import: &mut Import,
+        request: &mut RequestSpec,
+        content: &Map<String, Value>,
+        name: &str,
+    ) {
+        let media_type = [
+            "application/json",
+            "json",
+            "application/x-www-form-urlencoded",
+            "multipart/form-data",
+        ]
+        .iter()
+        .find_map(|wanted| {
+            content
+                .keys()
+                .find(|media_type| media_type.contains(wanted))
+        })
+        .or_else(|| content.keys().next());
+        let Some(media_type) = media_type else {
+            return;
+        };
+
+        let media = &content[media_type];
+        let example = if let Some(example) = media.get("example") {
+            example.clone()
+        } else if let Some(example) = media["examples"]
+            .as_object()
+            .and_then(|examples| examples.values().next())
+        {
+            self.resolve(example)["value"].clone()
+        } else {
+            self.example(&media["schema"], &[])
+        };
+
+        if media_type.contains("json") {
+            self.json_body(request, media_type, &example);
+        } else if media_type == "application/x-www-form-urlencoded" {
+            let fields = example
+                .as_object()
+                .into_iter()
+                .flatten()
+                .map(|(key, value)| KeyValue::new(key, value_text(value)))
+                .collect();
+            request.body = BodySpec::Form { fields };
+        } else if media_type == "multipart/form-data" {
+            let schema = self.resolve(&media["schema"]);
+            let mut parts = vec![];
+            for (key, value) in example.as_object().into_iter().flatten() {
+                let property = self.resolve(&schema["properties"][key]);
+                if text(&property["format"]) == "binary" {
+                    import.warn(format!("{name}: choose a file for part '{key}'"));
+                    parts.push(MultipartPart::file(key, ""));
+                } else {
+                    parts.push(MultipartPart::text(key, value_text(value)));
+                }
+            }
+            request.body = BodySpec::Multipart { parts };
+        } else if let Value::String(example) = example {
+            default_header(request, "Content-Type", media_type);
+            request.body = BodySpec::Text { text: example };
+        } else {
+            default_header(request, "Content-Type", media_type);
+            import.warn(format!("{name}: no example for the {media_type} body"));
+        }
+    }
+
+    /// Swagger 2 `formData` parameters, sent as multipart when the operation consumes it or
+    /// uploads a file.
+    fn swagger_form(
+        &self,
+        import: &mut Import,
+        request: &mut RequestSpec,
+        operation: &Value,
+        parameters: &[&Value],
+        name: &str,
+    ) {
+        let consumes = match operation.get("consumes") {
+            Some(consumes) => consumes,
+            None => &self.document["consumes"],
+        };
+        let multipart = parameters
+            .iter()
+            .any(|parameter| parameter["type"] == "file")
+            || consumes
+                .as_array()
+                .is_some_and(|types| types.iter().any(|kind| kind == "multipart/form-data"));
+
+        if multipart {
+            let mut parts = vec![];
+            for parameter in parameters {
+                let key = text(&parameter["name"]);
+                if parameter["type"] == "file" {
+                    import.warn(format!("{name}: choose a file for part '{key}'"));
+                    parts.push(MultipartPart::file(key, ""));
+                } else {
+                    parts.push(MultipartPart::text(key, self.parameter_value(parameter)));
+                }
+            }
+            request.body = BodySpec::Multipart { parts };
+        } else {
+            let fields = parameters
+                .iter()
+                .map(|parameter| {
+                    KeyValue::new(text(&parameter["name"]), self.parameter_value(parameter))
+                })
+                .collect();
+            request.body = BodySpec::Form { fields };
+        }
+    }
+
+    fn json_body(&self, request: &mut RequestSpec, media_type: &str, example: &Value) {
+        default_header(request, "Content-Type", media_type);
+        if !example.is_null() {
+            request.body = BodySpec::Text {
+                text: serde_json::to_string_pretty(example).unwrap_or_default(),
+            };
+        }
+    }
+
+    /// The first scheme of the first security requirement. Credentials are left as
+    /// variables of the environment.
+    fn auth(&mut self, import: &mut Import, security: &Value, name: &str) -> Auth {
+        let Some((scheme_name, scopes)) = security
+            .get(0)
+            .and_then(Value::as_object)
+            .and_then(|requirement| requirement.iter().next())
+        else {
+            return Auth::None;
+        };
+        let schemes = match self.swagger {
+            true => &self.document["securityDefinitions"],
+            false => &self.document["components"]["securitySchemes"],
+        };
+        let scheme = self.resolve(&schemes[scheme_name]);
+        let scope = scopes
+            .as_array()
+            .into_iter()
+            .flatten()
+            .map(value_text)
+            .collect::<Vec<_>>()
+            .join(" ");
+
+        let kind = match (text(&scheme["type"]), text(&scheme["scheme"])) {
+            ("http", scheme) => scheme.to_lowercase(),
+            (kind, _) => kind.to_string(),
+        };
+        match kind.as_str() {
+            "basic" | "digest" => {
+                let username = self.secret("username");
+                let password = self.secret("password");
+                match kind.as_str() {
+                    "basic" => Auth::Basic { username, password },
+                    _ => Auth::Digest { username, password },
+                }
+            }
+            "bearer" => Auth::Bearer {
+                token: self.secret("token"),
+            },
+            "apiKey" if text(&scheme["in"]) != "cookie" => Auth::ApiKey {
+                key: text(&scheme["name"]).to_string(),
+                value: self.secret("apiKey"),
+                location: match text(&scheme["in"]) {
+                    "query" => ApiKeyLocation::Query,
+                    _ => ApiKeyLocation::Header,
+                },
+            },
+            "oauth2" => {
+                let (flow, token_url) = if self.swagger {
+                    (text(&scheme["flow"]), &scheme["tokenUrl"])
+                } else if scheme["flows"]["clientCredentials"].is_object() {
+                    (
+                        "application",
+                        &scheme["flows"]["clientCredentials"]["tokenUrl"],
+                    )
+                } else if scheme["flows"]["password"].is_object() {
+                    ("password", &scheme["flows"]["password"]["tokenUrl"])
+                } else {
+                    ("", &NULL)
+                };
+                let grant = match flow {
+                    "application" => OAuth2Grant::ClientCredentials,
+                    "password" => OAuth2Grant::Password {
+                        username: self.secret("username"),
+                        password: self.secret("password"),
+                    },
+                    _ => {
+                        import.warn(format!(
+                            "{name}: OAuth 2.0 scheme '{scheme_name}' has no client credentials or password flow"
+                        ));
+                        return Auth::None;
+                    }
+                };
+                Auth::OAuth2(OAuth2 {
+                    token_url: text(token_url).to_string(),
+                    client_id: self.secret("clientId"),
+                    client_secret: self.secret("clientSecret"),
+                    scope,
+                    credentials_in_body: false,
+                    grant,
+                })
+            }
+            _ => {
+                import.warn(format!(
+                    "{name}: security scheme '{scheme_name}' is not supported"
+                ));
+                Auth::None
+            }
+        }
+    }
+
+    /// A `{{name}}` placeholder for a credential, declared empty in the environment.
+    fn secret(&mut self, name: &str) -> String {
+        self.variable(name, "");
+        format!("{{{{{name}}}}}")
+    }
+
+    fn variable(&mut self, name: &str, value: &str) {
+        if !self.variables.iter().any(|variable| variable.key == name) {
+            self.variables.push(KeyValue::new(name, value));
+        }
+    }
+
+    /// An example value for `schema`: its own example or default when it has one, otherwise
+    /// built from its type. `refs` are the schemas being expanded, so that recursive schemas
+    /// stop instead of nesting forever.
+    fn example(&self, schema: &Value, refs: &[&str]) -> Value {
+        let mut refs = refs.to_vec();
+        if let Some(reference) = schema["$ref"].as_str() {
+            if refs.contains(&reference) || refs.len() > MAX_DEPTH {
+                return Value::Null;
+            }
+            refs.push(reference);
+        }
+        let schema = self.resolve(schema);
+        if !schema.is_object() {
+            return Value::Null;
+        }
+        for key in ["example", "default", "x-example"] {
+            if let Some(example) = schema.get(key) {
+                return example.clone();
+            }
+        }
+        if let Some(example) = schema["examples"].get(0).or(schema["enum"].get(0)) {
+            return example.clone();
+        }
+        if let Some(all_of) = schema["allOf"].as_array() {
+            let mut merged = Map::new();
+            for part in all_of {
+                if let Value::Object(fields) = self.example(part, &refs) {
+                    merged.extend(fields);
+                }
+            }
+            return Value::Object(merged);
+        }
+        if let Some(first) = schema["oneOf"].get(0).or(schema["anyOf"].get(0)) {
+            return self.example(first, &refs);
+        }
+
+        // OpenAPI 3.1 allows a list of types, such as `[string, "null"]`.
+        let kind = match &schema["type"] {
+            Value::Array(kinds) => kinds
+                .iter()
+                .map(text)
+                .find(|kind| *kind != "null")
+                .unwrap_or_default(),
+            kind => text(kind),
+        };
+        match kind {
+            "object" | "" if schema["properties"].is_object() => schema["properties"]
+                .as_object()
+                .into_iter()
+                .flatten()
+                .map(|(key, property)| (key.clone(), self.example(property, &refs)))
+                .filter(|(_, example)| !example.is_null())
+                .collect::<Map<_, _>>()
+                .into(),
+            "object" => json!({}),
+            "array" => match self.example(&schema["items"], &refs) {
+                Value::Null => json!([]),
+                item => json!([item]),
+            },
+            "integer" => json!(0),
+            "number" => json!(0.0),
+            "boolean" => json!(true),
+            "string" => json!(match text(&schema["format"]) {
+                "date-time" => "2025-01-01T00:00:00Z",
+                "date" => "2025-01-01",
+                "time" => "00:00:00",
+                "email" => "user@example.com",
+                "uuid" => "00000000-0000-0000-0000-000000000000",
+                "uri" | "url" => "https://example.com",
+                "ipv4" => "127.0.0.1",
+                "binary" | "byte" => "",
+                _ => "string",
+            }),
+            _ => Value::Null,
+        }
+    }
+
+    fn resolve(&self, value: &'a Value) -> &'a Value {
+        openapi::resolve(self.document, value)
+    }
+}
+
+fn has_external_ref(value: &Value) -> bool {
+    match value {
+        Value::Object(fields) => fields.iter().any(|(key, value)| {
+            (key == "$ref" && !text(value).starts_with('#')) || has_external_ref(value)
+        }),
+        Value::Array(items) => items.iter().any(has_external_ref),
+        _ => false,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::import::fixture;
+    use crate::core::requests::PartValue;
+
+    fn import(name: &str) -> Import {
+        parse_file(&fixture(name)).unwrap()
+    }
+
+    fn request<'a>(import: &'a Import, path: &str) -> &'a RequestSpec {
+        &import
+            .requests
+            .iter()
+            .find(|imported| imported.path == path)
+            .unwrap_or_else(|| panic!("no request at {path}"))
+            .request
+    }
+
+    fn variable<'a>(import: &'a Import, key: &str) -> &'a str {
+        import.environments[0]
+            .variables
+            .iter()
+            .find(|variable| variable.key == key)
+            .map(|variable| variable.value.as_str())
+            .unwrap_or_else(|| panic!("no variable {key}"))
+    }
+
+    #[test]
+    fn creates_a_request_per_operation() {
+        let import = import("openapi/petstore.yaml");
+        let requests: Vec<(&str, &Method)> = import
+            .requests
+            .iter()
+            .map(|r| (r.path.as_str(), &r.request.method))
+            .collect();
+
+        assert_eq!(import.name.as_deref(), Some("Petstore"));
+        assert_eq!(
+            requests,
+            [
+                ("pets/List pets", &Method::GET),
+                ("pets/Create a pet", &Method::POST),
+                ("pets/showPetById", &Method::GET),
+                ("pets/Delete a pet", &Method::DELETE),
+                ("photos/Upload a photo", &Method::PUT),
+                ("Log in", &Method::POST),
+                ("Token via OAuth", &Method::POST),
+            ]
+        );
+    }
+
+    #[test]
+    fn turns_server_and_path_parameters_into_variables() {
+        let import = import("openapi/petstore.yaml");
+
+        assert_eq!(import.environments[0].name, "Petstore");
+        assert_eq!(variable(&import, "baseUrl"), "http://127.0.0.1:7878/v1");
+        assert_eq!(
+            variable(&import, "petId"),
+            "00000000-0000-0000-0000-000000000000"
+        );
+        assert_eq!(
+            request(&import, "pets/showPetById").url,
+            "{{baseUrl}}/pets/{{petId}}"
+        );
+        assert_eq!(
+            request(&import, "photos/Upload a photo").url,
+            "{{baseUrl}}/pets/{{petId}}/photo"
+        );
+    }
+
+    #[test]
+    fn fills_parameters_and_bodies_from_examples() {
+        let import = import("openapi/petstore.yaml");
+
+        // Optional parameters are added disabled.
+        let list = request(&import, "pets/List pets");
+        assert_eq!(list.query[0], KeyValue::new("limit", "20"));
+        assert_eq!(list.query[1].key, "status");
+        assert_eq!(list.query[1].value, "available");
+        assert!(!list.query[1].enabled);
+        assert_eq!(
+            list.headers,
+            [KeyValue::new(
+                "X-Request-Id",
+                "00000000-0000-0000-0000-000000000000"
+            )]
+        );
+
+        let create = request(&import, "pets/Create a pet");
+        assert_eq!(
+            create.headers,
+            [KeyValue::new("Content-Type", "application/json")]
+        );
+        let BodySpec::Text { text } = &create.body else {
+            panic!("expected a text body, got {:?}", create.body);
+        };
+        let body: Value = serde_json::from_str(text).unwrap();
+        assert_eq!(
+            body,
+            json!({
+                "name": "Ferris",
+                "tag": "string",
+                "birthday": "2025-01-01",
+                "owner": {"email": "user@example.com", "pets": [{"id": 0}]}
+            })
+        );
+
+        assert_eq!(
+            request(&import, "Log in").body,
+            BodySpec::Form {
+                fields: vec![
+                    KeyValue::new("username", "ferris"),
+                    KeyValue::new("password", "string"),
+                ]
+            }
+        );
+
+        let upload = request(&import, "photos/Upload a photo");
+        let BodySpec::Multipart { parts } = &upload.body else {
+            panic!("expected a multipart body, got {:?}", upload.body);
+        };
+        assert_eq!(parts[0].name, "caption");
+        assert_eq!(parts[1].name, "file");
+        assert_eq!(parts[1].value, PartValue::File { path: "".into() });
+    }
+
+    #[test]
+    fn maps_security_schemes() {
+        let import = import("openapi/petstore.yaml");
+
+        assert_eq!(
+            request(&import, "pets/List pets").auth,
+            Auth::Bearer {
+                token: "{{token}}".to_string()
+            }
+        );
+        assert_eq!(
+            request(&import, "pets/Delete a pet").auth,
+            Auth::ApiKey {
+                key: "X-API-Key".to_string(),
+                value: "{{apiKey}}".to_string(),
+                location: ApiKeyLocation::Header,
+            }
+        );
+        // An empty requirement list turns authentication off.
+        assert_eq!(request(&import, "Log in").auth, Auth::None);
+        assert_eq!(
+            request(&import, "Token via OAuth").auth,
+            Auth::OAuth2(OAuth2 {
+                token_url: "http://127.0.0.1:7881/token".to_string(),
+                client_id: "{{clientId}}".to_string(),
+                client_secret: "{{clientSecret}}".to_string(),
+                scope: "pets:read pets:write".to_string(),
+                credentials_in_body: false,
+                grant: OAuth2Grant::ClientCredentials,
+            })
+        );
+        assert_eq!(variable(&import, "clientSecret"), "");
+    }
+
+    #[test]
+    fn warns_about_file_parts() {
+        assert_eq!(
+            import("openapi/petstore.yaml").warnings,
+            ["photos/Upload a photo: choose a file for part 'file'"]
+        );
+    }
+
+    #[test]
+    fn imports_swagger_documents() {
+        let import = import("openapi/swagger.json");
+        let paths: Vec<&str> = import.requests.iter().map(|r| r.path.as_str()).collect();
+
+        assert_eq!(import.name.as_deref(), Some("Legacy Users"));
+        assert_eq!(
+            paths,
+            ["users/Get user", "users/Update user", "users/Upload avatar"]
+        );
+        assert_eq!(variable(&import, "baseUrl"), "http://127.0.0.1:7878/api");
+        assert_eq!(variable(&import, "id"), "42");
+
+        let get = request(&import, "users/Get user");
+        assert_eq!(get.url, "{{baseUrl}}/users/{{id}}");
+        assert_eq!(get.query[0].key, "fields");
+        assert_eq!(get.query[0].value, "name,email");
+        assert!(!get.query[0].enabled);
+        assert_eq!(
+            get.auth,
+            Auth::Basic {
+                username: "{{username}}".to_string(),
+                password: "{{password}}".to_string(),
+            }
+        );
+
+        let update = request(&import, "users/Update user");
+        let BodySpec::Text { text } = &update.body else {
+            panic!("expected a text body, got {:?}", update.body);
+        };
+        assert_eq!(
+            serde_json::from_str::<Value>(text).unwrap(),
+            json!({"name": "string", "email": "user@example.com", "admin": false})
+        );
+        let Auth::OAuth2(oauth) = &update.auth else {
+            panic!("expected OAuth 2.0, got {:?}", update.auth);
+        };
+        assert_eq!(oauth.scope, "users");
+        assert_eq!(
+            oauth.grant,
+            OAuth2Grant::Password {
+                username: "{{username}}".to_string(),
+                password: "{{password}}".to_string(),
+            }
+        );
+
+        let upload = request(&import, "users/Upload avatar");
+        assert_eq!(
+            upload.body,
+            BodySpec::Multipart {
+                parts: vec![
+                    MultipartPart::file("avatar", ""),
+                    MultipartPart::text("caption", "me"),
+                ]
+            }
+        );
+        assert_eq!(
+            import.warnings,
+            ["users/Upload avatar: choose a file for part 'avatar'"]
+        );
+    }
+
+    #[test]
+    fn warns_about_what_cannot_be_imported() {
+        let import = parse(
+            r##"
+openapi: 3.0.0
+info: {title: Partial}
+paths:
+  /items:
+    post:
+      summary: Add item
+      parameters:
+        - {name: session, in: cookie, required: true}
+      security: [{oidc: []}]
+      requestBody:
+        content:
+          application/octet-stream: {}
+  /remote:
+    get:
+      summary: Remote
+      responses:
+        200: {$ref: "other.yaml#/components/responses/Ok"}
+components:
+  securitySchemes:
+    oidc: {type: openIdConnect, openIdConnectUrl: "https://example.com"}
+"##,
+        )
+        .unwrap();
+
+        assert_eq!(
+            import.warnings,
+            [
+                "references to other files are not followed",
+                "no server URL given, set the 'baseUrl' variable",
+                "Add item: cookie parameter 'session' was not added",
+                "Add item: no example for the application/octet-stream body",
+                "Add item: security scheme 'oidc' is not supported",
+            ]
+        );
+    }
+}
@@ -0,0 +1,637 @@
+use super::{
+    Error, Import, ImportedRequest, default_header, entry_name, read_file, text, unique_name,
+    value_text,
+};
+use crate::core::auth::{ApiKeyLocation, Auth, OAuth2, OAuth2Grant};
+use crate::core::environments::Environment;
+use crate::core::requests::{BodySpec, KeyValue, Method, MultipartPart, RequestSpec};
+use serde_json::Value;
+use std::path::Path;
+
+/// Reads a Postman collection (v2.1, or the close v2.0) or a Postman environment export.
+pub fn parse(json: &str) -> Result<Import, Error> {
+    let document: Value = serde_json::from_str(json)?;
+    if is_collection(&document) {
+        collection(&document)
+    } else if is_environment(&document) {
+        Ok(environment(&document))
+    } else {
+        Err(Error::UnsupportedFormat(
+            "not a Postman collection or environment".to_string(),
+        ))
+    }
+}
+
+pub fn parse_file(path: &Path) -> Result<Import, Error> {
+    parse(&read_file(path)?)
+}
+
+/// Whether `document` looks like something [`parse`] understands.
+pub fn detect(document: &Value) -> bool {
+    is_collection(document) || is_environment(document)
+}
+
+fn is_collection(document: &Value) -> bool {
+    document["info"]["schema"]
+        .as_str()
+        .is_some_and(|schema| schema.contains("schema.getpostman.com"))
+        || (document["info"].is_object() && document["item"].is_array())
+}
+
+fn is_environment(document: &Value) -> bool {
+    document["values"].is_array()
+        && (document.get("_postman_variable_scope").is_some() || document["name"].is_string())
+}
+
+fn collection(document: &Value) -> Result<Import, Error> {
+    let schema = text(&document["info"]["schema"]);
+    if schema.contains("/v1.") {
+        return Err(Error::UnsupportedFormat(
+            "Postman collection v1, export it again as v2.1".to_string(),
+        ));
+    }
+
+    let name = match text(&document["info"]["name"]) {
+        "" => "Postman collection",
+        name => name,
+    };
+    let mut import = Import {
+        name: Some(entry_name(name)),
+        ..Import::default()
+    };
+
+    let variables = key_values(&document["variable"]);
+    if !variables.is_empty() {
+        import.environments.push(Environment {
+            name: name.to_string(),
+            variables,
+        });
+    }
+
+    events(&mut import, document, name);
+    items(&mut import, &document["item"], "", auth_block(document));
+    Ok(import)
+}
+
+fn environment(document: &Value) -> Import {
+    let name = match text(&document["name"]) {
+        "" => "Postman environment",
+        name => name,
+    };
+    let variables = document["values"]
+        .as_array()
+        .into_iter()
+        .flatten()
+        .filter(|variable| !text(&variable["key"]).is_empty())
+        .map(|variable| KeyValue {
+            key: text(&variable["key"]).to_string(),
+            value: value_text(&variable["value"]),
+            enabled: variable["enabled"].as_bool().unwrap_or(true),
+        })
+        .collect();
+
+    Import {
+        environments: vec![Environment {
+            name: name.to_string(),
+            variables,
+        }],
+        ..Import::default()
+    }
+}
+
+fn items(import: &mut Import, children: &Value, folder: &str, inherited_auth: Option<&Value>) {
+    let mut taken = vec![];
+    for item in children.as_array().into_iter().flatten() {
+        let name = unique_name(entry_name(text(&item["name"])), &mut taken);
+        let path = match folder {
+            "" => name,
+            folder => format!("{folder}/{name}"),
+        };
+        events(import, item, &path);
+
+        if item["item"].is_array() {
+            let auth = auth_block(item).or(inherited_auth);
+            items(import, &item["item"], &path, auth);
+        } else if !item["request"].is_null() {
+            let auth = auth_block(&item["request"]).or(inherited_auth);
+            let request = request(import, item, auth, &path);
+            import.requests.push(ImportedRequest {
+                path,
+                request,
+                response: None,
+            });
+        } else {
+            import.warn(format!("{path}: item has no request and was skipped"));
+        }
+    }
+}
+
+/// The folder's or request's own auth block, unless it inherits from its parent.
+fn auth_block(item: &Value) -> Option<&Value> {
+    let auth = item.get("auth").filter(|auth| auth.is_object())?;
+    (text(&auth["type"]) != "inherit").then_some(auth)
+}
+
+fn request(import: &mut Import, item: &Value, auth: Option<&Value>, path: &str) -> RequestSpec {
+    let source = &item["request"];
+    // The schema allows a bare URL string for simple GET requests.
+    if let Some(url) = source.as_str() {
+        return RequestSpec::new(Method::GET, url);
+    }
+
+    let method = match text(&source["method"]) {
+        "" => Method::GET,
+        method => method.parse().unwrap_or_else(|_| {
+            import.warn(format!("{path}: invalid method '{method}', using GET"));
+            Method::GET
+        }),
+    };
+    let (url, query) = url(&source["url"]);
+    let mut request = RequestSpec::new(method, url);
+    request.query = query;
+    request.headers = match &source["header"] {
+        Value::String(headers) => headers
+            .lines()
+            .filter_map(|line| line.split_once(':'))
+            .map(|(key, value)| KeyValue::new(key.trim(), value.trim()))
+            .collect(),
+        headers => key_values(headers),
+    };
+
+    body(import, &source["body"], &mut request, path);
+    if let Some(auth) = auth {
+        request.auth = self::auth(import, auth, path);
+    }
+
+    let behavior = &item["protocolProfileBehavior"];
+    if let Some(follow_redirects) = behavior["followRedirects"].as_bool() {
+        request.options.follow_redirects = follow_redirects;
+    }
+    if behavior["strictSSL"].as_bool() == Some(false) {
+        request.options.insecure = true;
+    }
+
+    if serde_json::to_string(&request).is_ok_and(|json| json.contains("{{$")) {
+        import.warn(format!(
+            "{path}: Postman dynamic variables ({{{{$...}}}}) are not supported"
+        ));
+    }
+    request
+}
+
+/// URL without its query string, and the query rows. Path variables (`:id`) are replaced by
+/// their value, or by a `{{id}}` placeholder when they have none.
+fn url(url: &Value) -> (String, Vec<KeyValue>) {
+    if let Some(raw) = url.as_str() {
+        return (raw.to_string(), vec![]);
+    }
+
+    let raw = text(&url["raw"]);
+    let mut base = match raw.is_empty() {
+        false => raw.split(['?', '#']).next().unwrap_or_default().to_string(),
+        true => {
+            let host = match &url["host"] {
+                Value::Array(parts) => parts.iter().map(value_text).collect::<Vec<_>>().join("."),
+                host => value_text(host),
+            };
+            let path = match &url["path"] {
+                Value::Array(parts) => parts.iter().map(value_text).collect::<Vec<_>>().join("/"),
+                path => value_text(path),
+            };
+            let mut base = match text(&url["protocol"]) {
+                "" => host,
+                protocol => format!("{protocol}://{host}"),
+            };
+            if !text(&url["port"]).is_empty() {
+                base.push_str(&format!(":{}", text(&url["port"])));
+            }
+            if !path.is_empty() {
+                base.push_str(&format!("/{}", path.trim_start_matches('/')));
+            }
+            base
+        }
+    };
+
+    for variable in url["variable"].as_array().into_iter().flatten() {
+        let key = text(&variable["key"]);
+        let value = match value_text(&variable["value"]) {
+            value if value.is_empty() => format!("{{{{{key}}}}}"),
+            value => value,
+        };
+        let segment = format!(":{key}");
+        base = base
+            .split('/')
+            .map(|part| {
+                if part == segment {
+                    value.as_str()
+                } else {
+                    part
+                }
+            })
+            .collect::<Vec<_>>()
+            .join("/");
+    }
+
+    let query = url["query"]
+        .as_array()
+        .into_iter()
+        .flatten()
+        .filter(|pair| !pair["key"].is_null())
+        .map(|pair| KeyValue {
+            key: text(&pair["key"]).to_string(),
+            value: value_text(&pair["value"]),
+            enabled: !pair["disabled"].as_bool().unwrap_or(false),
+        })
+        .collect();
+    (base, query)
+}
+
+fn body(import: &mut Import, body: &Value, request: &mut RequestSpec, path: &str) {
+    if body.is_null() || body["disabled"].as_bool() == Some(true) {
+        return;
+    }
+
+    match text(&body["mode"]) {
+        "raw" => {
+            let raw = text(&body["raw"]);
+            if raw.is_empty() {
+                return;
+            }
+            if text(&body["options"]["raw"]["language"]) == "json" {
+                default_header(request, "Content-Type", "application/json");
+            }
+            request.body = BodySpec::Text {
+                text: raw.to_string(),
+            };
+        }
+        "urlencoded" => {
+            request.body = BodySpec::Form {
+                fields: key_values(&body["urlencoded"]),
+            };
+        }
+        "formdata" => {
+            let mut parts = vec![];
+            for field in body["formdata"].as_array().into_iter().flatten() {
+                let name = text(&field["key"]);
+                if field["disabled"].as_bool() == Some(true) {
+                    import.warn(format!("{path}: disabled form field '{name}' skipped"));
+                    continue;
+                }
+                let mut part = if text(&field["type"]) == "file" {
+                    let src = match &field["src"] {
+                        Value::Array(files) => files.first().map(value_text).unwrap_or_default(),
+                        src => value_text(src),
+                    };
+                    if src.is_empty() {
+                        import.warn(format!("{path}: file field '{name}' has no file"));
+                        continue;
+                    }
+                    MultipartPart::file(name, src)
+                } else {
+                    MultipartPart::text(name, value_text(&field["value"]))
+                };
+                if !text(&field["contentType"]).is_empty() {
+                    part.content_type = Some(text(&field["contentType"]).to_string());
+                }
+                parts.push(part);
+            }
+            request.body = BodySpec::Multipart { parts };
+        }
+        "file" => {
+            let src = text(&body["file"]["src"]);
+            if !src.is_empty() {
+                request.body = BodySpec::File { path: src.into() };
+            } else if !text(&body["file"]["content"]).is_empty() {
+                request.body = BodySpec::Text {
+                    text: text(&body["file"]["content"]).to_string(),
+                };
+            }
+        }
+        "graphql" => {
+            let graphql = &body["graphql"];
+            let variables = match &graphql["variables"] {
+                Value::String(variables) if !variables.trim().is_empty() => {
+                    serde_json::from_str(variables).unwrap_or_else(|_| {
+                        import.warn(format!("{path}: GraphQL variables are not valid JSON"));
+                        Value::Null
+                    })
+                }
+                Value::String(_) => Value::Null,
+                variables => variables.clone(),
+            };
+            let payload = serde_json::json!({
+                "query": text(&graphql["query"]),
+                "variables": variables,
+            });
+            default_header(request, "Content-Type", "application/json");
+            request.body = BodySpec::Text {
+                text: payload.to_string(),
+            };
+        }
+        mode => import.warn(format!("{path}: body mode '{mode}' is not supported")),
+    }
+}
+
+fn auth(import: &mut Import, auth: &Value, path: &str) -> Auth {
+    let kind = text(&auth["type"]);
+    let param = |key: &str| auth_param(&auth[kind], key);
+
+    match kind {
+        "noauth" | "" => Auth::None,
+        "basic" => Auth::Basic {
+            username: param("username"),
+            password: param("password"),
+        },
+        "digest" => Auth::Digest {
+            username: param("username"),
+            password: param("password"),
+        },
+        "bearer" => Auth::Bearer {
+            token: param("token"),
+        },
+        "apikey" => Auth::ApiKey {
+            key: param("key"),
+            value: param("value"),
+            location: match param("in").as_str() {
+                "query" => ApiKeyLocation::Query,
+                _ => ApiKeyLocation::Header,
+            },
+        },
+        "oauth2" => {
+            let grant = match param("grant_type").as_str() {
+                "" | "client_credentials" => Some(OAuth2Grant::ClientCredentials),
+                "password_credentials" => Some(OAuth2Grant::Password {
+                    username: param("username"),
+                    password: param("password"),
+                }),
+                _ => None,
+            };
+            match grant {
+                Some(grant) => Auth::OAuth2(OAuth2 {
+                    token_url: param("accessTokenUrl"),
+                    client_id: param("clientId"),
+                    client_secret: param("clientSecret"),
+                    scope: param("scope"),
+                    credentials_in_body: param("client_authentication") == "body",
+                    grant,
+                }),
+                None => {
+                    import.warn(format!(
+                        "{path}: OAuth 2.0 grant '{}' is not supported, using its current access token",
+                        param("grant_type")
+                    ));
+                    Auth::Bearer {
+                        token: param("accessToken"),
+                    }
+                }
+            }
+        }
+        kind => {
+            import.warn(format!("{path}: auth type '{kind}' is not supported"));
+            Auth::None
+        }
+    }
+}
+
+/// Auth parameters are `[{"key": ..., "value": ...}]` in v2.1 and an object in v2.0.
+fn auth_param(params: &Value, key: &str) -> String {
+    match params {
+        Value::Array(params) => params
+            .iter()
+            .find(|param| text(&param["key"]) == key)
+            .map(|param| value_text(&param["value"]))
+            .unwrap_or_default(),
+        params => value_text(&params[key]),
+    }
+}
+
+/// Pre-request and test scripts cannot be imported.
+fn events(import: &mut Import, item: &Value, path: &str) {
+    for event in item["event"].as_array().into_iter().flatten() {
+        let script = match &event["script"]["exec"] {
+            Value::Array(lines) => lines.iter().map(value_text).collect::<Vec<_>>().join("\n"),
+            exec => value_text(exec),
+        };
+        if script.trim().is_empty() {
+            continue;
+        }
+        let kind = match text(&event["listen"]) {
+            "prerequest" => "pre-request script",
+            "test" => "test script",
+            _ => "script",
+        };
+        import.warn(format!("{path}: {kind} was not imported"));
+    }
+}
+
+fn key_values(rows: &Value) -> Vec<KeyValue> {
+    rows.as_array()
+        .into_iter()
+        .flatten()
+        .filter(|row| !text(&row["key"]).is_empty())
+        .map(|row| KeyValue {
+            key: text(&row["key"]).to_string(),
+            value: value_text(&row["value"]),
+            enabled: !row["disabled"].as_bool().unwrap_or(false),
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::import::fixture;
+    use crate::core::requests::PartValue;
+
+    fn collection() -> Import {
+        parse_file(&fixture("postman/collection.json")).unwrap()
+    }
+
+    fn request<'a>(import: &'a Import, path: &str) -> &'a RequestSpec {
+        &import
+            .requests
+            .iter()
+            .find(|imported| imported.path == path)
+            .unwrap_or_else(|| panic!("no request at {path}"))
+            .request
+    }
+
+    #[test]
+    fn keeps_folder_structure() {
+        let import = collection();
+        let paths: Vec<&str> = import.requests.iter().map(|r| r.path.as_str()).collect();
+
+        assert_eq!(import.name.as_deref(), Some("Echo API"));
+        assert_eq!(
+            paths,
+            [
+                "Users/Get user",
+                "Users/Create user",
+                "Users/Delete user",
+                "Login",
+                "Upload avatar",
+                "Search",
+                "Signed",
+            ]
+        );
+    }
+
+    #[test]
+    fn maps_url_query_and_path_variables() {
+        let import = collection();
+
+        let get = request(&import, "Users/Get user");
+        assert_eq!(get.method, Method::GET);
+        assert_eq!(get.url, "{{baseUrl}}/users/42");
+        assert_eq!(get.query[0], KeyValue::new("expand", "roles"));
+        assert_eq!(get.query[1].key, "debug");
+        assert!(!get.query[1].enabled);
+        assert_eq!(get.headers, [KeyValue::new("Accept", "application/json")]);
+
+        // A path variable without a value becomes a placeholder.
+        let delete = request(&import, "Users/Delete user");
+        assert_eq!(delete.url, "{{baseUrl}}/users/{{id}}");
+    }
+
+    #[test]
+    fn maps_auth_with_inheritance() {
+        let import = collection();
+        let basic = Auth::Basic {
+            username: "admin".to_string(),
+            password: "hunter2".to_string(),
+        };
+
+        // Folder auth overrides the collection's, `noauth` overrides the folder's.
+        assert_eq!(request(&import, "Users/Get user").auth, basic);
+        assert_eq!(request(&import, "Users/Create user").auth, basic);
+        assert_eq!(request(&import, "Users/Delete user").auth, Auth::None);
+        assert_eq!(
+            request(&import, "Login").auth,
+            Auth::Bearer {
+                token: "{{token}}".to_string()
+            }
+        );
+        assert_eq!(
+            request(&import, "Upload avatar").auth,
+            Auth::ApiKey {
+                key: "api_key".to_string(),
+                value: "{{apiKey}}".to_string(),
+                location: ApiKeyLocation::Query,
+            }
+        );
+        assert_eq!(
+            request(&import, "Search").auth,
+            Auth::OAuth2(OAuth2 {
+                token_url: "http://127.0.0.1:7881/token".to_string(),
+                client_id: "app".to_string(),
+                client_secret: "s3cret".to_string(),
+                scope: "read".to_string(),
+                credentials_in_body: false,
+                grant: OAuth2Grant::ClientCredentials,
+            })
+        );
+        assert_eq!(request(&import, "Signed").auth, Auth::None);
+    }
+
+    #[test]
+    fn maps_bodies_and_options() {
+        let import = collection();
+
+        let create = request(&import, "Users/Create user");
+        assert_eq!(
+            create.headers,
+            [KeyValue::new("Content-Type", "application/json")]
+        );
+        assert_eq!(
+            create.body,
+            BodySpec::Text {
+                text: "{\n  \"name\": \"Ferris\"\n}".to_string()
+            }
+        );
+
+        let login = request(&import, "Login");
+        let BodySpec::Form { fields } = &login.body else {
+            panic!("expected a form body, got {:?}", login.body);
+        };
+        assert_eq!(fields[1], KeyValue::new("password", "p@ss word"));
+        assert!(!fields[2].enabled);
+        assert!(!login.options.follow_redirects);
+
+        let upload = request(&import, "Upload avatar");
+        let BodySpec::Multipart { parts } = &upload.body else {
+            panic!("expected a multipart body, got {:?}", upload.body);
+        };
+        assert_eq!(parts.len(), 2);
+        assert_eq!(parts[0], MultipartPart::text("caption", "Me"));
+        assert_eq!(
+            parts[1].value,
+            PartValue::File {
+                path: "/tmp/avatar.png".into()
+            }
+        );
+        assert_eq!(parts[1].content_type.as_deref(), Some("image/png"));
+
+        assert_eq!(
+            request(&import, "Search").body,
+            BodySpec::Text {
+                text: r#"{"query":"query { users { id } }","variables":{"limit":10}}"#.to_string()
+            }
+        );
+        assert!(request(&import, "Signed").options.insecure);
+    }
+
+    #[test]
+    fn collection_variables_become_an_environment() {
+        let import = collection();
+
+        assert_eq!(import.environments.len(), 1);
+        assert_eq!(import.environments[0].name, "Echo API");
+        assert_eq!(
+            import.environments[0].variables,
+            [
+                KeyValue::new("baseUrl", "http://127.0.0.1:7878"),
+                KeyValue::new("token", "secret"),
+            ]
+        );
+    }
+
+    #[test]
+    fn warns_about_what_was_not_imported() {
+        assert_eq!(
+            collection().warnings,
+            [
+                "Echo API: pre-request script was not imported",
+                "Users/Get user: test script was not imported",
+                "Upload avatar: disabled form field 'old' skipped",
+                "Search: Postman dynamic variables ({{$...}}) are not supported",
+                "Signed: auth type 'awsv4' is not supported",
+            ]
+        );
+    }
+
+    #[test]
+    fn parses_environment() {
+        let import = parse_file(&fixture("postman/environment.json")).unwrap();
+
+        assert!(import.requests.is_empty());
+        assert!(import.warnings.is_empty());
+        assert_eq!(import.environments.len(), 1);
+        let environment = &import.environments[0];
+        assert_eq!(environment.name, "Local");
+        assert_eq!(
+            environment.variables[..2],
+            [
+                KeyValue::new("baseUrl", "http://127.0.0.1:7878"),
+                KeyValue::new("apiKey", "abc123"),
+            ]
+        );
+        assert_eq!(environment.variables[2].key, "legacy");
+        assert!(!environment.variables[2].enabled);
+    }
+
+    #[test]
+    fn detects_fixtures_by_content() {
+        let import = crate::core::import::from_file(&fixture("postman/collection.json")).unwrap();
+        assert_eq!(import.requests.len(), 7);
+    }
+}
@@ -0,0 +1,28 @@
+use std::fmt;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Error {
+    /// `position` counts characters from the start of `expression`.
+    Syntax {
+        expression: String,
+        position: usize,
+        expected: &'static str,
+    },
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Error::Syntax {
+                expression,
+                position,
+                expected,
+            } => write!(
+                f,
+                "invalid JSONPath '{expression}': expected {expected} at position {position}"
+            ),
+        }
+    }
+}
+
+impl std::error::Error for Error {}
@@ -0,0 +1,894 @@
+//! JSONPath queries (RFC 9535) over `serde_json` values: member names, indexes, wildcards,
+//! slices, recursive descent and `?()` filters. The jq-like shorthands `.items[].id` and
+//! `.[0]` are accepted as well.
+
+mod error;
+
+use serde_json::Value;
+use std::cmp::Ordering;
+use std::collections::HashMap;
+use std::fmt;
+use std::str::FromStr;
+
+pub use error::Error;
+
+/// A parsed query, e.g. `$.items[?(@.price < 10)].name`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct JsonPath {
+    expression: String,
+    segments: Vec<Segment>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+enum Segment {
+    Child(Vec<Selector>),
+    /// `..`: applies the selectors to the node and to everything nested in it.
+    Descendant(Vec<Selector>),
+}
+
+#[derive(Debug, Clone, PartialEq)]
+enum Selector {
+    Name(String),
+    Index(i64),
+    Wildcard,
+    Slice {
+        start: Option<i64>,
+        end: Option<i64>,
+        step: Option<i64>,
+    },
+    Filter(Filter),
+}
+
+#[derive(Debug, Clone, PartialEq)]
+enum Filter {
+    Or(Box<Filter>, Box<Filter>),
+    And(Box<Filter>, Box<Filter>),
+    Not(Box<Filter>),
+    Exists(Query),
+    Compare(Operand, Comparison, Operand),
+}
+
+#[derive(Debug, Clone, PartialEq)]
+enum Operand {
+    Query(Query),
+    Literal(Value),
+}
+
+/// A path inside a filter, starting at the current node (`@`) or at the root (`$`).
+#[derive(Debug, Clone, PartialEq)]
+struct Query {
+    relative: bool,
+    segments: Vec<Segment>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Comparison {
+    Equal,
+    NotEqual,
+    Less,
+    LessOrEqual,
+    Greater,
+    GreaterOrEqual,
+}
+
+impl JsonPath {
+    pub fn parse(expression: &str) -> Result<Self, Error> {
+        let (path, rest) = Self::parse_prefix(expression)?;
+        if !rest.is_empty() {
+            let position = expression.chars().count() - rest.chars().count();
+            return Err(Error::Syntax {
+                expression: expression.to_string(),
+                position,
+                expected: "the end of the path",
+            });
+        }
+        Ok(path)
+    }
+
+    /// Parses the path at the start of `text`, up to the first character that cannot
+    /// continue it (typically a space), and returns it with the remaining text.
+    pub fn parse_prefix(text: &str) -> Result<(Self, &str), Error> {
+        let mut parser = Parser::new(text);
+        let segments = parser.path()?;
+        let end = text
+            .char_indices()
+            .nth(parser.position)
+            .map_or(text.len(), |(index, _)| index);
+        let path = Self {
+            expression: text[..end].to_string(),
+            segments,
+        };
+        Ok((path, &text[end..]))
+    }
+
+    /// Every value the path selects, in document order.
+    pub fn select<'a>(&self, root: &'a Value) -> Vec<&'a Value> {
+        select(&self.segments, root, root)
+    }
+
+    pub fn first<'a>(&self, root: &'a Value) -> Option<&'a Value> {
+        self.select(root).into_iter().next()
+    }
+
+    /// Every value the path selects with its location in `root`, e.g. `$.items[0].id`, in
+    /// document order.
+    pub fn locate<'a>(&self, root: &'a Value) -> Vec<(String, &'a Value)> {
+        let mut locations = HashMap::new();
+        collect_locations(root, "$".to_string(), &mut locations);
+        self.select(root)
+            .into_iter()
+            .map(|value| {
+                let location = locations
+                    .get(&(value as *const Value))
+                    .cloned()
+                    .unwrap_or_default();
+                (location, value)
+            })
+            .collect()
+    }
+}
+
+/// Location of the member `name` of the value at `parent`, in dot notation when the name
+/// allows it.
+pub fn member_location(parent: &str, name: &str) -> String {
+    if !name.is_empty() && name.chars().all(is_name_char) {
+        format!("{parent}.{name}")
+    } else {
+        let name = name.replace('\\', "\\\\").replace('\'', "\\'");
+        format!("{parent}['{name}']")
+    }
+}
+
+pub fn index_location(parent: &str, index: usize) -> String {
+    format!("{parent}[{index}]")
+}
+
+impl fmt::Display for JsonPath {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.expression)
+    }
+}
+
+impl FromStr for JsonPath {
+    type Err = Error;
+
+    fn from_str(expression: &str) -> Result<Self, Self::Err> {
+        Self::parse(expression)
+    }
+}
+
+fn select<'a>(segments: &[Segment], root: &'a Value, start: &'a Value) -> Vec<&'a Value> {
+    let mut nodes = vec![start];
+    for segment in segments {
+        let mut next = vec![];
+        for node in nodes {
+            match segment {
+                Segment::Child(selectors) => apply(selectors, node, root, &mut next),
+                Segment::Descendant(selectors) => {
+                    let mut descendants = vec![];
+                    collect_descendants(node, &mut descendants);
+                    for descendant in descendants {
+                        apply(selectors, descendant, root, &mut next);
+                    }
+                }
+            }
+        }
+        nodes = next;
+    }
+    nodes
+}
+
+fn apply<'a>(
+    selectors: &[Selector],
+    node: &'a Value,
+    root: &'a Value,
+    output: &mut Vec<&'a Value>,
+) {
+    for selector in selectors {
+        match selector {
+            Selector::Name(name) => output.extend(node.get(name)),
+            Selector::Index(index) => {
+                if let Some(items) = node.as_array() {
+                    output.extend(normalize(*index, items.len()).map(|index| &items[index]));
+                }
+            }
+            Selector::Wildcard => output.extend(children(node)),
+            Selector::Slice { start, end, step } => {
+                if let Some(items) = node.as_array() {
+                    output.extend(slice(items, *start, *end, step.unwrap_or(1)));
+                }
+            }
+            Selector::Filter(filter) => output.extend(
+                children(node)
+                    .into_iter()
+                    .filter(|child| filter.test(child, root)),
+            ),
+        }
+    }
+}
+
+fn children(node: &Value) -> Vec<&Value> {
+    match node {
+        Value::Array(items) => items.iter().collect(),
+        Value::Object(fields) => fields.values().collect(),
+        _ => vec![],
+    }
+}
+
+/// Values are told apart by address: `select` returns references into the same document.
+fn collect_locations(node: &Value, location: String, output: &mut HashMap<*const Value, String>) {
+    match node {
+        Value::Array(items) => {
+            for (index, item) in items.iter().enumerate() {
+                collect_locations(item, index_location(&location, index), output);
+            }
+        }
+        Value::Object(fields) => {
+            for (name, field) in fields {
+                collect_locations(field, member_location(&location, name), output);
+            }
+        }
+        _ => {}
+    }
+    output.insert(node as *const Value, location);
+}
+
+/// The node itself, then everything nested in it, depth first.
+fn collect_descendants<'a>(node: &'a Value, output: &mut Vec<&'a Value>) {
+    output.push(node);
+    for child in children(node) {
+        collect_descendants(child, output);
+    }
+}
+
+/// Negative indexes count from the end.
+fn normalize(index: i64, length: usize) -> Option<usize> {
+    let length = length as i64;
+    let index = if index < 0 { length + index } else { index };
+    (0..length).contains(&index).then_some(index as usize)
+}
+
+/// `[start:end:step]` with Python semantics, as specified by RFC 9535.
+fn slice(items: &[Value], start: Option<i64>, end: Option<i64>, step: i64) -> Vec<&Value> {
+    let length = items.len() as i64;
+    let normalize = |index: i64| if index < 0 { length + index } else { index };
+    let mut selected = vec![];
+    match step.cmp(&0) {
+        Ordering::Greater => {
+            let lower = normalize(start.unwrap_or(0)).clamp(0, length);
+            let upper = normalize(end.unwrap_or(length)).clamp(0, length);
+            let mut index = lower;
+            while index < upper {
+                selected.push(&items[index as usize]);
+                match index.checked_add(step) {
+                    Some(next) => index = next,
+                    None => break,
+                }
+            }
+        }
+        Ordering::Less => {
+            let upper = normalize(start.unwrap_or(length - 1)).clamp(-1, length - 1);
+            let lower = normalize(end.unwrap_or(-length - 1)).clamp(-1, length - 1);
+            let mut index = upper;
+            while lower < index {
+                selected.push(&items[index as usize]);
+                match index.checked_add(step) {
+                    Some(next) => index = next,
+                    None => break,
+                }
+            }
+        }
+        Ordering::Equal => {}
+    }
+    selected
+}
+
+impl Filter {
+    fn test(&self, current: &Value, root: &Value) -> bool {
+        match self {
+            Filter::Or(left, right) => left.test(current, root) || right.test(current, root),
+            Filter::And(left, right) => left.test(current, root) && right.test(current, root),
+            Filter::Not(filter) => !filter.test(current, root),
+            Filter::Exists(query) => !query.select(current, root).is_empty(),
+            Filter::Compare(left, comparison, right) => compare(
+                left.value(current, root),
+                *comparison,
+                right.value(current, root),
+            ),
+        }
+    }
+}
+
+impl Query {
+    fn select<'a>(&self, current: &'a Value, root: &'a Value) -> Vec<&'a Value> {
+        let start = if self.relative { current } else { root };
+        select(&self.segments, root, start)
+    }
+}
+
+impl Operand {
+    fn value<'a>(&'a self, current: &'a Value, root: &'a Value) -> Option<&'a Value> {
+        match self {
+            Operand::Query(query) => query.select(current, root).into_iter().next(),
+            Operand::Literal(value) => Some(value),
+        }
+    }
+}
+
+/// Numbers compare by value and strings by code points; other values are only equal or
+/// not. A path selecting nothing only equals another path selecting nothing.
+fn compare(left: Option<&Value>, comparison: Comparison, right: Option<&Value>) -> bool {
+    let ordering = match (left, right) {
+        (None, None) => Some(Ordering::Equal),
+        (Some(Value::Number(left)), Some(Value::Number(right))) => {
+            left.as_f64().partial_cmp(&right.as_f64())
+        }
+        (Some(Value::String(left)), Some(Value::String(right))) => Some(left.cmp(right)),
+        (Some(left), Some(right)) => (left == right).then_some(Ordering::Equal),
+        _ => None,
+    };
+    match comparison {
+        Comparison::Equal => ordering == Some(Ordering::Equal),
+        Comparison::NotEqual => ordering != Some(Ordering::Equal),
+        Comparison::Less => ordering == Some(Ordering::Less),
+        Comparison::LessOrEqual => matches!(ordering, Some(Ordering::Less | Ordering::Equal)),
+        Comparison::Greater => ordering == Some(Ordering::Greater),
+        Comparison::GreaterOrEqual => {
+            matches!(ordering, Some(Ordering::Greater | Ordering::Equal))
+        }
+    }
+}
+
+fn is_name_char(c: char) -> bool {
+    c.is_alphanumeric() || c == '_' || c == '-' || !c.is_ascii()
+}
+
+struct Parser<'a> {
+    expression: &'a str,
+    chars: Vec<char>,
+    position: usize,
+}
+
+impl<'a> Parser<'a> {
+    fn new(expression: &'a str) -> Self {
+        Self {
+            expression,
+            chars: expression.chars().collect(),
+            position: 0,
+        }
+    }
+
+    fn path(&mut self) -> Result<Vec<Segment>, Error> {
+        // jq-like paths have no `$`: `.items[0]`, `[0]`.
+        if !self.eat('$') && !matches!(self.peek(), Some('.' | '[')) {
+            return Err(self.error("'$'"));
+        }
+        self.segments()
+    }
+
+    fn segments(&mut self) -> Result<Vec<Segment>, Error> {
+        let mut segments = vec![];
+        loop {
+            match self.peek() {
+                Some('.') if self.peek_at(1) == Some('.') => {
+                    self.position += 2;
+                    let selectors = if self.peek() == Some('[') {
+                        self.bracket()?
+                    } else {
+                        vec![self.member()?]
+                    };
+                    segments.push(Segment::Descendant(selectors));
+                }
+                Some('.') => {
+                    self.position += 1;
+                    match self.peek() {
+                        Some('[') => {}
+                        Some(c) if c == '*' || is_name_char(c) => {
+                            segments.push(Segment::Child(vec![self.member()?]));
+                        }
+                        // A lone `.` is jq's root.
+                        _ if self.position == 1 => break,
+                        _ => return Err(self.error("a member name")),
+                    }
+                }
+                Some('[') => segments.push(Segment::Child(self.bracket()?)),
+                _ => break,
+            }
+        }
+        Ok(segments)
+    }
+
+    fn member(&mut self) -> Result<Selector, Error> {
+        if self.eat('*') {
+            return Ok(Selector::Wildcard);
+        }
+        let start = self.position;
+        while self.peek().is_some_and(is_name_char) {
+            self.position += 1;
+        }
+        if start == self.position {
+            return Err(self.error("a member name"));
+        }
+        Ok(Selector::Name(
+            self.chars[start..self.position].iter().collect(),
+        ))
+    }
+
+    fn bracket(&mut self) -> Result<Vec<Selector>, Error> {
+        self.expect('[', "'['")?;
+        self.skip_spaces();
+        // jq's `[]` iterates like `[*]`.
+        if self.eat(']') {
+            return Ok(vec![Selector::Wildcard]);
+        }
+        let mut selectors = vec![];
+        loop {
+            self.skip_spaces();
+            selectors.push(self.selector()?);
+            self.skip_spaces();
+            if self.eat(']') {
+                return Ok(selectors);
+            }
+            self.expect(',', "',' or ']'")?;
+        }
+    }
+
+    fn selector(&mut self) -> Result<Selector, Error> {
+        match self.peek() {
+            Some('*') => {
+                self.position += 1;
+                Ok(Selector::Wildcard)
+            }
+            Some('\'' | '"') => Ok(Selector::Name(self.string()?)),
+            Some('?') => {
+                self.position += 1;
+                Ok(Selector::Filter(self.or()?))
+            }
+            _ => {
+                let start = self.integer()?;
+                self.skip_spaces();
+                if !self.eat(':') {
+                    return start
+                        .map(Selector::Index)
+                        .ok_or_else(|| self.error("a selector"));
+                }
+                self.skip_spaces();
+                let end = self.integer()?;
+                self.skip_spaces();
+                let step = if self.eat(':') {
+                    self.skip_spaces();
+                    self.integer()?
+                } else {
+                    None
+                };
+                Ok(Selector::Slice { start, end, step })
+            }
+        }
+    }
+
+    fn integer(&mut self) -> Result<Option<i64>, Error> {
+        let start = self.position;
+        self.eat('-');
+        while self.peek().is_some_and(|c| c.is_ascii_digit()) {
+            self.position += 1;
+        }
+        if start == self.position {
+            return Ok(None);
+        }
+        let digits: String = self.chars[start..self.position].iter().collect();
+        digits.parse().map(Some).map_err(|_| {
+            self.position = start;
+            self.error("an integer")
+        })
+    }
+
+    fn string(&mut self) -> Result<String, Error> {
+        let quote = self.chars[self.position];
+        self.position += 1;
+        let mut text = String::new();
+        loop {
+            match self.peek() {
+                None => return Err(self.error("a closing quote")),
+                Some(c) if c == quote => {
+                    self.position += 1;
+                    return Ok(text);
+                }
+                Some('\\') => {
+                    self.position += 1;
+                    let escaped = self
+                        .peek()
+                        .ok_or_else(|| self.error("an escaped character"))?;
+                    self.position += 1;
+                    text.push(match escaped {
+                        'n' => '\n',
+                        'r' => '\r',
+                        't' => '\t',
+                        other => other,
+                    });
+                }
+                Some(c) => {
+                    self.position += 1;
+                    text.push(c);
+                }
+            }
+        }
+    }
+
+    fn or(&mut self) -> Result<Filter, Error> {
+        let mut filter = self.and()?;
+        loop {
+            self.skip_spaces();
+            if !self.eat_str("||") {
+                return Ok(filter);
+            }
+            filter = Filter::Or(Box::new(filter), Box::new(self.and()?));
+        }
+    }
+
+    fn and(&mut self) -> Result<Filter, Error> {
+        let mut filter = self.unary()?;
+        loop {
+            self.skip_spaces();
+            if !self.eat_str("&&") {
+                return Ok(filter);
+            }
+            filter = Filter::And(Box::new(filter), Box::new(self.unary()?));
+        }
+    }
+
+    fn unary(&mut self) -> Result<Filter, Error> {
+        self.skip_spaces();
+        if self.peek() == Some('!') && self.peek_at(1) != Some('=') {
+            self.position += 1;
+            return Ok(Filter::Not(Box::new(self.unary()?)));
+        }
+        if self.eat('(') {
+            let filter = self.or()?;
+            self.skip_spaces();
+            self.expect(')', "')'")?;
+            return Ok(filter);
+        }
+        self.comparison()
+    }
+
+    fn comparison(&mut self) -> Result<Filter, Error> {
+        let left = self.operand()?;
+        self.skip_spaces();
+        let Some(comparison) = self.comparison_operator() else {
+            return match left {
+                Operand::Query(query) => Ok(Filter::Exists(query)),
+                Operand::Literal(_) => Err(self.error("a comparison operator")),
+            };
+        };
+        self.skip_spaces();
+        let right = self.operand()?;
+        Ok(Filter::Compare(left, comparison, right))
+    }
+
+    fn comparison_operator(&mut self) -> Option<Comparison> {
+        let operators = [
+            ("==", Comparison::Equal),
+            ("!=", Comparison::NotEqual),
+            ("<=", Comparison::LessOrEqual),
+            (">=", Comparison::GreaterOrEqual),
+            ("<", Comparison::Less),
+            (">", Comparison::Greater),
+        ];
+        operators
+            .into_iter()
+            .find(|(operator, _)| self.eat_str(operator))
+            .map(|(_, comparison)| comparison)
+    }
+
+    fn operand(&mut self) -> Result<Operand, Error> {
+        match self.peek() {
+            Some(origin @ ('@' | '$')) => {
+                self.position += 1;
+                Ok(Operand::Query(Query {
+                    relative: origin == '@',
+                    segments: self.segments()?,
+                }))
+            }
+            Some('\'' | '"') => Ok(Operand::Literal(Value::String(self.string()?))),
+            _ => {
+                let start = self.position;
+                while self
+                    .peek()
+                    .is_some_and(|c| c.is_ascii_alphanumeric() || matches!(c, '-' | '+' | '.'))
+                {
+                    self.position += 1;
+                }
+                let token: String = self.chars[start..self.position].iter().collect();
+                match serde_json::from_str::<Value>(&token) {
+                    Ok(value) if !token.is_empty() => Ok(Operand::Literal(value)),
+                    _ => {
+                        self.position = start;
+                        Err(self.error("a path or a literal"))
+                    }
+                }
+            }
+        }
+    }
+
+    fn peek(&self) -> Option<char> {
+        self.peek_at(0)
+    }
+
+    fn peek_at(&self, offset: usize) -> Option<char> {
+        self.chars.get(self.position + offset).copied()
+    }
+
+    fn eat(&mut self, c: char) -> bool {
+        let found = self.peek() == Some(c);
+        if found {
+            self.position += 1;
+        }
+        found
+    }
+
+    fn eat_str(&mut self, text: &str) -> bool {
+        let found = text
+            .chars()
+            .enumerate()
+            .all(|(offset, c)| self.peek_at(offset) == Some(c));
+        if found {
+            self.position += text.chars().count();
+        }
+        found
+    }
+
+    fn expect(&mut self, c: char, expected: &'static str) -> Result<(), Error> {
+        if self.eat(c) {
+            Ok(())
+        } else {
+            Err(self.error(expected))
+        }
+    }
+
+    fn skip_spaces(&mut self) {
+        while self.peek().is_some_and(char::is_whitespace) {
+            self.position += 1;
+        }
+    }
+
+    fn error(&self, expected: &'static str) -> Error {
+        Error::Syntax {
+            expression: self.expression.to_string(),
+            position: self.position,
+            expected,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn store() -> Value {
+        json!({
+            "store": {
+                "book": [
+                    {"category": "reference", "author": "Nigel Rees", "price": 8.95},
+                    {"category": "fiction", "author": "Evelyn Waugh", "price": 12.99},
+                    {"category": "fiction", "author": "Herman Melville", "price": 8.99, "isbn": "0-553"},
+                    {"category": "fiction", "author": "J. R. R. Tolkien", "price": 22.99, "isbn": "0-395"}
+                ],
+                "bicycle": {"color": "red", "price": 399}
+            },
+            "expensive": 10
+        })
+    }
+
+    fn query(expression: &str, document: &Value) -> Vec<Value> {
+        JsonPath::parse(expression)
+            .unwrap()
+            .select(document)
+            .into_iter()
+            .cloned()
+            .collect()
+    }
+
+    fn numbers() -> Value {
+        json!([0, 1, 2, 3, 4, 5, 6, 7, 8, 9])
+    }
+
+    fn syntax_error(expression: &str) -> (usize, &'static str) {
+        match JsonPath::parse(expression) {
+            Err(Error::Syntax {
+                position, expected, ..
+            }) => (position, expected),
+            Ok(path) => panic!("{expression} parsed as {path:?}"),
+        }
+    }
+
+    #[test]
+    fn selects_members_and_indexes() {
+        let store = store();
+
+        assert_eq!(query("$.store.bicycle.color", &store), [json!("red")]);
+        assert_eq!(
+            query("$['store']['bicycle'][\"price\"]", &store),
+            [json!(399)]
+        );
+        assert_eq!(
+            query("$.store.book[0].author", &store),
+            [json!("Nigel Rees")]
+        );
+        assert_eq!(query("$.store.book[-1].price", &store), [json!(22.99)]);
+        assert_eq!(
+            query("$.store.book[0,2].price", &store),
+            [json!(8.95), json!(8.99)]
+        );
+        assert_eq!(
+            query("$.store.bicycle.*", &store),
+            [json!("red"), json!(399)]
+        );
+        assert!(query("$.store.book[4]", &store).is_empty());
+        assert!(query("$.store.book[-5]", &store).is_empty());
+        assert!(query("$.missing.deeper", &store).is_empty());
+        assert_eq!(query("$", &store), [store.clone()]);
+    }
+
+    #[test]
+    fn slices_like_python() {
+        let numbers = numbers();
+        let slice = |expression: &str| query(expression, &numbers);
+
+        assert_eq!(slice("$[1:3]"), [json!(1), json!(2)]);
+        assert_eq!(slice("$[:2]"), [json!(0), json!(1)]);
+        assert_eq!(slice("$[8:]"), [json!(8), json!(9)]);
+        assert_eq!(slice("$[-2:]"), [json!(8), json!(9)]);
+        assert_eq!(slice("$[::4]"), [json!(0), json!(4), json!(8)]);
+        assert_eq!(slice("$[5:1:-2]"), [json!(5), json!(3)]);
+        assert_eq!(slice("$[::-3]"), [json!(9), json!(6), json!(3), json!(0)]);
+        assert_eq!(slice("$[-100:2]"), [json!(0), json!(1)]);
+        assert!(slice("$[3:1]").is_empty());
+        assert!(slice("$[::0]").is_empty());
+    }
+
+    #[test]
+    fn slices_with_extreme_steps() {
+        let numbers = numbers();
+        let slice = |expression: &str| query(expression, &numbers);
+
+        assert_eq!(slice("$[1::9223372036854775807]"), [json!(1)]);
+        assert_eq!(slice("$[-1::-9223372036854775808]"), [json!(9)]);
+        assert_eq!(
+            slice("$[-9223372036854775808:9223372036854775807:9223372036854775807]"),
+            [json!(0)]
+        );
+    }
+
+    #[test]
+    fn descends_recursively() {
+        let store = store();
+
+        assert_eq!(
+            query("$..author", &store),
+            [
+                json!("Nigel Rees"),
+                json!("Evelyn Waugh"),
+                json!("Herman Melville"),
+                json!("J. R. R. Tolkien"),
+            ]
+        );
+        assert_eq!(
+            query("$.store..price", &store),
+            [
+                json!(8.95),
+                json!(12.99),
+                json!(8.99),
+                json!(22.99),
+                json!(399)
+            ]
+        );
+        assert_eq!(query("$..book[2].isbn", &store), [json!("0-553")]);
+        assert_eq!(
+            query("$..['isbn']", &store),
+            [json!("0-553"), json!("0-395")]
+        );
+        assert_eq!(query("$..*", &json!({"a": [1]})), [json!([1]), json!(1)]);
+    }
+
+    #[test]
+    fn filters_children() {
+        let store = store();
+        let authors = |expression: &str| -> Vec<Value> {
+            query(expression, &store)
+                .into_iter()
+                .map(|book| book["author"].clone())
+                .collect()
+        };
+
+        assert_eq!(
+            authors("$.store.book[?(@.isbn)]"),
+            [json!("Herman Melville"), json!("J. R. R. Tolkien")]
+        );
+        assert_eq!(
+            authors("$.store.book[?(@.price < $.expensive)]"),
+            [json!("Nigel Rees"), json!("Herman Melville")]
+        );
+        assert_eq!(
+            authors("$.store.book[?@.category == 'fiction' && @.price >= 20]"),
+            [json!("J. R. R. Tolkien")]
+        );
+        assert_eq!(
+            authors("$.store.book[?(@.price > 20 || @.category == \"reference\")]"),
+            [json!("Nigel Rees"), json!("J. R. R. Tolkien")]
+        );
+        assert_eq!(
+            authors("$.store.book[?(!@.isbn)]"),
+            [json!("Nigel Rees"), json!("Evelyn Waugh")]
+        );
+        assert_eq!(
+            authors("$.store.book[?(@.price != 8.95 && !(@.category == 'fiction'))]"),
+            Vec::<Value>::new()
+        );
+        assert_eq!(
+            query("$..[?(@.color == 'red')].price", &store),
+            [json!(399)]
+        );
+        // Values of different types are only ever unequal.
+        assert!(query("$.store.book[?(@.price > '8')]", &store).is_empty());
+        assert_eq!(
+            query("$.store.book[?(@.missing == $.nothing)]", &store).len(),
+            4
+        );
+    }
+
+    #[test]
+    fn accepts_jq_shorthands() {
+        let store = store();
+
+        assert_eq!(query(".store.bicycle.color", &store), [json!("red")]);
+        assert_eq!(query(".", &store), [store.clone()]);
+        assert_eq!(query("[1]", &numbers()), [json!(1)]);
+        assert_eq!(query(".[0]", &numbers()), [json!(0)]);
+        assert_eq!(
+            query(".store.book[].price", &store),
+            query("$.store.book[*].price", &store)
+        );
+    }
+
+    #[test]
+    fn locates_selected_values() {
+        let document = json!({"items": [{"id": 1}, {"id": 2}], "odd key": true});
+        let locations: Vec<String> = JsonPath::parse("$..id")
+            .unwrap()
+            .locate(&document)
+            .into_iter()
+            .map(|(location, _)| location)
+            .collect();
+
+        assert_eq!(locations, ["$.items[0].id", "$.items[1].id"]);
+        assert_eq!(
+            JsonPath::parse("$['odd key']").unwrap().locate(&document)[0].0,
+            "$['odd key']"
+        );
+    }
+
+    #[test]
+    fn parses_prefixes() {
+        let (path, rest) = JsonPath::parse_prefix("$.items[?(@.a == 1)].id == 42").unwrap();
+
+        assert_eq!(path.to_string(), "$.items[?(@.a == 1)].id");
+        assert_eq!(rest, " == 42");
+    }
+
+    #[test]
+    fn reports_syntax_errors() {
+        assert_eq!(syntax_error("items"), (0, "'$'"));
+        assert_eq!(syntax_error("$.items.("), (8, "a member name"));
+        assert_eq!(syntax_error("$[0"), (3, "',' or ']'"));
+        assert_eq!(syntax_error("$['a"), (4, "a closing quote"));
+        assert_eq!(syntax_error("$[?(@.a == )]"), (11, "a path or a literal"));
+        assert_eq!(syntax_error("$[?(1)]"), (5, "a comparison operator"));
+        assert_eq!(syntax_error("$[99999999999999999999]"), (2, "an integer"));
+        assert_eq!(syntax_error("$.a b"), (3, "the end of the path"));
+        assert_eq!(
+            JsonPath::parse("$[").unwrap_err().to_string(),
+            "invalid JSONPath '$[': expected a selector at position 2"
+        );
+    }
+}
@@ -1,5 +1,18 @@
-// Recommendation: use http::Request. Already have headers/body. We need an abstraction that works with Request to make it easier to add Auth-z/Auth-n headers.
-// https://docs.rs/http/latest/http/request/struct.Request.html
 pub use http::Request;
 pub mod app;
+pub mod assertions;
+pub mod auth;
+pub mod bench;
+pub mod codegen;
+pub mod collections;
+pub mod environments;
+pub mod history;
+pub mod import;
+pub mod jsonpath;
+pub mod openapi;
+pub mod render;
 pub mod requests;
+pub mod runner;
+pub mod scripts;
+#[cfg(test)]
+pub(crate) mod test_server;
@@ -0,0 +1,48 @@
+use std::fmt;
+use std::path::PathBuf;
+
+#[derive(Debug)]
+pub enum Error {
+    Io {
+        path: PathBuf,
+        source: std::io::Error,
+    },
+    Parse(serde_json::Error),
+    ParseYaml(serde_yaml::Error),
+    /// The document parsed but is neither OpenAPI 3 nor Swagger 2.
+    NotOpenApi,
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Error::Io { path, source } => write!(f, "{}: {source}", path.display()),
+            Error::Parse(source) => write!(f, "invalid JSON: {source}"),
+            Error::ParseYaml(source) => write!(f, "invalid YAML: {source}"),
+            Error::NotOpenApi => write!(f, "not an OpenAPI 3 or Swagger 2 document"),
+        }
+    }
+}
+
+impl std::error::Error for Error {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Error::Io { source, .. } => Some(source),
+            Error::Parse(source) => Some(source),
+            Error::ParseYaml(source) => Some(source),
+            Error::NotOpenApi => None,
+        }
+    }
+}
+
+impl From<serde_json::Error> for Error {
+    fn from(error: serde_json::Error) -> Self {
+        Error::Parse(error)
+    }
+}
+
+impl From<serde_yaml::Error> for Error {
+    fn from(error: serde_yaml::Error) -> Self {
+        Error::ParseYaml(error)
+    }
+}
@@ -0,0 +1,615 @@
+//! OpenAPI 3 and Swagger 2 documents, used to check responses against the contract of the
+//! operation that produced them.
+
+mod error;
+mod schema;
+
+use crate::core::requests::{Method, ResponseRecord, Url};
+use regex::Regex;
+use schema::Patterns;
+use serde_json::{Map, Value, json};
+use std::fmt;
+use std::path::{Path, PathBuf};
+
+pub use error::Error;
+pub(crate) use schema::{has_type, type_name};
+
+const MAX_REF_HOPS: usize = 16;
+
+static NULL: Value = Value::Null;
+
+/// A loaded API description.
+#[derive(Debug, Clone)]
+pub struct OpenApi {
+    path: Option<PathBuf>,
+    document: Value,
+    /// Path prefixes of the servers (`/v1`), longest first, always ending with the empty one.
+    base_paths: Vec<String>,
+    patterns: Patterns,
+}
+
+/// Outcome of checking one response against the spec.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Validation {
+    /// The matched operation, e.g. `GET /pets/{petId}`.
+    pub operation: Option<String>,
+    pub violations: Vec<Violation>,
+}
+
+/// One way the response differs from the spec. `pointer` locates the offending value in the
+/// JSON body; it is `None` for the status code and headers.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Violation {
+    pub pointer: Option<String>,
+    pub message: String,
+}
+
+struct Operation<'a> {
+    template: &'a str,
+    value: &'a Value,
+}
+
+impl OpenApi {
+    pub fn load(path: &Path) -> Result<Self, Error> {
+        let content = std::fs::read_to_string(path).map_err(|source| Error::Io {
+            path: path.to_path_buf(),
+            source,
+        })?;
+        let mut spec = Self::parse(&content)?;
+        spec.path = Some(path.to_path_buf());
+        Ok(spec)
+    }
+
+    /// Reads a JSON or YAML document.
+    pub fn parse(content: &str) -> Result<Self, Error> {
+        let document = document(content)?;
+        if !detect(&document) {
+            return Err(Error::NotOpenApi);
+        }
+
+        let mut base_paths: Vec<String> = if document["swagger"].is_string() {
+            vec![text(&document["basePath"]).to_string()]
+        } else {
+            document["servers"]
+                .as_array()
+                .into_iter()
+                .flatten()
+                .map(|server| {
+                    let mut url = text(&server["url"]).to_string();
+                    for (name, variable) in server["variables"].as_object().into_iter().flatten() {
+                        url = url.replace(&format!("{{{name}}}"), text(&variable["default"]));
+                    }
+                    match Url::parse(&url) {
+                        Ok(url) => url.path().to_string(),
+                        Err(_) => url,
+                    }
+                })
+                .collect()
+        };
+        for base_path in &mut base_paths {
+            *base_path = base_path.trim_end_matches('/').to_string();
+        }
+        base_paths.sort_by_key(|base_path| std::cmp::Reverse(base_path.len()));
+        if base_paths
+            .last()
+            .is_none_or(|base_path| !base_path.is_empty())
+        {
+            base_paths.push(String::new());
+        }
+
+        Ok(Self {
+            path: None,
+            patterns: Patterns::compile(&document),
+            document,
+            base_paths,
+        })
+    }
+
+    /// File the spec was loaded from.
+    pub fn path(&self) -> Option<&Path> {
+        self.path.as_deref()
+    }
+
+    pub fn title(&self) -> &str {
+        text(&self.document["info"]["title"])
+    }
+
+    /// Matches the response to its operation (method plus path template), then checks the
+    /// status code, the content type and the JSON body against what the operation declares.
+    pub fn validate(&self, method: &Method, url: &Url, response: &ResponseRecord) -> Validation {
+        let Some(operation) = self.operation(method, url.path()) else {
+            return Validation {
+                operation: None,
+                violations: vec![Violation::new(
+                    None,
+                    format!("no operation matches {method} {}", url.path()),
+                )],
+            };
+        };
+
+        let mut violations = vec![];
+        if let Some(schema) = self.response_schema(&operation, response, &mut violations) {
+            match serde_json::from_slice::<Value>(&response.body) {
+                Ok(body) => schema::validate(
+                    &self.document,
+                    &self.patterns,
+                    schema,
+                    &body,
+                    &mut violations,
+                ),
+                Err(error) => violations.push(Violation::new(
+                    Some(String::new()),
+                    format!("body is not valid JSON: {error}"),
+                )),
+            }
+        }
+
+        Validation {
+            operation: Some(format!("{method} {}", operation.template)),
+            violations,
+        }
+    }
+
+    fn operation(&self, method: &Method, path: &str) -> Option<Operation<'_>> {
+        let method = method.as_str().to_lowercase();
+        for base_path in &self.base_paths {
+            let Some(rest) = path.strip_prefix(base_path.as_str()) else {
+                continue;
+            };
+            if !rest.is_empty() && !rest.starts_with('/') {
+                continue;
+            }
+            let rest = if rest.is_empty() { "/" } else { rest };
+
+            // Prefer the template with the most literal segments: `/pets/mine` over `/pets/{id}`.
+            let best = self.document["paths"]
+                .as_object()
+                .into_iter()
+                .flatten()
+                .filter_map(|(template, item)| {
+                    let value = resolve(&self.document, item).get(&method)?;
+                    let literals = template_matches(template, rest)?;
+                    Some((literals, Operation { template, value }))
+                })
+                .max_by_key(|(literals, _)| *literals);
+            if let Some((_, operation)) = best {
+                return Some(operation);
+            }
+        }
+        None
+    }
+
+    /// The schema of the JSON body declared for the response's status and content type, if
+    /// any. Undeclared statuses and content types are reported as violations.
+    fn response_schema<'a>(
+        &'a self,
+        operation: &Operation<'a>,
+        response: &ResponseRecord,
+        violations: &mut Vec<Violation>,
+    ) -> Option<&'a Value> {
+        let responses = &operation.value["responses"];
+        let status = response.status.as_u16();
+        let range = format!("{}XX", status / 100);
+        let declared = responses
+            .get(status.to_string())
+            .or_else(|| responses.get(&range))
+            .or_else(|| responses.get(range.to_lowercase()))
+            .or_else(|| responses.get("default"));
+        let Some(declared) = declared else {
+            violations.push(Violation::new(
+                None,
+                format!("status {status} is not declared"),
+            ));
+            return None;
+        };
+        let declared = resolve(&self.document, declared);
+        let content_type = response.content_type().map(essence);
+
+        let schema = if self.document["swagger"].is_string() {
+            let produces = match operation.value.get("produces") {
+                Some(produces) => produces,
+                None => &self.document["produces"],
+            };
+            let produces: Vec<&str> = produces
+                .as_array()
+                .into_iter()
+                .flatten()
+                .map(text)
+                .collect();
+            if let Some(content_type) = &content_type {
+                if !produces.is_empty()
+                    && !produces
+                        .iter()
+                        .any(|declared| media_type_matches(declared, content_type))
+                {
+                    violations.push(Violation::new(
+                        None,
+                        format!(
+                            "content type '{content_type}' is not declared, expected {}",
+                            produces.join(" or ")
+                        ),
+                    ));
+                    return None;
+                }
+            }
+            declared.get("schema")?
+        } else {
+            let content = declared["content"]
+                .as_object()
+                .filter(|content| !content.is_empty())?;
+            let expected = || content.keys().cloned().collect::<Vec<_>>().join(" or ");
+            let Some(content_type) = &content_type else {
+                if !response.body.is_empty() {
+                    violations.push(Violation::new(
+                        None,
+                        format!("missing Content-Type, expected {}", expected()),
+                    ));
+                }
+                return None;
+            };
+            match content
+                .iter()
+                .find(|(declared, _)| media_type_matches(declared, content_type))
+            {
+                Some((_, media)) => media.get("schema")?,
+                None => {
+                    violations.push(Violation::new(
+                        None,
+                        format!(
+                            "content type '{content_type}' is not declared, expected {}",
+                            expected()
+                        ),
+                    ));
+                    return None;
+                }
+            }
+        };
+
+        // Only JSON bodies are checked against the schema.
+        let is_json = content_type
+            .as_deref()
+            .is_none_or(|content_type| content_type.contains("json"));
+        (is_json && !response.body.is_empty()).then_some(schema)
+    }
+}
+
+impl Validation {
+    pub fn is_valid(&self) -> bool {
+        self.violations.is_empty()
+    }
+}
+
+impl Violation {
+    pub fn new(pointer: Option<String>, message: impl Into<String>) -> Self {
+        Self {
+            pointer,
+            message: message.into(),
+        }
+    }
+}
+
+/// Body violations are prefixed with their pointer as a URI fragment: `#/pets/0/name: ...`.
+impl fmt::Display for Violation {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match &self.pointer {
+            Some(pointer) => write!(f, "#{pointer}: {}", self.message),
+            None => write!(f, "{}", self.message),
+        }
+    }
+}
+
+impl fmt::Display for Validation {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let operation = self.operation.as_deref().unwrap_or("unknown operation");
+        if self.is_valid() {
+            return write!(f, "Contract ({operation}): OK");
+        }
+        write!(
+            f,
+            "Contract ({operation}): {} violation(s)",
+            self.violations.len()
+        )?;
+        for violation in &self.violations {
+            write!(f, "\n\t{violation}")?;
+        }
+        Ok(())
+    }
+}
+
+/// Checks `instance` against a standalone JSON Schema, whose `$ref`s point inside itself.
+pub fn validate_schema(schema: &Value, instance: &Value) -> Vec<Violation> {
+    let mut violations = vec![];
+    let patterns = Patterns::compile(schema);
+    schema::validate(schema, &patterns, schema, instance, &mut violations);
+    violations
+}
+
+/// Whether `document` is an OpenAPI 3 or Swagger 2 description.
+pub fn detect(document: &Value) -> bool {
+    text(&document["openapi"]).starts_with("3.") || text(&document["swagger"]) == "2.0"
+}
+
+/// Parses JSON, or YAML when the content is not JSON.
+pub fn document(content: &str) -> Result<Value, Error> {
+    if content.trim_start().starts_with('{') {
+        return Ok(serde_json::from_str(content)?);
+    }
+    let yaml: serde_yaml::Value = serde_yaml::from_str(content)?;
+    Ok(yaml_to_json(yaml))
+}
+
+/// YAML allows non-string keys, such as the status codes of `responses`; JSON objects don't.
+fn yaml_to_json(yaml: serde_yaml::Value) -> Value {
+    match yaml {
+        serde_yaml::Value::Null => Value::Null,
+        serde_yaml::Value::Bool(value) => Value::Bool(value),
+        serde_yaml::Value::Number(number) => {
+            if let Some(number) = number.as_i64() {
+                json!(number)
+            } else if let Some(number) = number.as_u64() {
+                json!(number)
+            } else {
+                json!(number.as_f64())
+            }
+        }
+        serde_yaml::Value::String(value) => Value::String(value),
+        serde_yaml::Value::Sequence(items) => items.into_iter().map(yaml_to_json).collect(),
+        serde_yaml::Value::Mapping(mapping) => mapping
+            .into_iter()
+            .map(|(key, value)| {
+                let key = match yaml_to_json(key) {
+                    Value::String(key) => key,
+                    key => key.to_string(),
+                };
+                (key, yaml_to_json(value))
+            })
+            .collect::<Map<_, _>>()
+            .into(),
+        serde_yaml::Value::Tagged(tagged) => yaml_to_json(tagged.value),
+    }
+}
+
+/// Follows local `$ref`s (`#/components/schemas/User`). Anything that cannot be followed
+/// resolves to null.
+pub fn resolve<'a>(document: &'a Value, mut value: &'a Value) -> &'a Value {
+    for _ in 0..MAX_REF_HOPS {
+        let Some(reference) = value["$ref"].as_str() else {
+            return value;
+        };
+        value = reference
+            .strip_prefix('#')
+            .and_then(|pointer| document.pointer(pointer))
+            .unwrap_or(&NULL);
+    }
+    &NULL
+}
+
+/// Number of literal segments when `path` matches `template`, `None` otherwise.
+fn template_matches(template: &str, path: &str) -> Option<usize> {
+    let template: Vec<&str> = template.trim_end_matches('/').split('/').collect();
+    let path: Vec<&str> = path.trim_end_matches('/').split('/').collect();
+    if template.len() != path.len() {
+        return None;
+    }
+
+    let mut literals = 0;
+    for (expected, actual) in template.iter().zip(&path) {
+        if !expected.contains('{') {
+            if expected != actual {
+                return None;
+            }
+            literals += 1;
+            continue;
+        }
+        // Parameters may share a segment with text, as in `{name}.json`.
+        let mut pattern = String::from("^");
+        for (index, part) in expected.split(['{', '}']).enumerate() {
+            match index % 2 {
+                0 => pattern.push_str(&regex::escape(part)),
+                _ => pattern.push_str("[^/]+"),
+            }
+        }
+        pattern.push('$');
+        if actual.is_empty() || !Regex::new(&pattern).is_ok_and(|regex| regex.is_match(actual)) {
+            return None;
+        }
+    }
+    Some(literals)
+}
+
+/// `application/json` for `application/json; charset=utf-8`.
+fn essence(content_type: &str) -> String {
+    content_type
+        .split(';')
+        .next()
+        .unwrap_or_default()
+        .trim()
+        .to_lowercase()
+}
+
+/// Whether a declared media type range such as `application/*` covers `content_type`.
+fn media_type_matches(declared: &str, content_type: &str) -> bool {
+    let declared = essence(declared);
+    declared == content_type
+        || declared == "*/*"
+        || declared
+            .strip_suffix("/*")
+            .is_some_and(|kind| content_type.split('/').next() == Some(kind))
+}
+
+fn text(value: &Value) -> &str {
+    value.as_str().unwrap_or_default()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SPEC: &str = r#"
+openapi: 3.0.3
+info:
+  title: Pets
+servers:
+  - url: https://api.example.com/{version}
+    variables:
+      version:
+        default: v1
+paths:
+  /pets/{petId}:
+    get:
+      responses:
+        200:
+          content:
+            application/json:
+              schema:
+                $ref: '#/components/schemas/Pet'
+        4XX:
+          description: Client error
+  /pets/mine:
+    get:
+      responses:
+        200:
+          description: Mine
+  /files/{name}.json:
+    get:
+      responses:
+        default:
+          description: Any
+components:
+  schemas:
+    Pet:
+      type: object
+      required: [id, name]
+      properties:
+        id:
+          type: integer
+          minimum: 1
+        name:
+          type: string
+          pattern: '^[A-Z]'
+        tags:
+          type: array
+          items:
+            type: string
+      additionalProperties: false
+"#;
+
+    fn validate(method: Method, url: &str, response: &ResponseRecord) -> Validation {
+        let spec = OpenApi::parse(SPEC).unwrap();
+        spec.validate(&method, &Url::parse(url).unwrap(), response)
+    }
+
+    fn json(status: u16, body: &str) -> ResponseRecord {
+        ResponseRecord::stub(status, &[("content-type", "application/json")], body)
+    }
+
+    fn messages(validation: &Validation) -> Vec<String> {
+        let violations = validation.violations.iter();
+        violations.map(ToString::to_string).collect()
+    }
+
+    #[test]
+    fn matches_operations_below_the_server_base_path() {
+        let ok = json(200, r#"{"id": 1, "name": "Rex"}"#);
+        let operation = |method: Method, url: &str| validate(method, url, &ok).operation;
+
+        let pet = Some("GET /pets/{petId}".to_string());
+        assert_eq!(
+            operation(Method::GET, "https://api.example.com/v1/pets/7"),
+            pet
+        );
+        assert_eq!(
+            operation(Method::GET, "http://localhost:8080/v1/pets/7/"),
+            pet
+        );
+        assert_eq!(operation(Method::GET, "http://localhost/pets/7"), pet);
+        assert_eq!(
+            operation(Method::GET, "http://localhost/v1/files/report.json"),
+            Some("GET /files/{name}.json".to_string())
+        );
+
+        let validation = validate(Method::GET, "http://localhost/v2/pets/7", &ok);
+        assert_eq!(validation.operation, None);
+        assert_eq!(
+            messages(&validation),
+            ["no operation matches GET /v2/pets/7"]
+        );
+        assert_eq!(operation(Method::POST, "http://localhost/v1/pets/7"), None);
+        assert_eq!(operation(Method::GET, "http://localhost/v1pets/7"), None);
+    }
+
+    #[test]
+    fn prefers_literal_segments_over_templates() {
+        let ok = ResponseRecord::stub(200, &[], "");
+        let validation = validate(Method::GET, "http://localhost/v1/pets/mine", &ok);
+        assert_eq!(validation.operation.as_deref(), Some("GET /pets/mine"));
+        assert!(validation.is_valid());
+    }
+
+    #[test]
+    fn reports_undeclared_statuses_and_content_types() {
+        let url = "http://localhost/v1/pets/7";
+        let validation = validate(Method::GET, url, &json(500, "{}"));
+        assert_eq!(messages(&validation), ["status 500 is not declared"]);
+        assert!(validate(Method::GET, url, &json(404, "{}")).is_valid());
+
+        let html = ResponseRecord::stub(200, &[("content-type", "text/html")], "<p>");
+        assert_eq!(
+            messages(&validate(Method::GET, url, &html)),
+            ["content type 'text/html' is not declared, expected application/json"]
+        );
+        let untyped = ResponseRecord::stub(200, &[], "{}");
+        assert_eq!(
+            messages(&validate(Method::GET, url, &untyped)),
+            ["missing Content-Type, expected application/json"]
+        );
+    }
+
+    #[test]
+    fn reports_schema_violations_with_their_pointers() {
+        let url = "http://localhost/v1/pets/7";
+        let body = r#"{"id": 0, "name": "rex", "tags": ["a", 2], "a/b": true}"#;
+        let validation = validate(Method::GET, url, &json(200, body));
+        assert_eq!(validation.operation.as_deref(), Some("GET /pets/{petId}"));
+        assert_eq!(
+            messages(&validation),
+            [
+                "#/id: 0 is below the minimum of 1",
+                "#/name: does not match the pattern '^[A-Z]'",
+                "#/tags/1: expected string, found integer",
+                "#/a~1b: property 'a/b' is not allowed",
+            ]
+        );
+
+        let validation = validate(Method::GET, url, &json(200, r#"[{"id": 1}]"#));
+        assert_eq!(messages(&validation), ["#: expected object, found array"]);
+        let validation = validate(Method::GET, url, &json(200, r#"{"id": 1}"#));
+        assert_eq!(
+            messages(&validation),
+            ["#: missing required property 'name'"]
+        );
+        let validation = validate(Method::GET, url, &json(200, "{"));
+        assert_eq!(validation.violations[0].pointer.as_deref(), Some(""));
+        assert!(
+            validation.violations[0]
+                .message
+                .starts_with("body is not valid JSON")
+        );
+    }
+
+    #[test]
+    fn strips_the_swagger_base_path() {
+        let spec = OpenApi::parse(
+            r#"{"swagger": "2.0", "basePath": "/api/", "produces": ["application/json"],
+                "paths": {"/users": {"get": {"responses": {"200": {"schema": {"type": "array"}}}}}}}"#,
+        )
+        .unwrap();
+        let url = Url::parse("http://localhost/api/users").unwrap();
+
+        let validation = spec.validate(&Method::GET, &url, &json(200, "[]"));
+        assert_eq!(validation.operation.as_deref(), Some("GET /users"));
+        assert!(validation.is_valid());
+        let validation = spec.validate(&Method::GET, &url, &json(200, "{}"));
+        assert_eq!(messages(&validation), ["#: expected array, found object"]);
+    }
+}
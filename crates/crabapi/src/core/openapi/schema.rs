@@ -0,0 +1,412 @@
+//! The subset of JSON Schema used by OpenAPI: types, enums, bounds, patterns, required and
+//! additional properties, items and the `allOf`/`anyOf`/`oneOf`/`not` combinators.
+//! Formats are annotations only and are not checked.
+
+use super::{Violation, resolve};
+use regex::Regex;
+use serde_json::Value;
+use std::collections::HashMap;
+
+/// Deepest nesting of schemas followed, as they may be recursive.
+const MAX_DEPTH: usize = 64;
+
+/// Every `pattern` of a document, compiled once. Patterns that are not valid regular
+/// expressions are left out, and not checked.
+#[derive(Debug, Clone, Default)]
+pub struct Patterns(HashMap<String, Regex>);
+
+/// Appends to `violations` every way `instance` does not match `schema`.
+pub fn validate(
+    document: &Value,
+    patterns: &Patterns,
+    schema: &Value,
+    instance: &Value,
+    violations: &mut Vec<Violation>,
+) {
+    Validator { document, patterns }.check(schema, instance, "", 0, violations);
+}
+
+struct Validator<'a> {
+    document: &'a Value,
+    patterns: &'a Patterns,
+}
+
+impl Patterns {
+    pub fn compile(document: &Value) -> Self {
+        let mut patterns = HashMap::new();
+        let mut pending = vec![document];
+        while let Some(value) = pending.pop() {
+            match value {
+                Value::Object(fields) => {
+                    if let Some(Value::String(pattern)) = fields.get("pattern") {
+                        if let Ok(regex) = Regex::new(pattern) {
+                            patterns.insert(pattern.clone(), regex);
+                        }
+                    }
+                    pending.extend(fields.values());
+                }
+                Value::Array(items) => pending.extend(items),
+                _ => {}
+            }
+        }
+        Self(patterns)
+    }
+}
+
+impl Validator<'_> {
+    fn check(
+        &self,
+        schema: &Value,
+        instance: &Value,
+        pointer: &str,
+        depth: usize,
+        violations: &mut Vec<Violation>,
+    ) {
+        let schema = resolve(self.document, schema);
+        if depth > MAX_DEPTH {
+            return;
+        }
+        let mut violation = |message: String| {
+            violations.push(Violation::new(Some(pointer.to_string()), message));
+        };
+        if let Value::Bool(allowed) = schema {
+            if !allowed {
+                violation("no value is allowed here".to_string());
+            }
+            return;
+        }
+
+        let types: Vec<&str> = match &schema["type"] {
+            Value::String(kind) => vec![kind.as_str()],
+            Value::Array(kinds) => kinds.iter().filter_map(Value::as_str).collect(),
+            _ => vec![],
+        };
+        let nullable = schema["nullable"].as_bool() == Some(true)
+            || schema["x-nullable"].as_bool() == Some(true)
+            || types.contains(&"null");
+        if instance.is_null() && nullable {
+            return;
+        }
+        if !types.is_empty() && !types.iter().any(|kind| has_type(instance, kind)) {
+            violation(format!(
+                "expected {}, found {}",
+                types.join(" or "),
+                type_name(instance)
+            ));
+            return;
+        }
+
+        if let Some(values) = schema["enum"].as_array() {
+            if !values.contains(instance) {
+                let values: Vec<String> = values.iter().map(Value::to_string).collect();
+                violation(format!("{instance} is not one of {}", values.join(", ")));
+            }
+        }
+        if let Some(expected) = schema.get("const") {
+            if expected != instance {
+                violation(format!("expected {expected}, found {instance}"));
+            }
+        }
+
+        match instance {
+            Value::String(text) => {
+                let length = text.chars().count() as u64;
+                if let Some(min) = schema["minLength"].as_u64().filter(|min| length < *min) {
+                    violation(format!("shorter than {min} characters"));
+                }
+                if let Some(max) = schema["maxLength"].as_u64().filter(|max| length > *max) {
+                    violation(format!("longer than {max} characters"));
+                }
+                if let Some(pattern) = schema["pattern"].as_str() {
+                    let regex = self.patterns.0.get(pattern);
+                    if regex.is_some_and(|regex| !regex.is_match(text)) {
+                        violation(format!("does not match the pattern '{pattern}'"));
+                    }
+                }
+            }
+            Value::Number(number) => {
+                let number = number.as_f64().unwrap_or_default();
+                let exclusive = |key: &str| schema[key].as_bool() == Some(true);
+                if let Some(min) = schema["minimum"].as_f64() {
+                    if number < min || (exclusive("exclusiveMinimum") && number == min) {
+                        violation(format!("{number} is below the minimum of {min}"));
+                    }
+                }
+                if let Some(max) = schema["maximum"].as_f64() {
+                    if number > max || (exclusive("exclusiveMaximum") && number == max) {
+                        violation(format!("{number} is above the maximum of {max}"));
+                    }
+                }
+                // OpenAPI 3.1 gives the exclusive bounds as numbers.
+                if let Some(min) = schema["exclusiveMinimum"]
+                    .as_f64()
+                    .filter(|min| number <= *min)
+                {
+                    violation(format!("{number} is not above {min}"));
+                }
+                if let Some(max) = schema["exclusiveMaximum"]
+                    .as_f64()
+                    .filter(|max| number >= *max)
+                {
+                    violation(format!("{number} is not below {max}"));
+                }
+                if let Some(step) = schema["multipleOf"].as_f64().filter(|step| *step > 0.0) {
+                    let quotient = number / step;
+                    if (quotient - quotient.round()).abs() > 1e-9 {
+                        violation(format!("{number} is not a multiple of {step}"));
+                    }
+                }
+            }
+            Value::Array(items) => {
+                let length = items.len() as u64;
+                if let Some(min) = schema["minItems"].as_u64().filter(|min| length < *min) {
+                    violation(format!("fewer than {min} items"));
+                }
+                if let Some(max) = schema["maxItems"].as_u64().filter(|max| length > *max) {
+                    violation(format!("more than {max} items"));
+                }
+                if schema["uniqueItems"].as_bool() == Some(true) {
+                    let duplicate = items
+                        .iter()
+                        .enumerate()
+                        .any(|(index, item)| items[..index].contains(item));
+                    if duplicate {
+                        violation("items are not unique".to_string());
+                    }
+                }
+            }
+            Value::Object(fields) => {
+                let count = fields.len() as u64;
+                if let Some(min) = schema["minProperties"].as_u64().filter(|min| count < *min) {
+                    violation(format!("fewer than {min} properties"));
+                }
+                if let Some(max) = schema["maxProperties"].as_u64().filter(|max| count > *max) {
+                    violation(format!("more than {max} properties"));
+                }
+                for required in schema["required"].as_array().into_iter().flatten() {
+                    let name = required.as_str().unwrap_or_default();
+                    if !fields.contains_key(name) {
+                        violation(format!("missing required property '{name}'"));
+                    }
+                }
+            }
+            _ => {}
+        }
+
+        // Nested values report their own violations under a longer pointer.
+        match instance {
+            Value::Array(items) => {
+                if schema.get("items").is_some_and(|items| !items.is_null()) {
+                    for (index, item) in items.iter().enumerate() {
+                        let pointer = format!("{pointer}/{index}");
+                        self.check(&schema["items"], item, &pointer, depth + 1, violations);
+                    }
+                }
+            }
+            Value::Object(fields) => {
+                let properties = schema["properties"].as_object();
+                for (name, value) in fields {
+                    let pointer = format!("{pointer}/{}", escape(name));
+                    match properties.and_then(|properties| properties.get(name)) {
+                        Some(property) => {
+                            self.check(property, value, &pointer, depth + 1, violations)
+                        }
+                        None => match &schema["additionalProperties"] {
+                            Value::Bool(false) => violations.push(Violation::new(
+                                Some(pointer),
+                                format!("property '{name}' is not allowed"),
+                            )),
+                            additional if additional.is_object() => {
+                                self.check(additional, value, &pointer, depth + 1, violations)
+                            }
+                            _ => {}
+                        },
+                    }
+                }
+            }
+            _ => {}
+        }
+
+        for part in schema["allOf"].as_array().into_iter().flatten() {
+            self.check(part, instance, pointer, depth + 1, violations);
+        }
+        if let Some(options) = schema["anyOf"].as_array() {
+            if !options
+                .iter()
+                .any(|option| self.matches(option, instance, pointer, depth))
+            {
+                violations.push(Violation::new(
+                    Some(pointer.to_string()),
+                    "does not match any of the anyOf schemas",
+                ));
+            }
+        }
+        if let Some(options) = schema["oneOf"].as_array() {
+            let matching = options
+                .iter()
+                .filter(|option| self.matches(option, instance, pointer, depth))
+                .count();
+            if matching != 1 {
+                violations.push(Violation::new(
+                    Some(pointer.to_string()),
+                    format!("matches {matching} of the oneOf schemas instead of exactly one"),
+                ));
+            }
+        }
+        if schema
+            .get("not")
+            .is_some_and(|not| self.matches(not, instance, pointer, depth))
+        {
+            violations.push(Violation::new(
+                Some(pointer.to_string()),
+                "matches a schema it must not match",
+            ));
+        }
+    }
+
+    fn matches(&self, schema: &Value, instance: &Value, pointer: &str, depth: usize) -> bool {
+        let mut violations = vec![];
+        self.check(schema, instance, pointer, depth + 1, &mut violations);
+        violations.is_empty()
+    }
+}
+
+pub(crate) fn has_type(instance: &Value, kind: &str) -> bool {
+    match kind {
+        "null" => instance.is_null(),
+        "boolean" => instance.is_boolean(),
+        "integer" => {
+            instance.is_i64()
+                || instance.is_u64()
+                || instance
+                    .as_f64()
+                    .is_some_and(|number| number.fract() == 0.0)
+        }
+        "number" => instance.is_number(),
+        "string" => instance.is_string(),
+        "array" => instance.is_array(),
+        "object" => instance.is_object(),
+        // Unknown types are not ours to reject.
+        _ => true,
+    }
+}
+
+pub(crate) fn type_name(instance: &Value) -> &'static str {
+    match instance {
+        Value::Null => "null",
+        Value::Bool(_) => "boolean",
+        Value::Number(number) if number.is_f64() => "number",
+        Value::Number(_) => "integer",
+        Value::String(_) => "string",
+        Value::Array(_) => "array",
+        Value::Object(_) => "object",
+    }
+}
+
+/// Escapes a property name for a JSON pointer (RFC 6901).
+fn escape(name: &str) -> String {
+    name.replace('~', "~0").replace('/', "~1")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn violations(schema: Value, instance: Value) -> Vec<String> {
+        let mut violations = vec![];
+        let patterns = Patterns::compile(&schema);
+        validate(&schema, &patterns, &schema, &instance, &mut violations);
+        violations.iter().map(ToString::to_string).collect()
+    }
+
+    #[test]
+    fn compiles_each_pattern_once() {
+        let patterns = Patterns::compile(&json!({
+            "properties": {
+                "pattern": {"type": "string", "pattern": "^a"},
+                "code": {"pattern": "^a"},
+                "broken": {"pattern": "("},
+            }
+        }));
+        let mut compiled: Vec<&str> = patterns.0.keys().map(String::as_str).collect();
+        compiled.sort();
+        assert_eq!(compiled, ["^a"]);
+    }
+
+    #[test]
+    fn checks_strings_against_their_pattern() {
+        let schema = json!({"type": "string", "pattern": "^[a-z]+$"});
+        assert!(violations(schema.clone(), json!("abc")).is_empty());
+        assert_eq!(
+            violations(schema, json!("ABC")),
+            ["#: does not match the pattern '^[a-z]+$'"]
+        );
+        assert!(violations(json!({"pattern": "("}), json!("anything")).is_empty());
+    }
+
+    #[test]
+    fn reports_nested_values_under_their_pointer() {
+        let schema = json!({
+            "type": "object",
+            "properties": {
+                "items": {
+                    "type": "array",
+                    "items": {"$ref": "#/definitions/Item"},
+                    "maxItems": 2,
+                },
+            },
+            "additionalProperties": {"type": "integer"},
+            "definitions": {
+                "Item": {
+                    "type": "object",
+                    "required": ["name"],
+                    "properties": {"name": {"type": "string", "minLength": 2}},
+                },
+            },
+        });
+        let instance = json!({
+            "items": [{"name": "ok"}, {"name": "x"}, {}],
+            "~count": "3",
+        });
+        assert_eq!(
+            violations(schema, instance),
+            [
+                "#/items: more than 2 items",
+                "#/items/1/name: shorter than 2 characters",
+                "#/items/2: missing required property 'name'",
+                "#/~0count: expected integer, found string",
+            ]
+        );
+    }
+
+    #[test]
+    fn applies_nullable_enums_and_combinators() {
+        let nullable = json!({"type": "string", "nullable": true, "enum": ["a", "b"]});
+        assert!(violations(nullable.clone(), json!(null)).is_empty());
+        assert_eq!(
+            violations(nullable, json!("c")),
+            [r#"#: "c" is not one of "a", "b""#]
+        );
+
+        let one_of = json!({"oneOf": [{"type": "integer"}, {"type": "number"}]});
+        assert!(violations(one_of.clone(), json!(1.5)).is_empty());
+        assert_eq!(
+            violations(one_of, json!(1)),
+            ["#: matches 2 of the oneOf schemas instead of exactly one"]
+        );
+        let any_of = json!({"anyOf": [{"type": "string"}, {"minimum": 10}]});
+        assert_eq!(
+            violations(any_of, json!(5)),
+            ["#: does not match any of the anyOf schemas"]
+        );
+        assert_eq!(
+            violations(json!({"not": {"type": "null"}}), json!(null)),
+            ["#: matches a schema it must not match"]
+        );
+        assert_eq!(
+            violations(json!({"properties": {"a": false}}), json!({"a": 1})),
+            ["#/a: no value is allowed here"]
+        );
+    }
+}
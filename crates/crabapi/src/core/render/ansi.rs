@@ -0,0 +1,108 @@
+//! Terminal colors for the syntaxes we pretty print: JSON keys, strings, numbers and
+//! literals, and markup tags and comments.
+
+use super::BodyKind;
+use super::markup::{RAW_ELEMENTS, SPECIAL, tag_end, tag_name};
+
+const RESET: &str = "\x1b[0m";
+const KEY: &str = "\x1b[34m";
+const STRING: &str = "\x1b[32m";
+const NUMBER: &str = "\x1b[33m";
+const LITERAL: &str = "\x1b[35m";
+const TAG: &str = "\x1b[36m";
+const COMMENT: &str = "\x1b[90m";
+
+/// `text` with ANSI colors for its kind; kinds we do not color are returned unchanged.
+pub fn highlight(kind: BodyKind, text: &str) -> String {
+    match kind {
+        BodyKind::Json => json(text),
+        BodyKind::Xml | BodyKind::Html => markup(text),
+        _ => text.to_string(),
+    }
+}
+
+fn json(text: &str) -> String {
+    let mut output = String::with_capacity(text.len());
+    let mut rest = text;
+    while let Some(c) = rest.chars().next() {
+        let length = match c {
+            '"' => string_length(rest),
+            '-' | '0'..='9' => rest
+                .find(|c: char| !matches!(c, '-' | '+' | '.' | 'e' | 'E' | '0'..='9'))
+                .unwrap_or(rest.len()),
+            't' | 'f' | 'n' => rest
+                .find(|c: char| !c.is_ascii_alphabetic())
+                .unwrap_or(rest.len()),
+            _ => c.len_utf8(),
+        };
+        let (token, after) = rest.split_at(length);
+        let color = match c {
+            '"' if after.trim_start().starts_with(':') => Some(KEY),
+            '"' => Some(STRING),
+            '-' | '0'..='9' => Some(NUMBER),
+            't' | 'f' | 'n' => Some(LITERAL),
+            _ => None,
+        };
+        match color {
+            Some(color) => {
+                output.push_str(color);
+                output.push_str(token);
+                output.push_str(RESET);
+            }
+            None => output.push_str(token),
+        }
+        rest = after;
+    }
+    output
+}
+
+/// Length of the string literal `text` starts with, closing quote included.
+fn string_length(text: &str) -> usize {
+    let mut escaped = false;
+    for (index, c) in text.char_indices().skip(1) {
+        match (escaped, c) {
+            (true, _) => escaped = false,
+            (false, '\\') => escaped = true,
+            (false, '"') => return index + 1,
+            _ => {}
+        }
+    }
+    text.len()
+}
+
+fn markup(text: &str) -> String {
+    let mut output = String::with_capacity(text.len());
+    let mut rest = text;
+    while let Some(start) = rest.find('<') {
+        output.push_str(&rest[..start]);
+        rest = &rest[start..];
+        let special = SPECIAL.iter().find(|(start, _)| rest.starts_with(start));
+        let length = match special {
+            Some((_, end)) => rest.find(end).map(|index| index + end.len()),
+            None => tag_end(rest).map(|index| index + 1),
+        }
+        .unwrap_or(rest.len());
+        let color = match special {
+            Some(("<!--", _)) => COMMENT,
+            _ => TAG,
+        };
+        let (tag, after) = rest.split_at(length);
+        output.push_str(color);
+        output.push_str(tag);
+        output.push_str(RESET);
+        rest = after;
+
+        // The content of raw text elements is not markup.
+        let name = tag_name(tag);
+        if special.is_none() && !tag.starts_with("</") && RAW_ELEMENTS.contains(&name.as_str()) {
+            let end = rest
+                .to_ascii_lowercase()
+                .find(&format!("</{name}"))
+                .unwrap_or(rest.len());
+            output.push_str(&rest[..end]);
+            rest = &rest[end..];
+        }
+    }
+    output.push_str(rest);
+    output
+}
@@ -0,0 +1,136 @@
+//! Re-indents XML and HTML, one element per line. Text-only elements stay on one line, and
+//! comments, CDATA and the content of HTML's raw text elements are copied as they are.
+
+/// HTML elements that have no content nor closing tag.
+const VOID_ELEMENTS: [&str; 14] = [
+    "area", "base", "br", "col", "embed", "hr", "img", "input", "link", "meta", "param", "source",
+    "track", "wbr",
+];
+
+/// HTML elements whose content is not markup, or where whitespace matters.
+pub(super) const RAW_ELEMENTS: [&str; 4] = ["script", "style", "pre", "textarea"];
+
+/// Constructs that end with a fixed terminator rather than the next `>`.
+pub(super) const SPECIAL: [(&str, &str); 3] = [("<!--", "-->"), ("<![CDATA[", "]]>"), ("<?", "?>")];
+
+enum Token<'a> {
+    Open(&'a str),
+    Close(&'a str),
+    /// Printed on its own line as is: self-closing and void tags, comments, declarations
+    /// and raw text elements.
+    Single(&'a str),
+    Text(&'a str),
+}
+
+/// `source` re-indented, or `None` when a tag, comment or raw element is not terminated.
+pub(super) fn indent(source: &str, html: bool) -> Option<String> {
+    let tokens = tokenize(source, html)?;
+    let mut output = String::new();
+    let mut depth = 0;
+    let mut index = 0;
+    while index < tokens.len() {
+        match (&tokens[index], tokens.get(index + 1), tokens.get(index + 2)) {
+            (Token::Open(open), Some(Token::Text(text)), Some(Token::Close(close))) => {
+                line(&mut output, depth, &format!("{open}{}{close}", text.trim()));
+                index += 2;
+            }
+            (Token::Open(open), Some(Token::Close(close)), _) => {
+                line(&mut output, depth, &format!("{open}{close}"));
+                index += 1;
+            }
+            (Token::Open(open), _, _) => {
+                line(&mut output, depth, open);
+                depth += 1;
+            }
+            (Token::Close(close), _, _) => {
+                depth = depth.saturating_sub(1);
+                line(&mut output, depth, close);
+            }
+            (Token::Single(single), _, _) => line(&mut output, depth, single),
+            (Token::Text(text), _, _) => line(&mut output, depth, text.trim()),
+        }
+        index += 1;
+    }
+    Some(output)
+}
+
+fn line(output: &mut String, depth: usize, content: &str) {
+    for _ in 0..depth {
+        output.push_str("  ");
+    }
+    output.push_str(content);
+    output.push('\n');
+}
+
+fn tokenize(source: &str, html: bool) -> Option<Vec<Token<'_>>> {
+    let mut tokens = vec![];
+    let mut position = 0;
+    'tokens: while position < source.len() {
+        let rest = &source[position..];
+        if !rest.starts_with('<') {
+            let end = rest.find('<').unwrap_or(rest.len());
+            if !rest[..end].trim().is_empty() {
+                tokens.push(Token::Text(&rest[..end]));
+            }
+            position += end;
+            continue;
+        }
+
+        for (start, end) in SPECIAL {
+            if rest.starts_with(start) {
+                let length = rest.find(end)? + end.len();
+                tokens.push(Token::Single(&rest[..length]));
+                position += length;
+                continue 'tokens;
+            }
+        }
+
+        let length = tag_end(rest)? + 1;
+        let tag = &rest[..length];
+        let name = tag_name(tag);
+        if tag.starts_with("</") {
+            tokens.push(Token::Close(tag));
+        } else if tag.starts_with("<!")
+            || tag.ends_with("/>")
+            || (html && VOID_ELEMENTS.contains(&name.as_str()))
+        {
+            tokens.push(Token::Single(tag));
+        } else if html && RAW_ELEMENTS.contains(&name.as_str()) {
+            // Lowercasing ASCII keeps byte offsets.
+            let content = rest[length..].to_ascii_lowercase();
+            let close = content.find(&format!("</{name}"))?;
+            let close_length = tag_end(&rest[length + close..])? + 1;
+            let element = &rest[..length + close + close_length];
+            tokens.push(Token::Single(element));
+            position += element.len();
+            continue;
+        } else {
+            tokens.push(Token::Open(tag));
+        }
+        position += length;
+    }
+    Some(tokens)
+}
+
+/// Index of the `>` that ends the tag `source` starts with, skipping quoted attribute
+/// values.
+pub(super) fn tag_end(source: &str) -> Option<usize> {
+    let mut quote = None;
+    for (index, c) in source.char_indices() {
+        match (quote, c) {
+            (None, '"' | '\'') => quote = Some(c),
+            (Some(open), _) if c == open => quote = None,
+            (None, '>') => return Some(index),
+            _ => {}
+        }
+    }
+    None
+}
+
+pub(super) fn tag_name(tag: &str) -> String {
+    tag.trim_start_matches(['<', '/'])
+        .split(|c: char| c.is_whitespace() || c == '>' || c == '/')
+        .next()
+        .unwrap_or_default()
+        .to_ascii_lowercase()
+}
@@ -0,0 +1,184 @@
+//! Picks how a response body is shown from its `Content-Type`: pretty printed and
+//! highlighted text, an image preview or a hex dump.
+
+mod ansi;
+mod markup;
+
+use crate::core::requests::ResponseRecord;
+use serde::de::IgnoredAny;
+use std::fmt::Write;
+
+pub use ansi::highlight;
+
+/// Bytes shown by [`hex_dump`]; the rest is summarized in a last line.
+pub const HEX_DUMP_LIMIT: usize = 64 * 1024;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BodyKind {
+    Json,
+    Xml,
+    Html,
+    Yaml,
+    JavaScript,
+    Image,
+    Text,
+    Binary,
+}
+
+impl BodyKind {
+    /// The kind named by the response's `Content-Type`. Without one, or with a type we do
+    /// not know, bodies that decoded to text without control characters are text.
+    pub fn of(response: &ResponseRecord) -> Self {
+        let mime = response
+            .content_type()
+            .and_then(|content_type| content_type.split(';').next())
+            .map(|mime| mime.trim().to_ascii_lowercase())
+            .unwrap_or_default();
+        Self::from_mime(&mime).unwrap_or(match &response.text {
+            Some(text) if !text.chars().any(|c| c.is_control() && !c.is_whitespace()) => {
+                BodyKind::Text
+            }
+            _ => BodyKind::Binary,
+        })
+    }
+
+    fn from_mime(mime: &str) -> Option<Self> {
+        let (kind, subtype) = mime.split_once('/')?;
+        // Structured syntax suffixes, e.g. `application/problem+json` or `image/svg+xml`.
+        let suffix = subtype.rsplit('+').next().unwrap_or(subtype);
+        Some(match (kind, subtype, suffix) {
+            (_, "html" | "xhtml+xml", _) => BodyKind::Html,
+            (_, _, "json") => BodyKind::Json,
+            (_, _, "xml") => BodyKind::Xml,
+            (_, _, "yaml" | "x-yaml") => BodyKind::Yaml,
+            (_, "javascript" | "x-javascript" | "ecmascript", _) => BodyKind::JavaScript,
+            ("image", _, _) => BodyKind::Image,
+            ("text", _, _) => BodyKind::Text,
+            _ => return None,
+        })
+    }
+
+    /// Name of the syntax the highlighters know the kind by, if it has one.
+    pub fn syntax(self) -> Option<&'static str> {
+        match self {
+            BodyKind::Json => Some("json"),
+            BodyKind::Xml => Some("xml"),
+            BodyKind::Html => Some("html"),
+            BodyKind::Yaml => Some("yaml"),
+            BodyKind::JavaScript => Some("js"),
+            BodyKind::Image | BodyKind::Text | BodyKind::Binary => None,
+        }
+    }
+}
+
+/// The body as text: pretty printed when asked and possible, and a hex dump when it is not
+/// text.
+pub fn body_text(response: &ResponseRecord, kind: BodyKind, pretty: bool) -> String {
+    let text = match (kind, &response.text) {
+        (BodyKind::Image | BodyKind::Binary, _) | (_, None) => return hex_dump(&response.body),
+        (_, Some(text)) => text,
+    };
+    match pretty {
+        true => self::pretty(kind, text).unwrap_or_else(|| text.clone()),
+        false => text.clone(),
+    }
+}
+
+/// `text` pretty printed, or `None` when the kind has no pretty form or `text` does not
+/// parse. YAML and JavaScript are left as written.
+pub fn pretty(kind: BodyKind, text: &str) -> Option<String> {
+    match kind {
+        BodyKind::Json => indent_json(text),
+        BodyKind::Xml => markup::indent(text, false),
+        BodyKind::Html => markup::indent(text, true),
+        _ => None,
+    }
+}
+
+/// Re-indents valid JSON token by token, so numbers and escapes stay as they were sent.
+fn indent_json(text: &str) -> Option<String> {
+    serde_json::from_str::<IgnoredAny>(text).ok()?;
+
+    let mut output = String::with_capacity(text.len() * 2);
+    let mut depth = 0;
+    let mut chars = text.chars().peekable();
+    let newline = |output: &mut String, depth: usize| {
+        output.push('\n');
+        for _ in 0..depth {
+            output.push_str("  ");
+        }
+    };
+    while let Some(c) = chars.next() {
+        match c {
+            '"' => {
+                output.push(c);
+                let mut escaped = false;
+                for c in chars.by_ref() {
+                    output.push(c);
+                    match (escaped, c) {
+                        (true, _) => escaped = false,
+                        (false, '\\') => escaped = true,
+                        (false, '"') => break,
+                        _ => {}
+                    }
+                }
+            }
+            '{' | '[' => {
+                output.push(c);
+                while chars.next_if(|c| c.is_whitespace()).is_some() {}
+                if let Some(close) = chars.next_if(|&next| matches!(next, '}' | ']')) {
+                    output.push(close);
+                } else {
+                    depth += 1;
+                    newline(&mut output, depth);
+                }
+            }
+            '}' | ']' => {
+                depth -= 1;
+                newline(&mut output, depth);
+                output.push(c);
+            }
+            ',' => {
+                output.push(c);
+                newline(&mut output, depth);
+            }
+            ':' => output.push_str(": "),
+            c if c.is_whitespace() => {}
+            c => output.push(c),
+        }
+    }
+    Some(output)
+}
+
+/// `xxd`-like dump of the first [`HEX_DUMP_LIMIT`] bytes: offset, 16 bytes in hex and
+/// their printable ASCII.
+pub fn hex_dump(bytes: &[u8]) -> String {
+    let mut dump = String::new();
+    for (line, chunk) in bytes[..bytes.len().min(HEX_DUMP_LIMIT)]
+        .chunks(16)
+        .enumerate()
+    {
+        let _ = write!(dump, "{:08x}  ", line * 16);
+        for index in 0..16 {
+            match chunk.get(index) {
+                Some(byte) => {
+                    let _ = write!(dump, "{byte:02x} ");
+                }
+                None => dump.push_str("   "),
+            }
+            if index == 7 {
+                dump.push(' ');
+            }
+        }
+        dump.push(' ');
+        dump.extend(chunk.iter().map(|&byte| match byte {
+            b' ' | b'!'..=b'~' => byte as char,
+            _ => '.',
+        }));
+        dump.push('\n');
+    }
+    if bytes.len() > HEX_DUMP_LIMIT {
+        let _ = writeln!(dump, "... {} more bytes", bytes.len() - HEX_DUMP_LIMIT);
+    }
+    dump
+}
@@ -7,6 +7,12 @@ pub const USER_AGENT: &str = formatcp!(
     crate::core::app::constants::APP_VERSION
 );
 
+/// Encodings `ResponseRecord` can decode, sent when a request asks for compression.
+pub const ACCEPT_ENCODING: &str = "gzip, deflate, br";
+
+/// Sent with form bodies unless the request sets its own `Content-Type`.
+pub const FORM_CONTENT_TYPE: &str = "application/x-www-form-urlencoded";
+
 pub const METHODS: [Method; 9] = [
     Method::GET,
     Method::POST,
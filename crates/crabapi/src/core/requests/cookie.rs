@@ -0,0 +1,56 @@
+/// A cookie set by a `Set-Cookie` response header.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SetCookie {
+    pub name: String,
+    pub value: String,
+    pub domain: Option<String>,
+    pub path: Option<String>,
+    /// `Expires` date as sent, if any.
+    pub expires: Option<String>,
+    pub max_age: Option<i64>,
+    pub secure: bool,
+    pub http_only: bool,
+    pub same_site: Option<String>,
+}
+
+impl SetCookie {
+    /// Parses a `Set-Cookie` value as RFC 6265 section 5.2 does: unknown attributes are
+    /// ignored, and a value without `=` in its first pair sets no cookie.
+    pub fn parse(header: &str) -> Option<Self> {
+        let mut pairs = header.split(';');
+        let (name, value) = pairs.next()?.split_once('=')?;
+        let name = name.trim();
+        if name.is_empty() {
+            return None;
+        }
+
+        let mut cookie = SetCookie {
+            name: name.to_string(),
+            value: value.trim().trim_matches('"').to_string(),
+            domain: None,
+            path: None,
+            expires: None,
+            max_age: None,
+            secure: false,
+            http_only: false,
+            same_site: None,
+        };
+        for attribute in pairs {
+            let (key, value) = match attribute.split_once('=') {
+                Some((key, value)) => (key.trim(), value.trim()),
+                None => (attribute.trim(), ""),
+            };
+            match key.to_ascii_lowercase().as_str() {
+                "domain" => cookie.domain = Some(value.trim_start_matches('.').to_string()),
+                "path" => cookie.path = Some(value.to_string()),
+                "expires" => cookie.expires = Some(value.to_string()),
+                "max-age" => cookie.max_age = value.parse().ok(),
+                "secure" => cookie.secure = true,
+                "httponly" => cookie.http_only = true,
+                "samesite" => cookie.same_site = Some(value.to_string()),
+                _ => {}
+            }
+        }
+        Some(cookie)
+    }
+}
@@ -0,0 +1,183 @@
+use std::error::Error as StdError;
+use std::fmt;
+use std::path::PathBuf;
+
+/// Errors raised while building, sending or receiving a request.
+#[derive(Debug)]
+pub enum Error {
+    InvalidUrl {
+        url: String,
+        source: url::ParseError,
+    },
+    InvalidHeaderName {
+        name: String,
+    },
+    InvalidHeaderValue {
+        name: String,
+    },
+    InvalidHeader {
+        header: String,
+        reason: &'static str,
+    },
+    InvalidQuery {
+        query: String,
+        reason: &'static str,
+    },
+    InvalidFormField {
+        field: String,
+        reason: &'static str,
+    },
+    BodyLoad {
+        path: PathBuf,
+        source: std::io::Error,
+    },
+    InvalidPartContentType {
+        part: String,
+        content_type: String,
+    },
+    UnresolvedVariables(Vec<String>),
+    OAuth2 {
+        token_url: String,
+        reason: String,
+    },
+    Transport(reqwest::Error),
+    Join(tokio::task::JoinError),
+}
+
+/// Coarse classification of a [`Error::Transport`] failure.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TransportErrorKind {
+    Dns,
+    Connect,
+    Tls,
+    Timeout,
+    Redirect,
+    Other,
+}
+
+impl Error {
+    pub fn transport_kind(&self) -> Option<TransportErrorKind> {
+        let Error::Transport(error) = self else {
+            return None;
+        };
+
+        let kind = if error.is_timeout() {
+            TransportErrorKind::Timeout
+        } else if error.is_redirect() {
+            TransportErrorKind::Redirect
+        } else if error.is_connect() {
+            // reqwest only exposes the underlying cause as text, so look through the chain.
+            let causes = source_chain(error).to_lowercase();
+            if causes.contains("dns error") || causes.contains("failed to lookup address") {
+                TransportErrorKind::Dns
+            } else if ["tls", "ssl", "certificate", "handshake"]
+                .iter()
+                .any(|needle| causes.contains(needle))
+            {
+                TransportErrorKind::Tls
+            } else {
+                TransportErrorKind::Connect
+            }
+        } else {
+            TransportErrorKind::Other
+        };
+
+        Some(kind)
+    }
+}
+
+fn source_chain(error: &dyn StdError) -> String {
+    let mut causes = vec![];
+    let mut source = error.source();
+    while let Some(cause) = source {
+        causes.push(cause.to_string());
+        source = cause.source();
+    }
+    causes.join(": ")
+}
+
+fn root_cause(error: &dyn StdError) -> String {
+    let mut cause = error;
+    while let Some(source) = cause.source() {
+        cause = source;
+    }
+    cause.to_string()
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Error::InvalidUrl { url, source } => write!(f, "invalid URL '{url}': {source}"),
+            Error::InvalidHeaderName { name } => write!(f, "invalid header name '{name}'"),
+            Error::InvalidHeaderValue { name } => write!(f, "invalid value for header '{name}'"),
+            Error::InvalidHeader { header, reason } => {
+                write!(f, "invalid header '{header}': {reason}")
+            }
+            Error::InvalidQuery { query, reason } => {
+                write!(f, "invalid query parameter '{query}': {reason}")
+            }
+            Error::InvalidFormField { field, reason } => {
+                write!(f, "invalid form field '{field}': {reason}")
+            }
+            Error::BodyLoad { path, source } => {
+                write!(f, "could not read body file '{}': {source}", path.display())
+            }
+            Error::InvalidPartContentType { part, content_type } => {
+                write!(f, "invalid content type '{content_type}' for part '{part}'")
+            }
+            Error::UnresolvedVariables(names) => {
+                let names: Vec<String> =
+                    names.iter().map(|name| format!("{{{{{name}}}}}")).collect();
+                write!(f, "unresolved variables: {}", names.join(", "))
+            }
+            Error::OAuth2 { token_url, reason } => {
+                write!(
+                    f,
+                    "could not get an OAuth 2.0 token from {token_url}: {reason}"
+                )
+            }
+            Error::Transport(error) => {
+                let url = error.url().map(|url| url.as_str()).unwrap_or("the server");
+                match self.transport_kind() {
+                    Some(TransportErrorKind::Dns) => write!(f, "could not resolve host for {url}"),
+                    Some(TransportErrorKind::Connect) => {
+                        write!(f, "could not connect to {url}: {}", root_cause(error))
+                    }
+                    Some(TransportErrorKind::Tls) => {
+                        write!(f, "TLS error talking to {url}: {}", root_cause(error))
+                    }
+                    Some(TransportErrorKind::Timeout) => write!(f, "request to {url} timed out"),
+                    Some(TransportErrorKind::Redirect) => {
+                        write!(f, "too many redirects from {url}")
+                    }
+                    _ => write!(f, "request to {url} failed: {error}"),
+                }
+            }
+            Error::Join(error) => write!(f, "request task failed: {error}"),
+        }
+    }
+}
+
+impl StdError for Error {
+    fn source(&self) -> Option<&(dyn StdError + 'static)> {
+        match self {
+            Error::InvalidUrl { source, .. } => Some(source),
+            Error::BodyLoad { source, .. } => Some(source),
+            Error::Transport(error) => Some(error),
+            Error::Join(error) => Some(error),
+            _ => None,
+        }
+    }
+}
+
+impl From<reqwest::Error> for Error {
+    fn from(error: reqwest::Error) -> Self {
+        Error::Transport(error)
+    }
+}
+
+impl From<tokio::task::JoinError> for Error {
+    fn from(error: tokio::task::JoinError) -> Self {
+        Error::Join(error)
+    }
+}
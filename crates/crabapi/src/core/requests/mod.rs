@@ -1,59 +1,512 @@
 pub mod constants;
+mod cookie;
+mod error;
+mod response;
+mod session;
+mod spec;
 pub mod validators;
 
-use reqwest::{Body, Client, Error, RequestBuilder, Response};
+use crate::core::auth::{ApiKeyLocation, Auth, DigestChallenge};
+use crate::core::environments::Variables;
+use crate::core::history::Outcome;
+use crate::core::jsonpath::JsonPath;
+use crate::core::render::{self, BodyKind};
+use reqwest::{Body, Client, RequestBuilder, multipart};
+use std::path::Path;
+use std::time::Instant;
 use tokio::task::JoinHandle;
+use tokio_util::io::ReaderStream;
 
+pub use cookie::SetCookie;
+pub use error::{Error, TransportErrorKind};
 pub use http::{HeaderMap, Method};
 pub use reqwest::Url;
+pub use response::ResponseRecord;
+pub use session::Session;
+pub use spec::{BodySpec, KeyValue, MultipartPart, PartValue, RequestOptions, RequestSpec};
 
-// TODO: Implement params too
+use http::header::{
+    ACCEPT_ENCODING, AUTHORIZATION, CONTENT_LENGTH, CONTENT_TYPE, WWW_AUTHENTICATE,
+};
+use http::{HeaderName, HeaderValue, StatusCode};
+
+/// Builds a request from `spec` after expanding its `{{name}}` placeholders from `variables`.
 pub fn build_request(
     client: &Client,
-    url: Url,
-    query: Vec<(String, String)>,
-    method: Method,
-    headers: HeaderMap,
-    body: Body,
-) -> RequestBuilder {
+    spec: &RequestSpec,
+    variables: &Variables,
+) -> Result<RequestBuilder, Error> {
+    build_resolved(client, &spec.resolve(variables)?)
+}
+
+fn build_resolved(client: &Client, spec: &RequestSpec) -> Result<RequestBuilder, Error> {
+    let url = Url::parse(&spec.url).map_err(|source| Error::InvalidUrl {
+        url: spec.url.clone(),
+        source,
+    })?;
+
     let mut default_headers = HeaderMap::new();
     default_headers.insert("User-Agent", constants::USER_AGENT.parse().unwrap());
+    if spec.options.compressed {
+        default_headers.insert(
+            ACCEPT_ENCODING,
+            HeaderValue::from_static(constants::ACCEPT_ENCODING),
+        );
+    }
+    // Replaced by a `Content-Type` the request sets itself.
+    if let BodySpec::Form { .. } = spec.body {
+        default_headers.insert(
+            CONTENT_TYPE,
+            HeaderValue::from_static(constants::FORM_CONTENT_TYPE),
+        );
+    }
+
+    let mut headers = HeaderMap::new();
+    for header in spec.enabled_headers() {
+        let name = header.key.trim();
+        headers.append(
+            HeaderName::from_bytes(name.as_bytes()).map_err(|_| Error::InvalidHeaderName {
+                name: name.to_string(),
+            })?,
+            HeaderValue::from_str(header.value.trim()).map_err(|_| Error::InvalidHeaderValue {
+                name: name.to_string(),
+            })?,
+        );
+    }
 
-    let request = reqwest::Request::new(method, url);
-    RequestBuilder::from_parts(client.clone(), request)
+    let mut query = vec![];
+    for pair in spec.enabled_query() {
+        if pair.key.is_empty() {
+            return Err(Error::InvalidQuery {
+                query: format!("={}", pair.value),
+                reason: "missing key",
+            });
+        }
+        query.push((pair.key.as_str(), pair.value.as_str()));
+    }
+
+    let request = reqwest::Request::new(spec.method.clone(), url);
+    let request = RequestBuilder::from_parts(client.clone(), request)
         .query(&query)
         .headers(default_headers)
-        .headers(headers.clone())
-        .body(body)
+        .headers(headers);
+
+    if let Auth::ApiKey {
+        key,
+        value,
+        location: ApiKeyLocation::Header,
+    } = &spec.auth
+    {
+        HeaderName::from_bytes(key.as_bytes())
+            .map_err(|_| Error::InvalidHeaderName { name: key.clone() })?;
+        HeaderValue::from_str(value)
+            .map_err(|_| Error::InvalidHeaderValue { name: key.clone() })?;
+    }
+    let mut request = spec.auth.apply(request);
+
+    if let Some(timeout) = spec.options.timeout() {
+        request = request.timeout(timeout);
+    }
+
+    let request = match &spec.body {
+        BodySpec::Empty => request,
+        BodySpec::Text { text } => request.body(text.clone()),
+        BodySpec::File { path } => {
+            let (body, length) = file_body(path)?;
+            request.header(CONTENT_LENGTH, length).body(body)
+        }
+        BodySpec::Multipart { parts } => request.multipart(multipart_form(parts)?),
+        BodySpec::Form { fields } => request.body(BodySpec::form_encoded(fields)),
+    };
+
+    Ok(request)
+}
+
+fn multipart_form(parts: &[MultipartPart]) -> Result<multipart::Form, Error> {
+    let mut form = multipart::Form::new();
+    for part in parts {
+        let (mut body, default_filename) = match &part.value {
+            PartValue::Text { text } => (multipart::Part::text(text.clone()), None),
+            PartValue::File { path } => {
+                let (body, length) = file_body(path)?;
+                (
+                    multipart::Part::stream_with_length(body, length),
+                    path.file_name()
+                        .map(|name| name.to_string_lossy().into_owned()),
+                )
+            }
+        };
+        if let Some(filename) = part.filename.clone().or(default_filename) {
+            body = body.file_name(filename);
+        }
+        if let Some(content_type) = &part.content_type {
+            body = body
+                .mime_str(content_type)
+                .map_err(|_| Error::InvalidPartContentType {
+                    part: part.name.clone(),
+                    content_type: content_type.clone(),
+                })?;
+        }
+        form = form.part(part.name.clone(), body);
+    }
+    Ok(form)
 }
 
-pub fn send_requests(requests: Vec<RequestBuilder>) -> Vec<JoinHandle<Result<Response, Error>>> {
+/// Streams the file at `path` from disk as it is sent, along with its length in bytes.
+fn file_body(path: &Path) -> Result<(Body, u64), Error> {
+    let load_error = |source: std::io::Error| Error::BodyLoad {
+        path: path.to_path_buf(),
+        source,
+    };
+    let file = std::fs::File::open(path).map_err(load_error)?;
+    let length = file.metadata().map_err(load_error)?.len();
+    let stream = ReaderStream::new(tokio::fs::File::from_std(file));
+    Ok((Body::wrap_stream(stream), length))
+}
+
+/// Sends every request concurrently, each in its own task, and records them to the
+/// session's history once they complete. Responses are validated against the session's
+/// API spec, when it has one.
+pub fn send_requests(
+    session: &Session,
+    requests: Vec<RequestSpec>,
+) -> Vec<JoinHandle<Result<ResponseRecord, Error>>> {
     let mut handles = vec![];
     for request in requests {
-        handles.push(tokio::spawn(async move { request.send().await }));
+        let session = session.clone();
+        handles.push(tokio::spawn(async move {
+            let started = Instant::now();
+            let mut result = send_request(&session, &request).await;
+            if let (Some(spec), Ok(response)) = (session.spec(), &mut result) {
+                let url = request
+                    .resolve(session.variables())
+                    .ok()
+                    .and_then(|resolved| Url::parse(&resolved.url).ok())
+                    .unwrap_or_else(|| response.url.clone());
+                response.validation = Some(spec.validate(&request.method, &url, response));
+            }
+            if let Some(history) = session.history().cloned() {
+                let environment = session.environment().map(str::to_string);
+                let outcome = Outcome::new(&result);
+                let elapsed = started.elapsed();
+                let recorded = tokio::task::spawn_blocking(move || {
+                    history.record(&request, environment.as_deref(), outcome, elapsed)
+                })
+                .await;
+                if let Ok(Err(error)) = recorded {
+                    eprintln!("Warning: could not record history: {error}");
+                }
+            }
+            result
+        }));
     }
 
     handles
 }
 
-pub async fn print_response(handle: JoinHandle<Result<Response, Error>>) -> Result<(), Error> {
-    let response = handle.await.unwrap()?;
-    let headers = response.headers().clone();
-    let url = response.url().clone();
-    let status = response.status();
-    let version = response.version();
-    let body = response.text().await?;
+/// Resolves, builds and sends a single request. A 401 is retried once when the auth can
+/// answer it: with Digest credentials, or with a fresh OAuth 2.0 token.
+pub async fn send_request(session: &Session, spec: &RequestSpec) -> Result<ResponseRecord, Error> {
+    let spec = spec.resolve(session.variables())?;
+    let (response, started) = send_once(session, &spec, None, false).await?;
+    if response.status() != StatusCode::UNAUTHORIZED {
+        return Ok(ResponseRecord::read(response, started).await?);
+    }
+
+    let retry = match &spec.auth {
+        Auth::Digest { username, password } => {
+            let challenge = response
+                .headers()
+                .get_all(WWW_AUTHENTICATE)
+                .iter()
+                .filter_map(|value| value.to_str().ok())
+                .find_map(DigestChallenge::parse);
 
-    println!("{url} - {status} - {version:#?} - ");
-    for header in headers.iter() {
-        println!("\t{:#?}: {:#?}", header.0, header.1);
+            match challenge {
+                Some(challenge) => {
+                    let url = response.url();
+                    let uri = match url.query() {
+                        Some(query) => format!("{}?{query}", url.path()),
+                        None => url.path().to_string(),
+                    };
+                    let body = digest_body(&spec.body).await;
+                    let authorization = challenge.authorization(
+                        username,
+                        password,
+                        spec.method.as_str(),
+                        &uri,
+                        body.as_deref(),
+                    );
+                    Some(send_once(session, &spec, Some(authorization), false))
+                }
+                None => None,
+            }
+        }
+        Auth::OAuth2(_) => Some(send_once(session, &spec, None, true)),
+        _ => None,
+    };
+
+    let (response, started) = match retry {
+        Some(retry) => retry.await?,
+        None => (response, started),
+    };
+    Ok(ResponseRecord::read(response, started).await?)
+}
+
+/// The body as it is sent, for Digest `qop=auth-int`. `None` when it cannot be known in
+/// advance: multipart boundaries are only chosen when the request is built.
+async fn digest_body(body: &BodySpec) -> Option<Vec<u8>> {
+    match body {
+        BodySpec::Empty => Some(vec![]),
+        BodySpec::Text { text } => Some(text.clone().into_bytes()),
+        BodySpec::Form { fields } => Some(BodySpec::form_encoded(fields).into_bytes()),
+        BodySpec::File { path } => tokio::fs::read(path).await.ok(),
+        BodySpec::Multipart { .. } => None,
+    }
+}
+
+async fn send_once(
+    session: &Session,
+    spec: &RequestSpec,
+    authorization: Option<String>,
+    refresh_token: bool,
+) -> Result<(reqwest::Response, Instant), Error> {
+    let mut request = build_resolved(&session.client_for(&spec.options)?, spec)?;
+    if let Auth::OAuth2(oauth2) = &spec.auth {
+        let token = oauth2
+            .access_token(
+                session.client(),
+                session.tokens(),
+                session.environment(),
+                refresh_token,
+            )
+            .await?;
+        request = request.bearer_auth(token);
+    }
+    if let Some(authorization) = authorization {
+        request = request.header(AUTHORIZATION, authorization);
+    }
+
+    let started = Instant::now();
+    Ok((request.send().await?, started))
+}
+
+/// Waits for a task started by [`send_requests`], flattening task failures into [`Error`].
+pub async fn wait_response(
+    handle: JoinHandle<Result<ResponseRecord, Error>>,
+) -> Result<ResponseRecord, Error> {
+    handle.await?
+}
+
+/// Prints the status line, headers and body. A `terminal` gets the whole body, pretty printed
+/// and colored for its content type; otherwise the body is truncated. With a `filter`, only
+/// the JSON values it selects are printed, one after the other.
+pub fn print_response(response: &ResponseRecord, terminal: bool, filter: Option<&JsonPath>) {
+    println!(
+        "{} - {} - {:?} - {} ms - {} bytes",
+        response.url,
+        response.status,
+        response.version,
+        response.elapsed.as_millis(),
+        response.size()
+    );
+    for (name, value) in response.header_pairs() {
+        println!("\t{name}: {value}");
     }
     println!();
-    if body.len() > 80 {
-        println!("Body:\n{}\n...[truncated]", &body[0..79]);
+    if let Some(validation) = &response.validation {
+        println!("{validation}\n");
+    }
+
+    if let Some(filter) = filter {
+        println!("Body ({filter}):");
+        let json = match serde_json::from_slice::<serde_json::Value>(&response.body) {
+            Ok(json) => json,
+            Err(error) => {
+                println!("not JSON: {error}");
+                return;
+            }
+        };
+        for value in filter.select(&json) {
+            let value = serde_json::to_string_pretty(value).unwrap_or_default();
+            match terminal {
+                true => println!("{}", render::highlight(BodyKind::Json, &value)),
+                false => println!("{value}"),
+            }
+        }
+        return;
+    }
+
+    if terminal {
+        let kind = BodyKind::of(response);
+        let body = match kind {
+            BodyKind::Image => format!(
+                "[{} image, {} bytes]",
+                response.content_type().unwrap_or_default(),
+                response.size()
+            ),
+            _ => render::highlight(kind, &render::body_text(response, kind, true)),
+        };
+        println!("Body:\n{}", body.trim_end());
+        return;
+    }
+
+    let body = response.text_lossy();
+    if body.chars().count() > 80 {
+        let truncated: String = body.chars().take(79).collect();
+        println!("Body:\n{}\n...[truncated]", truncated);
     } else {
         println!("Body:\n{}", body);
     }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::test_server::{StubRequest, StubServer};
+
+    #[tokio::test]
+    async fn digest_body_is_the_body_as_sent() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("body.json");
+        std::fs::write(&path, "{\"id\": 1}").unwrap();
+        let form = BodySpec::Form {
+            fields: vec![KeyValue::new("a b", "c&d")],
+        };
 
-    Ok(())
+        assert_eq!(digest_body(&BodySpec::Empty).await, Some(vec![]));
+        assert_eq!(
+            digest_body(&BodySpec::Text {
+                text: "hello".to_string()
+            })
+            .await,
+            Some(b"hello".to_vec())
+        );
+        assert_eq!(digest_body(&form).await, Some(b"a+b=c%26d".to_vec()));
+        assert_eq!(
+            digest_body(&BodySpec::File { path }).await,
+            Some(b"{\"id\": 1}".to_vec())
+        );
+        assert_eq!(
+            digest_body(&BodySpec::Multipart {
+                parts: vec![MultipartPart::text("a", "b")]
+            })
+            .await,
+            None
+        );
+    }
+
+    #[test]
+    fn form_content_type_defaults_until_the_request_sets_one() {
+        let mut spec = RequestSpec::new(Method::POST, "http://localhost/login");
+        spec.body = BodySpec::Form {
+            fields: vec![KeyValue::new("user", "{{name}}")],
+        };
+        let mut variables = Variables::new();
+        variables.insert("name", "ada lovelace");
+        let build = |spec: &RequestSpec| {
+            build_request(&Client::new(), spec, &variables)
+                .unwrap()
+                .build()
+                .unwrap()
+        };
+
+        let request = build(&spec);
+        assert_eq!(
+            request.headers()[CONTENT_TYPE],
+            constants::FORM_CONTENT_TYPE
+        );
+        let body = request.body().and_then(|body| body.as_bytes());
+        assert_eq!(body, Some(&b"user=ada+lovelace"[..]));
+
+        let spec = spec.with_header(
+            "content-type",
+            "application/x-www-form-urlencoded; charset=utf-8",
+        );
+        let request = build(&spec);
+        let content_types: Vec<_> = request.headers().get_all(CONTENT_TYPE).iter().collect();
+        assert_eq!(
+            content_types,
+            ["application/x-www-form-urlencoded; charset=utf-8"]
+        );
+    }
+
+    #[tokio::test]
+    async fn streams_file_bodies_with_their_length() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("upload.txt");
+        let content = "line\n".repeat(10_000);
+        std::fs::write(&path, &content).unwrap();
+        let server = StubServer::start(|_: &StubRequest| (200, "{}".to_string())).await;
+
+        let spec = RequestSpec::new(Method::PUT, server.url.clone())
+            .with_body(BodySpec::File { path: path.clone() });
+        send_request(&Session::default(), &spec).await.unwrap();
+
+        let received = &server.requests()[0];
+        assert_eq!(received.header("content-length"), Some("50000"));
+        assert_eq!(received.header("transfer-encoding"), None);
+        assert_eq!(received.body, content);
+
+        let spec = spec.with_body(BodySpec::File {
+            path: dir.path().join("missing.txt"),
+        });
+        let error = send_request(&Session::default(), &spec).await.unwrap_err();
+        assert!(matches!(error, Error::BodyLoad { .. }), "{error:?}");
+    }
+
+    #[tokio::test]
+    async fn sends_multipart_parts_in_order() {
+        let dir = tempfile::tempdir().unwrap();
+        let report = dir.path().join("report.csv");
+        std::fs::write(&report, "a,b\n1,2\n").unwrap();
+        let avatar = dir.path().join("avatar.bin");
+        std::fs::write(&avatar, "PNG").unwrap();
+        let server = StubServer::start(|_: &StubRequest| (200, "{}".to_string())).await;
+
+        let mut spec = RequestSpec::new(Method::POST, server.url.clone());
+        spec.body = BodySpec::Multipart {
+            parts: vec![
+                MultipartPart::text("tag", "a"),
+                MultipartPart::file("upload", &report),
+                MultipartPart::text("tag", "b"),
+                MultipartPart {
+                    filename: Some("me.png".to_string()),
+                    content_type: Some("image/png".to_string()),
+                    ..MultipartPart::file("avatar", &avatar)
+                },
+                MultipartPart {
+                    content_type: Some("application/json".to_string()),
+                    ..MultipartPart::text("meta", "{}")
+                },
+            ],
+        };
+        let response = send_request(&Session::default(), &spec).await.unwrap();
+        assert_eq!(response.status, StatusCode::OK);
+
+        let received = &server.requests()[0];
+        let content_type = received.header("content-type").unwrap();
+        let boundary = content_type
+            .strip_prefix("multipart/form-data; boundary=")
+            .unwrap();
+        let parts: Vec<&str> = received
+            .body
+            .split(&format!("--{boundary}"))
+            .map(|part| part.trim_start_matches("\r\n"))
+            .collect();
+        assert_eq!(
+            parts,
+            [
+                "",
+                "Content-Disposition: form-data; name=\"tag\"\r\n\r\na\r\n",
+                "Content-Disposition: form-data; name=\"upload\"; filename=\"report.csv\"\r\n\r\na,b\n1,2\n\r\n",
+                "Content-Disposition: form-data; name=\"tag\"\r\n\r\nb\r\n",
+                "Content-Disposition: form-data; name=\"avatar\"; filename=\"me.png\"\r\n\
+                 Content-Type: image/png\r\n\r\nPNG\r\n",
+                "Content-Disposition: form-data; name=\"meta\"\r\n\
+                 Content-Type: application/json\r\n\r\n{}\r\n",
+                "--\r\n",
+            ]
+        );
+    }
 }
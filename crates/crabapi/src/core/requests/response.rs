@@ -0,0 +1,190 @@
+use super::SetCookie;
+use crate::core::openapi::Validation;
+use bytes::Bytes;
+use http::header::{CONTENT_ENCODING, CONTENT_TYPE, SET_COOKIE};
+use http::{HeaderMap, StatusCode, Version};
+use reqwest::{Response, Url};
+use std::io::Read;
+use std::time::{Duration, Instant};
+
+/// Everything we keep from a response once its body has been read.
+#[derive(Debug, Clone)]
+pub struct ResponseRecord {
+    pub status: StatusCode,
+    pub version: Version,
+    /// Final URL, after redirects.
+    pub url: Url,
+    pub headers: HeaderMap,
+    /// Body as received, decompressed when `Content-Encoding` is gzip, deflate or br.
+    pub body: Bytes,
+    /// Size in bytes of the body as it was sent, before decompression.
+    pub received_size: usize,
+    /// Body decoded with the charset from `Content-Type` (UTF-8 by default), if it is text.
+    pub text: Option<String>,
+    /// Time from sending the request until the status line and headers were received.
+    pub waiting: Duration,
+    /// Time from sending the request until the whole body was received.
+    pub elapsed: Duration,
+    /// Result of checking the response against the session's API spec, when it has one.
+    pub validation: Option<Validation>,
+}
+
+impl ResponseRecord {
+    pub async fn read(response: Response, started: Instant) -> Result<Self, reqwest::Error> {
+        let status = response.status();
+        let version = response.version();
+        let url = response.url().clone();
+        let headers = response.headers().clone();
+        let waiting = started.elapsed();
+        let body = response.bytes().await?;
+        let elapsed = started.elapsed();
+        let received_size = body.len();
+        let body = decode_content(body, &headers);
+        let text = decode_text(
+            &body,
+            headers.get(CONTENT_TYPE).and_then(|v| v.to_str().ok()),
+        );
+
+        Ok(Self {
+            status,
+            version,
+            url,
+            headers,
+            body,
+            received_size,
+            text,
+            waiting,
+            elapsed,
+            validation: None,
+        })
+    }
+
+    pub fn size(&self) -> usize {
+        self.body.len()
+    }
+
+    /// Cookies set with `Set-Cookie`, in received order.
+    pub fn cookies(&self) -> Vec<SetCookie> {
+        self.headers
+            .get_all(SET_COOKIE)
+            .iter()
+            .filter_map(|value| value.to_str().ok())
+            .filter_map(SetCookie::parse)
+            .collect()
+    }
+
+    pub fn content_type(&self) -> Option<&str> {
+        self.headers
+            .get(CONTENT_TYPE)
+            .and_then(|value| value.to_str().ok())
+    }
+
+    /// Every header value, duplicates included. Values of the same header come together in
+    /// received order, but names do not keep their order on the wire. Non UTF-8 values are
+    /// decoded lossily.
+    pub fn header_pairs(&self) -> impl Iterator<Item = (&str, String)> {
+        self.headers.iter().map(|(name, value)| {
+            (
+                name.as_str(),
+                String::from_utf8_lossy(value.as_bytes()).into_owned(),
+            )
+        })
+    }
+
+    /// Text body, or a lossy UTF-8 rendering of the bytes when it could not be decoded.
+    pub fn text_lossy(&self) -> String {
+        match &self.text {
+            Some(text) => text.clone(),
+            None => String::from_utf8_lossy(&self.body).into_owned(),
+        }
+    }
+}
+
+#[cfg(test)]
+impl ResponseRecord {
+    /// A response from `http://localhost/` with a text body, received instantly.
+    pub(crate) fn stub(status: u16, headers: &[(&str, &str)], body: &str) -> Self {
+        let headers = headers
+            .iter()
+            .map(|(name, value)| {
+                (
+                    http::HeaderName::from_bytes(name.as_bytes()).unwrap(),
+                    http::HeaderValue::from_str(value).unwrap(),
+                )
+            })
+            .collect();
+        Self {
+            status: StatusCode::from_u16(status).unwrap(),
+            version: Version::HTTP_11,
+            url: Url::parse("http://localhost/").unwrap(),
+            headers,
+            body: Bytes::from(body.to_string()),
+            received_size: body.len(),
+            text: Some(body.to_string()),
+            waiting: Duration::ZERO,
+            elapsed: Duration::ZERO,
+            validation: None,
+        }
+    }
+}
+
+/// Undoes every `Content-Encoding` we know, last applied first. Bodies we cannot decode are
+/// returned unchanged.
+fn decode_content(body: Bytes, headers: &HeaderMap) -> Bytes {
+    let encodings: Vec<String> = headers
+        .get_all(CONTENT_ENCODING)
+        .iter()
+        .filter_map(|value| value.to_str().ok())
+        .flat_map(|value| value.split(','))
+        .map(|encoding| encoding.trim().to_ascii_lowercase())
+        .collect();
+
+    let mut decoded = body.to_vec();
+    for encoding in encodings.iter().rev() {
+        let mut output = vec![];
+        let result = match encoding.as_str() {
+            "gzip" | "x-gzip" => {
+                flate2::read::MultiGzDecoder::new(&decoded[..]).read_to_end(&mut output)
+            }
+            // Servers disagree on whether "deflate" is zlib wrapped; try both.
+            "deflate" => flate2::read::ZlibDecoder::new(&decoded[..])
+                .read_to_end(&mut output)
+                .or_else(|_| {
+                    output.clear();
+                    flate2::read::DeflateDecoder::new(&decoded[..]).read_to_end(&mut output)
+                }),
+            "br" => {
+                brotli_decompressor::Decompressor::new(&decoded[..], 4096).read_to_end(&mut output)
+            }
+            "identity" => continue,
+            _ => return body,
+        };
+        if result.is_err() {
+            return body;
+        }
+        decoded = output;
+    }
+
+    if encodings.is_empty() {
+        body
+    } else {
+        Bytes::from(decoded)
+    }
+}
+
+fn decode_text(body: &[u8], content_type: Option<&str>) -> Option<String> {
+    let encoding = content_type
+        .and_then(|content_type| {
+            content_type.split(';').skip(1).find_map(|param| {
+                let (key, value) = param.split_once('=')?;
+                key.trim()
+                    .eq_ignore_ascii_case("charset")
+                    .then(|| value.trim().trim_matches('"'))
+            })
+        })
+        .and_then(|charset| encoding_rs::Encoding::for_label(charset.as_bytes()))
+        .unwrap_or(encoding_rs::UTF_8);
+
+    let (text, _, had_errors) = encoding.decode(body);
+    (!had_errors).then(|| text.into_owned())
+}
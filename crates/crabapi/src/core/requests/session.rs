@@ -0,0 +1,96 @@
+use super::{Error, RequestOptions};
+use crate::core::auth::TokenCache;
+use crate::core::environments::Variables;
+use crate::core::history::History;
+use crate::core::openapi::OpenApi;
+use reqwest::Client;
+use reqwest::redirect::Policy;
+use std::sync::Arc;
+
+/// What requests share while being sent: the HTTP client, the environment with its
+/// variables, cached OAuth 2.0 tokens, the history requests are recorded to and the API
+/// spec responses are checked against. Clones share the client and the token cache.
+#[derive(Debug, Clone, Default)]
+pub struct Session {
+    client: Client,
+    environment: Option<String>,
+    variables: Variables,
+    tokens: TokenCache,
+    history: Option<History>,
+    spec: Option<Arc<OpenApi>>,
+}
+
+impl Session {
+    pub fn new(client: Client) -> Self {
+        Self {
+            client,
+            ..Self::default()
+        }
+    }
+
+    /// Uses `variables` from the environment called `environment` for the next requests.
+    pub fn with_environment(mut self, environment: Option<String>, variables: Variables) -> Self {
+        self.environment = environment;
+        self.variables = variables;
+        self
+    }
+
+    /// Records every request sent with [`super::send_requests`] to `history`.
+    pub fn with_history(mut self, history: History) -> Self {
+        self.history = Some(history);
+        self
+    }
+
+    /// Validates every response received with [`super::send_requests`] against `spec`.
+    pub fn with_spec(mut self, spec: OpenApi) -> Self {
+        self.spec = Some(Arc::new(spec));
+        self
+    }
+
+    /// Sets a variable for the next requests, e.g. a value taken from a response.
+    pub fn set_variable(&mut self, name: &str, value: &str) {
+        self.variables.insert(name, value);
+    }
+
+    pub fn client(&self) -> &Client {
+        &self.client
+    }
+
+    /// Client honouring the TLS and redirect options of a request: the shared client when
+    /// they are the defaults, otherwise a dedicated one.
+    pub fn client_for(&self, options: &RequestOptions) -> Result<Client, Error> {
+        if !options.insecure && options.follow_redirects {
+            return Ok(self.client.clone());
+        }
+        let redirect = if options.follow_redirects {
+            Policy::default()
+        } else {
+            Policy::none()
+        };
+        Ok(Client::builder()
+            .user_agent(super::constants::USER_AGENT)
+            .danger_accept_invalid_certs(options.insecure)
+            .redirect(redirect)
+            .build()?)
+    }
+
+    pub fn environment(&self) -> Option<&str> {
+        self.environment.as_deref()
+    }
+
+    pub fn variables(&self) -> &Variables {
+        &self.variables
+    }
+
+    pub fn tokens(&self) -> &TokenCache {
+        &self.tokens
+    }
+
+    pub fn history(&self) -> Option<&History> {
+        self.history.as_ref()
+    }
+
+    pub fn spec(&self) -> Option<&OpenApi> {
+        self.spec.as_deref()
+    }
+}
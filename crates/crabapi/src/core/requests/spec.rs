@@ -0,0 +1,416 @@
+use super::Error;
+use crate::core::auth::Auth;
+use crate::core::environments::Variables;
+use http::Method;
+use serde::{Deserialize, Serialize};
+use std::path::PathBuf;
+use std::time::Duration;
+
+/// Owned, serializable description of an HTTP request.
+///
+/// Unlike `reqwest::RequestBuilder`, a `RequestSpec` can be inspected, cloned and saved,
+/// and turned into a builder as many times as needed with [`super::build_request`].
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct RequestSpec {
+    #[serde(with = "method_serde")]
+    pub method: Method,
+    pub url: String,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub query: Vec<KeyValue>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub headers: Vec<KeyValue>,
+    #[serde(default, skip_serializing_if = "BodySpec::is_empty")]
+    pub body: BodySpec,
+    #[serde(default, skip_serializing_if = "Auth::is_none")]
+    pub auth: Auth,
+    #[serde(default, skip_serializing_if = "RequestOptions::is_default")]
+    pub options: RequestOptions,
+}
+
+/// A single query or header row. Rows keep their order, may repeat a key and can be
+/// disabled without being removed.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct KeyValue {
+    pub key: String,
+    pub value: String,
+    #[serde(default = "default_true", skip_serializing_if = "is_true")]
+    pub enabled: bool,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum BodySpec {
+    #[default]
+    Empty,
+    Text {
+        text: String,
+    },
+    File {
+        path: PathBuf,
+    },
+    Multipart {
+        parts: Vec<MultipartPart>,
+    },
+    /// `application/x-www-form-urlencoded` fields; disabled rows are not sent.
+    Form {
+        fields: Vec<KeyValue>,
+    },
+}
+
+/// A field of a `multipart/form-data` body. Parts keep their order and may repeat a name.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct MultipartPart {
+    pub name: String,
+    #[serde(flatten)]
+    pub value: PartValue,
+    /// File name sent in `Content-Disposition`; file parts default to the file's own name.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub filename: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub content_type: Option<String>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum PartValue {
+    Text { text: String },
+    File { path: PathBuf },
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct RequestOptions {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub timeout_ms: Option<u64>,
+    /// Accept invalid TLS certificates and host names.
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub insecure: bool,
+    #[serde(default = "default_true", skip_serializing_if = "is_true")]
+    pub follow_redirects: bool,
+    /// Ask for a compressed response with `Accept-Encoding`.
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub compressed: bool,
+}
+
+impl RequestSpec {
+    pub fn new(method: Method, url: impl Into<String>) -> Self {
+        Self {
+            method,
+            url: url.into(),
+            query: vec![],
+            headers: vec![],
+            body: BodySpec::Empty,
+            auth: Auth::None,
+            options: RequestOptions::default(),
+        }
+    }
+
+    pub fn with_query(mut self, key: impl Into<String>, value: impl Into<String>) -> Self {
+        self.query.push(KeyValue::new(key, value));
+        self
+    }
+
+    pub fn with_header(mut self, key: impl Into<String>, value: impl Into<String>) -> Self {
+        self.headers.push(KeyValue::new(key, value));
+        self
+    }
+
+    pub fn with_body(mut self, body: BodySpec) -> Self {
+        self.body = body;
+        self
+    }
+
+    pub fn with_auth(mut self, auth: Auth) -> Self {
+        self.auth = auth;
+        self
+    }
+
+    /// Copy of the request with every `{{name}}` placeholder in the URL, enabled query and
+    /// header rows, text and multipart body, enabled form fields and auth replaced from
+    /// `variables`.
+    pub fn resolve(&self, variables: &Variables) -> Result<RequestSpec, Error> {
+        let mut resolver = Resolver {
+            variables,
+            unresolved: vec![],
+        };
+
+        let mut request = self.clone();
+        request.url = resolver.expand(&self.url);
+        // Disabled rows are not sent, so they may refer to variables that do not exist.
+        let rows = request.query.iter_mut().chain(request.headers.iter_mut());
+        for row in rows.filter(|row| row.enabled) {
+            row.key = resolver.expand(&row.key);
+            row.value = resolver.expand(&row.value);
+        }
+        match &mut request.body {
+            BodySpec::Text { text } => *text = resolver.expand(text),
+            BodySpec::Multipart { parts } => {
+                for part in parts {
+                    part.name = resolver.expand(&part.name);
+                    if let PartValue::Text { text } = &mut part.value {
+                        *text = resolver.expand(text);
+                    }
+                    if let Some(filename) = &mut part.filename {
+                        *filename = resolver.expand(filename);
+                    }
+                }
+            }
+            BodySpec::Form { fields } => {
+                for field in fields.iter_mut().filter(|field| field.enabled) {
+                    field.key = resolver.expand(&field.key);
+                    field.value = resolver.expand(&field.value);
+                }
+            }
+            BodySpec::Empty | BodySpec::File { .. } => {}
+        }
+        request.auth = self
+            .auth
+            .resolve(resolver.variables, &mut resolver.unresolved);
+
+        if resolver.unresolved.is_empty() {
+            Ok(request)
+        } else {
+            Err(Error::UnresolvedVariables(resolver.unresolved))
+        }
+    }
+
+    pub fn enabled_query(&self) -> impl Iterator<Item = &KeyValue> {
+        self.query.iter().filter(|query| query.enabled)
+    }
+
+    pub fn enabled_headers(&self) -> impl Iterator<Item = &KeyValue> {
+        self.headers.iter().filter(|header| header.enabled)
+    }
+}
+
+impl Default for RequestSpec {
+    fn default() -> Self {
+        Self::new(Method::GET, String::new())
+    }
+}
+
+impl KeyValue {
+    pub fn new(key: impl Into<String>, value: impl Into<String>) -> Self {
+        Self {
+            key: key.into(),
+            value: value.into(),
+            enabled: true,
+        }
+    }
+}
+
+impl BodySpec {
+    pub fn is_empty(&self) -> bool {
+        matches!(self, BodySpec::Empty)
+    }
+
+    /// The enabled fields of a form body, percent-encoded as `key=value&...`.
+    pub fn form_encoded(fields: &[KeyValue]) -> String {
+        let mut serializer = url::form_urlencoded::Serializer::new(String::new());
+        for field in fields.iter().filter(|field| field.enabled) {
+            serializer.append_pair(&field.key, &field.value);
+        }
+        serializer.finish()
+    }
+}
+
+impl MultipartPart {
+    pub fn text(name: impl Into<String>, text: impl Into<String>) -> Self {
+        Self {
+            name: name.into(),
+            value: PartValue::Text { text: text.into() },
+            filename: None,
+            content_type: None,
+        }
+    }
+
+    pub fn file(name: impl Into<String>, path: impl Into<PathBuf>) -> Self {
+        Self {
+            name: name.into(),
+            value: PartValue::File { path: path.into() },
+            filename: None,
+            content_type: None,
+        }
+    }
+}
+
+impl Default for RequestOptions {
+    fn default() -> Self {
+        Self {
+            timeout_ms: None,
+            insecure: false,
+            follow_redirects: true,
+            compressed: false,
+        }
+    }
+}
+
+impl RequestOptions {
+    pub fn timeout(&self) -> Option<Duration> {
+        self.timeout_ms.map(Duration::from_millis)
+    }
+
+    fn is_default(&self) -> bool {
+        *self == Self::default()
+    }
+}
+
+/// Expands templates while collecting every unresolved name across all fields.
+struct Resolver<'a> {
+    variables: &'a Variables,
+    unresolved: Vec<String>,
+}
+
+impl Resolver<'_> {
+    fn expand(&mut self, input: &str) -> String {
+        match self.variables.expand(input) {
+            Ok(output) => output,
+            Err(names) => {
+                for name in names {
+                    if !self.unresolved.contains(&name) {
+                        self.unresolved.push(name);
+                    }
+                }
+                input.to_string()
+            }
+        }
+    }
+}
+
+fn default_true() -> bool {
+    true
+}
+
+fn is_true(value: &bool) -> bool {
+    *value
+}
+
+/// `http::Method` has no serde support, so it is stored as its string form.
+mod method_serde {
+    use http::Method;
+    use serde::{Deserialize, Deserializer, Serializer, de};
+
+    pub fn serialize<S: Serializer>(method: &Method, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(method.as_str())
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Method, D::Error> {
+        let method = String::deserialize(deserializer)?;
+        method.parse().map_err(de::Error::custom)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn variables(pairs: &[(&str, &str)]) -> Variables {
+        let mut variables = Variables::new();
+        for (name, value) in pairs {
+            variables.insert(*name, *value);
+        }
+        variables
+    }
+
+    fn disabled(key: &str, value: &str) -> KeyValue {
+        KeyValue {
+            enabled: false,
+            ..KeyValue::new(key, value)
+        }
+    }
+
+    #[test]
+    fn resolves_url_rows_and_body() {
+        let request = RequestSpec::new(Method::POST, "{{base}}/users/{{id}}")
+            .with_query("expand", "{{expand}}")
+            .with_header("{{header}}", "Bearer {{token}}")
+            .with_body(BodySpec::Text {
+                text: r#"{"id": {{id}}}"#.to_string(),
+            });
+        let variables = variables(&[
+            ("base", "http://127.0.0.1:7878"),
+            ("id", "42"),
+            ("expand", "roles"),
+            ("header", "Authorization"),
+            ("token", "t0k3n"),
+        ]);
+
+        let resolved = request.resolve(&variables).unwrap();
+
+        assert_eq!(resolved.url, "http://127.0.0.1:7878/users/42");
+        assert_eq!(resolved.query, [KeyValue::new("expand", "roles")]);
+        assert_eq!(
+            resolved.headers,
+            [KeyValue::new("Authorization", "Bearer t0k3n")]
+        );
+        assert_eq!(
+            resolved.body,
+            BodySpec::Text {
+                text: r#"{"id": 42}"#.to_string()
+            }
+        );
+    }
+
+    #[test]
+    fn collects_every_unresolved_name_once() {
+        let request = RequestSpec::new(Method::GET, "{{base}}/{{path}}")
+            .with_query("q", "{{path}}")
+            .with_header("X-Token", "{{token}}");
+
+        match request.resolve(&variables(&[("base", "http://localhost")])) {
+            Err(Error::UnresolvedVariables(names)) => assert_eq!(names, ["path", "token"]),
+            other => panic!("expected unresolved variables, got {other:?}"),
+        }
+    }
+
+    #[test]
+    fn leaves_disabled_rows_alone() {
+        let mut request = RequestSpec::new(Method::GET, "{{base}}/users");
+        request.query.push(disabled("debug", "{{missing}}"));
+        request.headers.push(disabled("X-Trace", "{{missing}}"));
+
+        let resolved = request
+            .resolve(&variables(&[("base", "http://localhost")]))
+            .unwrap();
+
+        assert_eq!(resolved.url, "http://localhost/users");
+        assert_eq!(resolved.query, [disabled("debug", "{{missing}}")]);
+        assert_eq!(resolved.headers, [disabled("X-Trace", "{{missing}}")]);
+
+        request.body = BodySpec::Form {
+            fields: vec![
+                KeyValue::new("name", "{{base}}"),
+                disabled("token", "{{missing}}"),
+            ],
+        };
+        let resolved = request
+            .resolve(&variables(&[("base", "http://localhost")]))
+            .unwrap();
+        assert_eq!(
+            resolved.body,
+            BodySpec::Form {
+                fields: vec![
+                    KeyValue::new("name", "http://localhost"),
+                    disabled("token", "{{missing}}"),
+                ],
+            }
+        );
+    }
+
+    #[test]
+    fn form_encodes_enabled_fields() {
+        let fields = [
+            KeyValue::new("q", "rust & crabs"),
+            disabled("debug", "1"),
+            KeyValue::new("a=b", "50%/100%"),
+            KeyValue::new("emoji", "🦀"),
+            KeyValue::new("tag", "x+y"),
+            KeyValue::new("tag", ""),
+        ];
+
+        assert_eq!(
+            BodySpec::form_encoded(&fields),
+            "q=rust+%26+crabs&a%3Db=50%25%2F100%25&emoji=%F0%9F%A6%80&tag=x%2By&tag="
+        );
+        assert_eq!(BodySpec::form_encoded(&[disabled("a", "b")]), "");
+    }
+}
@@ -0,0 +1,197 @@
+use super::Error;
+use crate::core::environments::Variables;
+use serde_json::Value;
+use std::path::Path;
+
+/// Reads one set of variables per iteration: the rows of a CSV file whose first line names
+/// the columns, or the objects of a JSON array (`.json` files).
+pub fn load(path: &Path) -> Result<Vec<Variables>, Error> {
+    let content = std::fs::read_to_string(path).map_err(|source| Error::Io {
+        path: path.to_path_buf(),
+        source,
+    })?;
+    let invalid = |reason: String| Error::InvalidData {
+        path: path.to_path_buf(),
+        reason,
+    };
+
+    let is_json = path
+        .extension()
+        .is_some_and(|extension| extension.eq_ignore_ascii_case("json"));
+    if is_json {
+        parse_json(&content).map_err(invalid)
+    } else {
+        parse_csv(&content).map_err(invalid)
+    }
+}
+
+fn parse_json(content: &str) -> Result<Vec<Variables>, String> {
+    let document: Value = serde_json::from_str(content).map_err(|error| error.to_string())?;
+    let rows = document
+        .as_array()
+        .ok_or_else(|| "expected an array of objects".to_string())?;
+
+    let mut iterations = vec![];
+    for (index, row) in rows.iter().enumerate() {
+        let fields = row
+            .as_object()
+            .ok_or_else(|| format!("item {index} is not an object"))?;
+        let mut variables = Variables::new();
+        for (name, value) in fields {
+            match value {
+                Value::String(text) => variables.insert(name, text),
+                value => variables.insert(name, value.to_string()),
+            }
+        }
+        iterations.push(variables);
+    }
+    Ok(iterations)
+}
+
+fn parse_csv(content: &str) -> Result<Vec<Variables>, String> {
+    let mut records = csv_records(content)?.into_iter();
+    let Some(columns) = records.next() else {
+        return Ok(vec![]);
+    };
+
+    let mut iterations = vec![];
+    for (index, record) in records.enumerate() {
+        if record.len() != columns.len() {
+            return Err(format!(
+                "row {} has {} fields, expected {}",
+                index + 1,
+                record.len(),
+                columns.len()
+            ));
+        }
+        let mut variables = Variables::new();
+        for (name, value) in columns.iter().zip(record) {
+            variables.insert(name.trim(), value);
+        }
+        iterations.push(variables);
+    }
+    Ok(iterations)
+}
+
+/// Splits RFC 4180 CSV into records. Quoted fields may contain commas, line breaks and
+/// doubled quotes; blank lines are skipped.
+fn csv_records(content: &str) -> Result<Vec<Vec<String>>, String> {
+    let content = content.strip_prefix('\u{feff}').unwrap_or(content);
+    let mut records = vec![];
+    let mut record = vec![];
+    let mut field = String::new();
+    let mut quoted = false;
+    let mut chars = content.chars().peekable();
+
+    while let Some(c) = chars.next() {
+        match c {
+            '"' if quoted => {
+                if chars.peek() == Some(&'"') {
+                    chars.next();
+                    field.push('"');
+                } else {
+                    quoted = false;
+                }
+            }
+            '"' if field.is_empty() => quoted = true,
+            ',' if !quoted => record.push(std::mem::take(&mut field)),
+            '\r' if !quoted => {}
+            '\n' if !quoted => {
+                record.push(std::mem::take(&mut field));
+                if record.len() > 1 || !record[0].is_empty() {
+                    records.push(std::mem::take(&mut record));
+                } else {
+                    record.clear();
+                }
+            }
+            c => field.push(c),
+        }
+    }
+    if quoted {
+        return Err("unterminated quoted field".to_string());
+    }
+    if !field.is_empty() || !record.is_empty() {
+        record.push(field);
+        records.push(record);
+    }
+    Ok(records)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn rows(iterations: &[Variables], name: &str) -> Vec<String> {
+        iterations
+            .iter()
+            .map(|variables| variables.get(name).unwrap_or_default().to_string())
+            .collect()
+    }
+
+    #[test]
+    fn reads_quoted_fields() {
+        let iterations = parse_csv(
+            "name,bio\n\
+             Ferris,\"crab, rustacean\"\n\
+             \"Tom \"\"Cat\"\"\",\"two\nlines\"\n\
+             ,\n",
+        )
+        .unwrap();
+
+        assert_eq!(iterations.len(), 3);
+        assert_eq!(rows(&iterations, "name"), ["Ferris", "Tom \"Cat\"", ""]);
+        assert_eq!(
+            rows(&iterations, "bio"),
+            ["crab, rustacean", "two\nlines", ""]
+        );
+    }
+
+    #[test]
+    fn accepts_crlf_bom_and_blank_lines() {
+        let iterations =
+            parse_csv("\u{feff}id , name\r\n1,Ferris\r\n\r\n2,\"a\r\nb\"\r\n3,Corro").unwrap();
+
+        assert_eq!(rows(&iterations, "id"), ["1", "2", "3"]);
+        // Column names are trimmed, values are kept as they are, line breaks in quotes too.
+        assert_eq!(rows(&iterations, "name"), ["Ferris", "a\r\nb", "Corro"]);
+    }
+
+    #[test]
+    fn rejects_ragged_rows_and_open_quotes() {
+        assert_eq!(
+            parse_csv("id,name\n1,Ferris\n2\n").unwrap_err(),
+            "row 2 has 1 fields, expected 2"
+        );
+        assert_eq!(
+            parse_csv("id,name\n1,Ferris,extra\n").unwrap_err(),
+            "row 1 has 3 fields, expected 2"
+        );
+        assert_eq!(
+            parse_csv("id,name\n1,\"Ferris\n").unwrap_err(),
+            "unterminated quoted field"
+        );
+    }
+
+    #[test]
+    fn reads_header_only_and_empty_files() {
+        assert!(parse_csv("id,name\n").unwrap().is_empty());
+        assert!(parse_csv("").unwrap().is_empty());
+    }
+
+    #[test]
+    fn reads_json_arrays() {
+        let iterations = parse_json(r#"[{"id": 1, "name": "Ferris", "admin": true}]"#).unwrap();
+
+        assert_eq!(rows(&iterations, "id"), ["1"]);
+        assert_eq!(rows(&iterations, "name"), ["Ferris"]);
+        assert_eq!(rows(&iterations, "admin"), ["true"]);
+        assert_eq!(
+            parse_json(r#"[{"id": 1}, 2]"#).unwrap_err(),
+            "item 1 is not an object"
+        );
+        assert_eq!(
+            parse_json(r#"{"id": 1}"#).unwrap_err(),
+            "expected an array of objects"
+        );
+    }
+}
@@ -0,0 +1,61 @@
+use crate::core::jsonpath;
+use std::fmt;
+use std::path::PathBuf;
+
+#[derive(Debug)]
+pub enum Error {
+    InvalidExtraction {
+        expression: String,
+        reason: &'static str,
+    },
+    JsonPath(jsonpath::Error),
+    Regex(regex::Error),
+    Io {
+        path: PathBuf,
+        source: std::io::Error,
+    },
+    /// A data file that is neither a CSV table nor a JSON array of objects.
+    InvalidData {
+        path: PathBuf,
+        reason: String,
+    },
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Error::InvalidExtraction { expression, reason } => {
+                write!(f, "invalid extraction '{expression}': {reason}")
+            }
+            Error::JsonPath(source) => write!(f, "{source}"),
+            Error::Regex(source) => write!(f, "invalid pattern: {source}"),
+            Error::Io { path, source } => write!(f, "{}: {source}", path.display()),
+            Error::InvalidData { path, reason } => {
+                write!(f, "invalid data file '{}': {reason}", path.display())
+            }
+        }
+    }
+}
+
+impl std::error::Error for Error {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Error::JsonPath(source) => Some(source),
+            Error::Regex(source) => Some(source),
+            Error::Io { source, .. } => Some(source),
+            Error::InvalidExtraction { .. } | Error::InvalidData { .. } => None,
+        }
+    }
+}
+
+impl From<jsonpath::Error> for Error {
+    fn from(error: jsonpath::Error) -> Self {
+        Error::JsonPath(error)
+    }
+}
+
+impl From<regex::Error> for Error {
+    fn from(error: regex::Error) -> Self {
+        Error::Regex(error)
+    }
+}
@@ -0,0 +1,136 @@
+use super::Error;
+use crate::core::jsonpath::JsonPath;
+use crate::core::requests::ResponseRecord;
+use regex::Regex;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::fmt;
+use std::str::FromStr;
+
+/// Where a variable comes from in a response, written `NAME = SOURCE`:
+///
+/// ```text
+/// token = $.access_token
+/// location = header Location
+/// id = regex "id":\s*(\d+)
+/// code = status
+/// ```
+///
+/// A regular expression yields its first capture group, or the whole match without one.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(try_from = "String", into = "String")]
+pub struct Extraction {
+    pub name: String,
+    pub source: Source,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum Source {
+    JsonPath(JsonPath),
+    Header(String),
+    Regex(String),
+    Status,
+}
+
+impl Extraction {
+    /// The extracted value; JSON strings are taken without their quotes. `Err` says why
+    /// nothing was found.
+    pub fn extract(&self, response: &ResponseRecord) -> Result<String, String> {
+        match &self.source {
+            Source::JsonPath(path) => {
+                let body = serde_json::from_slice::<Value>(&response.body)
+                    .map_err(|_| "body is not JSON".to_string())?;
+                match path.first(&body) {
+                    Some(Value::String(text)) => Ok(text.clone()),
+                    Some(value) => Ok(value.to_string()),
+                    None => Err(format!("{path} selects nothing")),
+                }
+            }
+            Source::Header(name) => response
+                .headers
+                .get(name.as_str())
+                .map(|value| String::from_utf8_lossy(value.as_bytes()).into_owned())
+                .ok_or_else(|| "header is missing".to_string()),
+            Source::Regex(pattern) => {
+                let regex = Regex::new(pattern).map_err(|error| error.to_string())?;
+                let body = response.text_lossy();
+                let captures = regex
+                    .captures(&body)
+                    .ok_or_else(|| "no match in the body".to_string())?;
+                let found = captures.get(1).or_else(|| captures.get(0));
+                Ok(found
+                    .map(|found| found.as_str().to_string())
+                    .unwrap_or_default())
+            }
+            Source::Status => Ok(response.status.as_u16().to_string()),
+        }
+    }
+}
+
+/// The `NAME = SOURCE` form, which parses back to the same extraction.
+impl fmt::Display for Extraction {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} = ", self.name)?;
+        match &self.source {
+            Source::JsonPath(path) => write!(f, "{path}"),
+            Source::Header(name) => write!(f, "header {name}"),
+            Source::Regex(pattern) => write!(f, "regex {pattern}"),
+            Source::Status => write!(f, "status"),
+        }
+    }
+}
+
+impl FromStr for Extraction {
+    type Err = Error;
+
+    fn from_str(expression: &str) -> Result<Self, Self::Err> {
+        let expression = expression.trim();
+        let invalid = |reason| Error::InvalidExtraction {
+            expression: expression.to_string(),
+            reason,
+        };
+
+        let (name, source) = expression
+            .split_once('=')
+            .ok_or_else(|| invalid("expected 'NAME = SOURCE'"))?;
+        let (name, source) = (name.trim(), source.trim());
+        if name.is_empty() || name.contains(['{', '}']) || name.contains(char::is_whitespace) {
+            return Err(invalid("expected a variable name before '='"));
+        }
+
+        let source = if source.starts_with('$') {
+            Source::JsonPath(JsonPath::parse(source)?)
+        } else if source == "status" {
+            Source::Status
+        } else if let Some(header) = source.strip_prefix("header ") {
+            Source::Header(header.trim().to_string())
+        } else if let Some(pattern) = source.strip_prefix("regex ") {
+            let pattern = pattern.trim();
+            Regex::new(pattern)?;
+            Source::Regex(pattern.to_string())
+        } else {
+            return Err(invalid(
+                "expected a JSONPath starting with '$', 'header NAME', 'regex PATTERN' or 'status'",
+            ));
+        };
+
+        Ok(Self {
+            name: name.to_string(),
+            source,
+        })
+    }
+}
+
+impl TryFrom<String> for Extraction {
+    type Error = Error;
+
+    fn try_from(expression: String) -> Result<Self, Self::Error> {
+        expression.parse()
+    }
+}
+
+impl From<Extraction> for String {
+    fn from(extraction: Extraction) -> Self {
+        extraction.to_string()
+    }
+}
@@ -0,0 +1,316 @@
+//! Sends saved requests one after the other, passing values extracted from each response
+//! on to the requests after it (login, then the token, then create, get and delete). The
+//! whole sequence can run once per row of a data file. The scripts of each request and of
+//! its folders run around it.
+
+mod data;
+mod error;
+mod extraction;
+
+use crate::core::assertions::{Assertion, AssertionResult, Report, TestCase};
+use crate::core::collections::{Collection, SavedRequest};
+use crate::core::environments::Variables;
+use crate::core::requests::{RequestSpec, Session, send_requests, wait_response};
+use crate::core::scripts::{Hooks, Outcome};
+use std::time::Instant;
+
+pub use data::load as load_data;
+pub use error::Error;
+pub use extraction::{Extraction, Source};
+
+/// Runs requests in order with a session of its own for every iteration, so variables
+/// extracted in one iteration do not leak into the next.
+#[derive(Debug, Clone)]
+pub struct Runner {
+    session: Session,
+    iterations: Vec<Variables>,
+    bail: bool,
+    collection: Option<Collection>,
+}
+
+impl Runner {
+    pub fn new(session: Session) -> Self {
+        Self {
+            session,
+            iterations: vec![],
+            bail: false,
+            collection: None,
+        }
+    }
+
+    /// Runs the scripts of the folders the requests are in, besides their own.
+    pub fn with_collection(mut self, collection: Collection) -> Self {
+        self.collection = Some(collection);
+        self
+    }
+
+    /// Runs the requests once per set of variables, which override the session's.
+    pub fn with_iterations(mut self, iterations: Vec<Variables>) -> Self {
+        self.iterations = iterations;
+        self
+    }
+
+    /// Stops at the first request that fails.
+    pub fn with_bail(mut self, bail: bool) -> Self {
+        self.bail = bail;
+        self
+    }
+
+    /// Number of data rows, 0 when running once without data.
+    pub fn iterations(&self) -> usize {
+        self.iterations.len()
+    }
+
+    /// Sends `requests`, given with their path in the collection, and calls `on_case` as
+    /// each one completes.
+    pub async fn run(
+        &self,
+        name: &str,
+        requests: &[(String, SavedRequest)],
+        mut on_case: impl FnMut(&TestCase),
+    ) -> Report {
+        let started = Instant::now();
+        let iterations = if self.iterations.is_empty() {
+            vec![Variables::new()]
+        } else {
+            self.iterations.clone()
+        };
+        let numbered = iterations.len() > 1;
+
+        let mut cases = vec![];
+        'iterations: for (index, row) in iterations.iter().enumerate() {
+            let mut session = self.session.clone();
+            for (name, value) in row.iter() {
+                session.set_variable(name, value);
+            }
+
+            for (path, saved) in requests {
+                let sent = Instant::now();
+                let mut request = saved.request.clone();
+                let prepared = self.hooks(path, saved).and_then(|hooks| {
+                    let outcome = hooks
+                        .pre_request(&mut session, &mut request)
+                        .map_err(|error| error.to_string())?;
+                    Ok((hooks, outcome))
+                });
+                let (hooks, outcome) = match prepared {
+                    Ok(prepared) => prepared,
+                    Err(error) => {
+                        let mut case = TestCase::unsent(path, &request, error, sent.elapsed());
+                        case.iteration = numbered.then_some(index + 1);
+                        on_case(&case);
+                        cases.push(case);
+                        if self.bail {
+                            break 'iterations;
+                        }
+                        continue;
+                    }
+                };
+
+                let handle = send_requests(&session, vec![request.clone()]).remove(0);
+                let result = wait_response(handle).await;
+                let assertions: Vec<Assertion> = saved
+                    .assertions
+                    .iter()
+                    .map(|assertion| assertion.resolve(session.variables()))
+                    .collect();
+                let mut case = TestCase::new(path, &request, &result, &assertions, sent.elapsed());
+                case.iteration = numbered.then_some(index + 1);
+                apply(outcome, &session, &mut case);
+                if let Ok(response) = &result {
+                    for extraction in &saved.extract {
+                        let value = extraction.extract(response);
+                        if let Ok(value) = &value {
+                            session.set_variable(&extraction.name, value);
+                        }
+                        case.extracted.push(AssertionResult {
+                            assertion: extraction.to_string(),
+                            passed: value.is_ok(),
+                            message: value.err(),
+                        });
+                    }
+
+                    let sent_request = resolved(&request, &session);
+                    match hooks.post_response(&mut session, &sent_request, response) {
+                        Ok(outcome) => apply(outcome, &session, &mut case),
+                        Err(error) => case.assertions.push(AssertionResult {
+                            assertion: error.to_string(),
+                            passed: false,
+                            message: None,
+                        }),
+                    }
+                }
+
+                on_case(&case);
+                let failed = !case.passed();
+                cases.push(case);
+                if failed && self.bail {
+                    break 'iterations;
+                }
+            }
+        }
+
+        Report {
+            name: name.to_string(),
+            duration: started.elapsed(),
+            cases,
+        }
+    }
+
+    /// The scripts around the request at `path`, as an error message when the scripts of
+    /// its folders cannot be read.
+    fn hooks(&self, path: &str, saved: &SavedRequest) -> Result<Hooks, String> {
+        match &self.collection {
+            Some(collection) => collection
+                .hooks(path, saved)
+                .map_err(|error| error.to_string()),
+            None => Ok(Hooks::new().with_request(&saved.scripts)),
+        }
+    }
+}
+
+/// Adds what scripts printed and tested to `case`, and saves the variables they set with
+/// `set_env` in the session's environment.
+fn apply(outcome: Outcome, session: &Session, case: &mut TestCase) {
+    if let Err(error) = outcome.save_environment(session.environment()) {
+        case.assertions.push(AssertionResult {
+            assertion: "set_env".to_string(),
+            passed: false,
+            message: Some(error.to_string()),
+        });
+    }
+    case.logs.extend(outcome.logs);
+    case.assertions.extend(outcome.tests);
+}
+
+/// The request with its placeholders replaced, as scripts should see it once sent.
+fn resolved(request: &RequestSpec, session: &Session) -> RequestSpec {
+    request
+        .resolve(session.variables())
+        .unwrap_or_else(|_| request.clone())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::requests::{BodySpec, KeyValue, Method};
+    use crate::core::test_server::{StubRequest, StubServer};
+
+    fn saved(method: Method, url: String, extract: &[&str], assertions: &[&str]) -> SavedRequest {
+        let mut saved = SavedRequest::new(RequestSpec::new(method, url));
+        saved.extract = extract.iter().map(|text| text.parse().unwrap()).collect();
+        saved.assertions = assertions
+            .iter()
+            .map(|text| text.parse().unwrap())
+            .collect();
+        saved
+    }
+
+    #[tokio::test]
+    async fn extracted_values_feed_the_next_requests() {
+        let server = StubServer::start(|request: &StubRequest| match request.path.as_str() {
+            "/login" => (
+                200,
+                format!(r#"{{"token":"t-{}","user":{{"id":7}}}}"#, request.body),
+            ),
+            _ => match request.header("authorization") {
+                Some(auth) => (200, format!(r#"{{"auth":"{auth}"}}"#)),
+                None => (401, "{}".to_string()),
+            },
+        })
+        .await;
+
+        let mut login = saved(
+            Method::POST,
+            format!("{}/login", server.url),
+            &["token = $.token", "id = $.user.id", "missing = $.nope"],
+            &[],
+        );
+        login.request.body = BodySpec::Text {
+            text: "{{user}}".to_string(),
+        };
+        let mut get = saved(
+            Method::GET,
+            format!("{}/users/{{{{id}}}}", server.url),
+            &[],
+            &["status == 200", "$.auth == \"Bearer {{token}}\""],
+        );
+        get.request.headers = vec![KeyValue::new("Authorization", "Bearer {{token}}")];
+        let requests = vec![("Login".to_string(), login), ("Get user".to_string(), get)];
+
+        let mut first = Variables::new();
+        first.insert("user", "ferris");
+        let mut second = Variables::new();
+        second.insert("user", "corro");
+        let runner = Runner::new(Session::default()).with_iterations(vec![first, second]);
+        let mut seen = vec![];
+        let report = runner
+            .run("Users", &requests, |case| seen.push(case.name()))
+            .await;
+
+        let sent: Vec<(String, Option<String>)> = server
+            .requests()
+            .into_iter()
+            .map(|request| {
+                let auth = request.header("authorization").map(str::to_string);
+                (request.path, auth)
+            })
+            .collect();
+        assert_eq!(
+            sent,
+            [
+                ("/login".to_string(), None),
+                ("/users/7".to_string(), Some("Bearer t-ferris".to_string())),
+                ("/login".to_string(), None),
+                ("/users/7".to_string(), Some("Bearer t-corro".to_string())),
+            ]
+        );
+        assert_eq!(
+            seen,
+            [
+                "Login (iteration 1)",
+                "Get user (iteration 1)",
+                "Login (iteration 2)",
+                "Get user (iteration 2)",
+            ]
+        );
+
+        // The extraction that finds nothing fails the login, the other requests pass.
+        let login = &report.cases[0];
+        assert!(!login.passed());
+        assert_eq!(
+            login
+                .failures()
+                .map(ToString::to_string)
+                .collect::<Vec<_>>(),
+            ["missing = $.nope: $.nope selects nothing"]
+        );
+        assert!(report.cases[1].passed());
+        assert!(report.cases[3].passed());
+        assert_eq!(report.passed(), 2);
+        assert_eq!(report.failed(), 2);
+    }
+
+    #[tokio::test]
+    async fn bail_stops_at_the_first_failure() {
+        let server = StubServer::start(|_: &StubRequest| (500, "{}".to_string())).await;
+        let requests = vec![
+            (
+                "First".to_string(),
+                saved(Method::GET, server.url.clone(), &[], &["status == 200"]),
+            ),
+            (
+                "Second".to_string(),
+                saved(Method::GET, server.url.clone(), &[], &[]),
+            ),
+        ];
+
+        let report = Runner::new(Session::default())
+            .with_bail(true)
+            .run("Bail", &requests, |_| {})
+            .await;
+
+        assert_eq!(report.cases.len(), 1);
+        assert_eq!(server.requests().len(), 1);
+    }
+}
@@ -0,0 +1,509 @@
+//! What scripts can use: the `request` and `response` types, variables, tests and the
+//! hashing and encoding helpers needed to sign requests.
+
+use super::Outcome;
+use crate::core::assertions::AssertionResult;
+use crate::core::environments::Variables;
+use crate::core::requests::{BodySpec, KeyValue, RequestSpec, ResponseRecord};
+use http::Method;
+use md5::Md5;
+use rhai::module_resolvers::DummyModuleResolver;
+use rhai::{Blob, Dynamic, Engine, EvalAltResult, Map};
+use sha2::{Digest, Sha256};
+use std::cell::RefCell;
+use std::fmt::Write;
+use std::rc::Rc;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+/// Enough for any reasonable script, small enough to stop an endless loop within a second.
+const MAX_OPERATIONS: u64 = 1_000_000;
+const MAX_CALL_LEVELS: usize = 32;
+const MAX_STRING_SIZE: usize = 64 * 1024 * 1024;
+const MAX_COLLECTION_SIZE: usize = 1_000_000;
+const HMAC_BLOCK_SIZE: usize = 64;
+
+type ScriptResult<T> = Result<T, Box<EvalAltResult>>;
+
+/// What the registered functions share with the caller while scripts run.
+pub(super) struct State {
+    pub variables: Variables,
+    pub outcome: Outcome,
+}
+
+/// The response as scripts see it.
+#[derive(Debug, Clone)]
+pub(super) struct ScriptResponse {
+    status: i64,
+    url: String,
+    /// Header names are lowercase; repeated headers keep their last value.
+    headers: Map,
+    body: String,
+    /// The parsed body, `()` when it is not JSON.
+    json: Dynamic,
+    time: i64,
+}
+
+impl ScriptResponse {
+    pub fn new(response: &ResponseRecord) -> Self {
+        let headers = response
+            .header_pairs()
+            .map(|(name, value)| (name.into(), value.into()))
+            .collect();
+        let json = serde_json::from_slice::<serde_json::Value>(&response.body)
+            .ok()
+            .and_then(|value| rhai::serde::to_dynamic(value).ok())
+            .unwrap_or(Dynamic::UNIT);
+        Self {
+            status: response.status.as_u16().into(),
+            url: response.url.to_string(),
+            headers,
+            body: response.text_lossy(),
+            json,
+            time: response.elapsed.as_millis() as i64,
+        }
+    }
+}
+
+pub(super) fn engine(state: &Rc<RefCell<State>>) -> Engine {
+    let mut engine = Engine::new();
+    // No `import`: scripts must not read files.
+    engine.set_module_resolver(DummyModuleResolver::new());
+    engine.set_max_operations(MAX_OPERATIONS);
+    engine.set_max_call_levels(MAX_CALL_LEVELS);
+    engine.set_max_string_size(MAX_STRING_SIZE);
+    engine.set_max_array_size(MAX_COLLECTION_SIZE);
+    engine.set_max_map_size(MAX_COLLECTION_SIZE);
+
+    let logs = state.clone();
+    engine.on_print(move |text| logs.borrow_mut().outcome.logs.push(text.to_string()));
+    let logs = state.clone();
+    engine.on_debug(move |text, _, _| logs.borrow_mut().outcome.logs.push(text.to_string()));
+
+    register_variables(&mut engine, state);
+    register_request(&mut engine);
+    register_response(&mut engine);
+    register_helpers(&mut engine);
+    engine
+}
+
+fn register_variables(engine: &mut Engine, state: &Rc<RefCell<State>>) {
+    let shared = state.clone();
+    engine.register_fn("get_var", move |name: &str| -> Dynamic {
+        match shared.borrow().variables.get(name) {
+            Some(value) => value.into(),
+            None => Dynamic::UNIT,
+        }
+    });
+
+    let shared = state.clone();
+    engine.register_fn("set_var", move |name: &str, value: Dynamic| {
+        let mut state = shared.borrow_mut();
+        let value = text(&value);
+        state.variables.insert(name, value.as_str());
+        state.outcome.variables.push((name.to_string(), value));
+    });
+
+    let shared = state.clone();
+    engine.register_fn("set_env", move |name: &str, value: Dynamic| {
+        let mut state = shared.borrow_mut();
+        let value = text(&value);
+        state.variables.insert(name, value.as_str());
+        state
+            .outcome
+            .variables
+            .push((name.to_string(), value.clone()));
+        state.outcome.environment.push((name.to_string(), value));
+    });
+
+    let shared = state.clone();
+    engine.register_fn("expand", move |input: &str| -> ScriptResult<String> {
+        shared
+            .borrow()
+            .variables
+            .expand(input)
+            .map_err(|unresolved| format!("unknown variables: {}", unresolved.join(", ")).into())
+    });
+
+    let shared = state.clone();
+    engine.register_fn("test", move |name: &str, passed: bool| {
+        shared.borrow_mut().outcome.tests.push(AssertionResult {
+            assertion: name.to_string(),
+            passed,
+            message: None,
+        });
+    });
+}
+
+fn register_request(engine: &mut Engine) {
+    engine
+        .register_type_with_name::<RequestSpec>("Request")
+        .register_get_set(
+            "method",
+            |request: &mut RequestSpec| request.method.to_string(),
+            |request: &mut RequestSpec, method: String| -> ScriptResult<()> {
+                request.method = Method::from_bytes(method.to_ascii_uppercase().as_bytes())
+                    .map_err(|_| format!("invalid method '{method}'"))?;
+                Ok(())
+            },
+        )
+        .register_get_set(
+            "url",
+            |request: &mut RequestSpec| request.url.clone(),
+            |request: &mut RequestSpec, url: String| request.url = url,
+        )
+        .register_get_set(
+            "body",
+            |request: &mut RequestSpec| match &request.body {
+                BodySpec::Text { text } => text.clone(),
+                _ => String::new(),
+            },
+            |request: &mut RequestSpec, text: String| request.body = BodySpec::Text { text },
+        )
+        .register_get("headers", |request: &mut RequestSpec| {
+            to_map(request.enabled_headers())
+        })
+        .register_get("query", |request: &mut RequestSpec| {
+            to_map(request.enabled_query())
+        })
+        .register_fn("header", |request: &mut RequestSpec, name: &str| {
+            find(&request.headers, name, true)
+        })
+        .register_fn(
+            "set_header",
+            |request: &mut RequestSpec, name: &str, value: Dynamic| {
+                set(&mut request.headers, name, text(&value), true)
+            },
+        )
+        .register_fn("remove_header", |request: &mut RequestSpec, name: &str| {
+            request
+                .headers
+                .retain(|row| !row.key.eq_ignore_ascii_case(name))
+        })
+        .register_fn("param", |request: &mut RequestSpec, name: &str| {
+            find(&request.query, name, false)
+        })
+        .register_fn(
+            "set_param",
+            |request: &mut RequestSpec, name: &str, value: Dynamic| {
+                set(&mut request.query, name, text(&value), false)
+            },
+        )
+        .register_fn("remove_param", |request: &mut RequestSpec, name: &str| {
+            request.query.retain(|row| row.key != name)
+        });
+}
+
+fn register_response(engine: &mut Engine) {
+    engine
+        .register_type_with_name::<ScriptResponse>("Response")
+        .register_get("status", |response: &mut ScriptResponse| response.status)
+        .register_get("url", |response: &mut ScriptResponse| response.url.clone())
+        .register_get("headers", |response: &mut ScriptResponse| {
+            response.headers.clone()
+        })
+        .register_get("body", |response: &mut ScriptResponse| {
+            response.body.clone()
+        })
+        .register_get("json", |response: &mut ScriptResponse| {
+            response.json.clone()
+        })
+        .register_get("time", |response: &mut ScriptResponse| response.time)
+        .register_fn("header", |response: &mut ScriptResponse, name: &str| {
+            response
+                .headers
+                .get(name.to_ascii_lowercase().as_str())
+                .cloned()
+                .unwrap_or(Dynamic::UNIT)
+        });
+}
+
+fn register_helpers(engine: &mut Engine) {
+    engine
+        .register_fn("sha256", |data: &str| Blob::from(sha256(data.as_bytes())))
+        .register_fn("sha256", |data: Blob| Blob::from(sha256(&data)))
+        .register_fn("md5", |data: &str| Blob::from(Md5::digest(data).to_vec()))
+        .register_fn("md5", |data: Blob| Blob::from(Md5::digest(data).to_vec()))
+        .register_fn("hmac_sha256", |key: &str, data: &str| {
+            hmac_sha256(key.as_bytes(), data.as_bytes())
+        })
+        .register_fn("hmac_sha256", |key: Blob, data: &str| {
+            hmac_sha256(&key, data.as_bytes())
+        })
+        .register_fn("hmac_sha256", |key: Blob, data: Blob| {
+            hmac_sha256(&key, &data)
+        })
+        .register_fn("hex", |data: Blob| hex(&data))
+        .register_fn("base64", |data: &str| base64(data.as_bytes()))
+        .register_fn("base64", |data: Blob| base64(&data))
+        .register_fn("url_encode", url_encode)
+        .register_fn("timestamp", || unix_time().as_secs() as i64)
+        .register_fn("timestamp_ms", || unix_time().as_millis() as i64)
+        .register_fn("now_iso", || {
+            humantime::format_rfc3339_seconds(SystemTime::now()).to_string()
+        })
+        .register_fn("uuid", uuid);
+}
+
+/// Strings as they are, `()` as nothing, maps and arrays as JSON, anything else printed.
+fn text(value: &Dynamic) -> String {
+    if value.is_unit() {
+        String::new()
+    } else if value.is_string() {
+        value.clone().into_string().unwrap_or_default()
+    } else if value.is_map() || value.is_array() {
+        rhai::serde::from_dynamic::<serde_json::Value>(value)
+            .map(|json| json.to_string())
+            .unwrap_or_else(|_| value.to_string())
+    } else {
+        value.to_string()
+    }
+}
+
+fn to_map<'a>(rows: impl Iterator<Item = &'a KeyValue>) -> Map {
+    rows.map(|row| (row.key.as_str().into(), row.value.clone().into()))
+        .collect()
+}
+
+/// Value of the first enabled row called `name`, or `()`.
+fn find(rows: &[KeyValue], name: &str, ignore_case: bool) -> Dynamic {
+    rows.iter()
+        .filter(|row| row.enabled)
+        .find(|row| same_key(&row.key, name, ignore_case))
+        .map(|row| row.value.clone().into())
+        .unwrap_or(Dynamic::UNIT)
+}
+
+/// Replaces every row called `name` with a single enabled one, in place of the first.
+fn set(rows: &mut Vec<KeyValue>, name: &str, value: String, ignore_case: bool) {
+    let mut value = Some(value);
+    rows.retain_mut(|row| {
+        if !same_key(&row.key, name, ignore_case) {
+            return true;
+        }
+        match value.take() {
+            Some(value) => {
+                *row = KeyValue::new(name, value);
+                true
+            }
+            None => false,
+        }
+    });
+    if let Some(value) = value {
+        rows.push(KeyValue::new(name, value));
+    }
+}
+
+fn same_key(key: &str, name: &str, ignore_case: bool) -> bool {
+    if ignore_case {
+        key.eq_ignore_ascii_case(name)
+    } else {
+        key == name
+    }
+}
+
+fn sha256(data: &[u8]) -> Vec<u8> {
+    Sha256::digest(data).to_vec()
+}
+
+/// HMAC as defined in RFC 2104.
+fn hmac_sha256(key: &[u8], data: &[u8]) -> Blob {
+    let mut block = if key.len() > HMAC_BLOCK_SIZE {
+        sha256(key)
+    } else {
+        key.to_vec()
+    };
+    block.resize(HMAC_BLOCK_SIZE, 0);
+
+    let mut inner = Sha256::new();
+    inner.update(block.iter().map(|byte| byte ^ 0x36).collect::<Vec<u8>>());
+    inner.update(data);
+    let mut outer = Sha256::new();
+    outer.update(block.iter().map(|byte| byte ^ 0x5c).collect::<Vec<u8>>());
+    outer.update(inner.finalize());
+    outer.finalize().to_vec()
+}
+
+fn hex(data: &[u8]) -> String {
+    data.iter().fold(String::new(), |mut output, byte| {
+        let _ = write!(output, "{byte:02x}");
+        output
+    })
+}
+
+/// Standard base64 with padding (RFC 4648).
+fn base64(data: &[u8]) -> String {
+    const ALPHABET: &[u8; 64] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789+/";
+    let mut encoded = String::with_capacity(data.len().div_ceil(3) * 4);
+    for chunk in data.chunks(3) {
+        let bytes = [
+            chunk[0],
+            *chunk.get(1).unwrap_or(&0),
+            *chunk.get(2).unwrap_or(&0),
+        ];
+        let bits = u32::from_be_bytes([0, bytes[0], bytes[1], bytes[2]]);
+        for index in 0..4 {
+            if index <= chunk.len() {
+                let sextet = (bits >> (18 - 6 * index)) & 0x3f;
+                encoded.push(ALPHABET[sextet as usize] as char);
+            } else {
+                encoded.push('=');
+            }
+        }
+    }
+    encoded
+}
+
+/// Percent-encodes everything but the unreserved characters of RFC 3986, as signatures
+/// such as AWS SigV4 and OAuth 1.0 expect.
+fn url_encode(text: &str) -> String {
+    text.bytes()
+        .map(|byte| match byte {
+            b'A'..=b'Z' | b'a'..=b'z' | b'0'..=b'9' | b'-' | b'_' | b'.' | b'~' => {
+                (byte as char).to_string()
+            }
+            byte => format!("%{byte:02X}"),
+        })
+        .collect()
+}
+
+fn unix_time() -> std::time::Duration {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .unwrap_or_default()
+}
+
+/// A random (version 4) UUID.
+fn uuid() -> String {
+    let mut bytes: [u8; 16] = rand::random();
+    bytes[6] = (bytes[6] & 0x0f) | 0x40;
+    bytes[8] = (bytes[8] & 0x3f) | 0x80;
+    let hex = hex(&bytes);
+    format!(
+        "{}-{}-{}-{}-{}",
+        &hex[0..8],
+        &hex[8..12],
+        &hex[12..16],
+        &hex[16..20],
+        &hex[20..32]
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::requests::Session;
+    use crate::core::scripts::{Error, Hook, Hooks, Scripts};
+
+    fn pre_request(source: &str) -> (Session, Result<Outcome, Error>) {
+        let scripts = Scripts {
+            pre_request: source.to_string(),
+            ..Scripts::default()
+        };
+        let mut session = Session::default();
+        let mut request = RequestSpec::new(Method::GET, "http://127.0.0.1:7878");
+        let result = Hooks::new()
+            .with_request(&scripts)
+            .pre_request(&mut session, &mut request);
+        (session, result)
+    }
+
+    /// Test cases 1 to 4, 6 and 7 of RFC 4231; case 5 checks a truncated output.
+    #[test]
+    fn hmac_sha256_matches_rfc_4231() {
+        let long_key = [0xaa; 131];
+        let cases: [(&[u8], &[u8], &str); 6] = [
+            (
+                &[0x0b; 20],
+                b"Hi There",
+                "b0344c61d8db38535ca8afceaf0bf12b881dc200c9833da726e9376c2e32cff7",
+            ),
+            (
+                b"Jefe",
+                b"what do ya want for nothing?",
+                "5bdcc146bf60754e6a042426089575c75a003f089d2739839dec58b964ec3843",
+            ),
+            (
+                &[0xaa; 20],
+                &[0xdd; 50],
+                "773ea91e36800e46854db8ebd09181a72959098b3ef8c122d9635514ced565fe",
+            ),
+            (
+                &(1..=25).collect::<Vec<u8>>(),
+                &[0xcd; 50],
+                "82558a389a443c0ea4cc819899f2083a85f0faa3e578f8077a2e3ff46729665b",
+            ),
+            (
+                &long_key,
+                b"Test Using Larger Than Block-Size Key - Hash Key First",
+                "60e431591ee0b67f0d8a26aacbf5b77f8e0bc6213728c5140546040f0ee37f54",
+            ),
+            (
+                &long_key,
+                b"This is a test using a larger than block-size key and a larger than \
+                  block-size data. The key needs to be hashed before being used by the \
+                  HMAC algorithm.",
+                "9b09ffa71b942fcb27635fbcd5b0e944bfdc63644f0713938a7f51535c3a35e2",
+            ),
+        ];
+        for (key, data, expected) in cases {
+            assert_eq!(hex(&hmac_sha256(key, data)), expected);
+        }
+    }
+
+    #[test]
+    fn base64_matches_rfc_4648() {
+        let cases = [
+            ("", ""),
+            ("f", "Zg=="),
+            ("fo", "Zm8="),
+            ("foo", "Zm9v"),
+            ("foob", "Zm9vYg=="),
+            ("fooba", "Zm9vYmE="),
+            ("foobar", "Zm9vYmFy"),
+        ];
+        for (data, expected) in cases {
+            assert_eq!(base64(data.as_bytes()), expected, "{data}");
+        }
+        assert_eq!(base64(&[0xfb, 0xff, 0xbf]), "+/+/");
+    }
+
+    #[test]
+    fn helpers_are_available_to_scripts() {
+        let (session, result) = pre_request(
+            r#"
+            set_var("signature", hex(hmac_sha256("Jefe", "what do ya want for nothing?")));
+            set_var("encoded", base64("foob"));
+            set_var("digest", hex(sha256("abc")));
+            "#,
+        );
+
+        result.unwrap();
+        let variables = session.variables();
+        assert_eq!(
+            variables.get("signature"),
+            Some("5bdcc146bf60754e6a042426089575c75a003f089d2739839dec58b964ec3843")
+        );
+        assert_eq!(variables.get("encoded"), Some("Zm9vYg=="));
+        assert_eq!(
+            variables.get("digest"),
+            Some("ba7816bf8f01cfea414140de5dae2223b00361a396177a9cb410ff61f20015ad")
+        );
+    }
+
+    #[test]
+    fn endless_loop_hits_the_operation_limit() {
+        let (_, result) = pre_request("let n = 0; loop { n += 1; }");
+
+        match result {
+            Err(Error::Runtime {
+                hook: Hook::PreRequest,
+                origin,
+                message,
+            }) => {
+                assert_eq!(origin, "the request");
+                assert!(message.contains("operations"), "{message}");
+            }
+            other => panic!("expected a runtime error, got {other:?}"),
+        }
+    }
+}
@@ -0,0 +1,41 @@
+use super::Hook;
+use std::fmt;
+
+#[derive(Debug)]
+pub enum Error {
+    /// The script does not parse.
+    Syntax {
+        hook: Hook,
+        origin: String,
+        message: String,
+    },
+    /// The script failed while running, e.g. by calling `throw` or by exceeding the limits
+    /// of the sandbox.
+    Runtime {
+        hook: Hook,
+        origin: String,
+        message: String,
+    },
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Error::Syntax {
+                hook,
+                origin,
+                message,
+            } => write!(
+                f,
+                "syntax error in the {hook} script of {origin}: {message}"
+            ),
+            Error::Runtime {
+                hook,
+                origin,
+                message,
+            } => write!(f, "{hook} script of {origin} failed: {message}"),
+        }
+    }
+}
+
+impl std::error::Error for Error {}
@@ -0,0 +1,215 @@
+//! Pre-request and post-response scripts, written in [Rhai](https://rhai.rs).
+//!
+//! A pre-request script sees the outgoing request as `request` and may change its method,
+//! URL, query, headers and body, e.g. to sign it:
+//!
+//! ```text
+//! let ts = timestamp().to_string();
+//! request.set_header("X-Timestamp", ts);
+//! request.set_header("X-Signature", hex(hmac_sha256(get_var("secret"), ts + request.body)));
+//! ```
+//!
+//! A post-response script sees the request that was sent and the `response`, with its
+//! `status`, `headers`, `body`, parsed `json` body and `time` in milliseconds:
+//!
+//! ```text
+//! test("created", response.status == 201);
+//! set_var("id", response.json.id);
+//! set_env("token", response.headers["x-token"]);
+//! ```
+//!
+//! Both can read variables with `get_var` and `expand("{{name}}")`, set them for the next
+//! requests with `set_var`, or save them in the active environment with `set_env`. The
+//! engine is sandboxed: scripts cannot reach files or the network and are stopped after a
+//! fixed number of operations.
+
+mod api;
+mod error;
+
+use crate::core::assertions::AssertionResult;
+use crate::core::environments::{self, Environments};
+use crate::core::requests::{RequestSpec, ResponseRecord, Session};
+use api::{ScriptResponse, State};
+use rhai::Scope;
+use serde::{Deserialize, Serialize};
+use std::cell::RefCell;
+use std::fmt;
+use std::rc::Rc;
+
+pub use error::Error;
+
+/// Scripts attached to a request or to a folder.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Scripts {
+    #[serde(default, skip_serializing_if = "String::is_empty")]
+    pub pre_request: String,
+    #[serde(default, skip_serializing_if = "String::is_empty")]
+    pub post_response: String,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Hook {
+    PreRequest,
+    PostResponse,
+}
+
+/// The scripts that apply to one request: those of the collection and of each enclosing
+/// folder, outermost first, then the request's own.
+#[derive(Debug, Clone, Default)]
+pub struct Hooks {
+    /// Scripts with a description of where they come from, for error messages.
+    scripts: Vec<(String, Scripts)>,
+}
+
+/// What scripts did besides changing the request.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct Outcome {
+    /// Variables set with `set_var` or `set_env`, in order.
+    pub variables: Vec<(String, String)>,
+    /// Variables set with `set_env`, to be saved in the active environment.
+    pub environment: Vec<(String, String)>,
+    /// Results recorded with `test(name, passed)`.
+    pub tests: Vec<AssertionResult>,
+    /// Lines written with `print` and `debug`.
+    pub logs: Vec<String>,
+}
+
+impl Scripts {
+    pub fn is_empty(&self) -> bool {
+        self.pre_request.trim().is_empty() && self.post_response.trim().is_empty()
+    }
+
+    fn source(&self, hook: Hook) -> &str {
+        match hook {
+            Hook::PreRequest => &self.pre_request,
+            Hook::PostResponse => &self.post_response,
+        }
+    }
+}
+
+impl fmt::Display for Hook {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Hook::PreRequest => write!(f, "pre-request"),
+            Hook::PostResponse => write!(f, "post-response"),
+        }
+    }
+}
+
+impl Hooks {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Adds the scripts of `folder`, the whole collection when empty, after those already
+    /// added.
+    pub fn with_folder(self, folder: &str, scripts: &Scripts) -> Self {
+        let origin = match folder {
+            "" => "the collection".to_string(),
+            folder => format!("folder '{folder}'"),
+        };
+        self.with(origin, scripts)
+    }
+
+    pub fn with_request(self, scripts: &Scripts) -> Self {
+        self.with("the request".to_string(), scripts)
+    }
+
+    fn with(mut self, origin: String, scripts: &Scripts) -> Self {
+        if !scripts.is_empty() {
+            self.scripts.push((origin, scripts.clone()));
+        }
+        self
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.scripts.is_empty()
+    }
+
+    /// Runs the pre-request scripts on `request`, before its placeholders are resolved, and
+    /// sets the variables they set in `session`.
+    pub fn pre_request(
+        &self,
+        session: &mut Session,
+        request: &mut RequestSpec,
+    ) -> Result<Outcome, Error> {
+        let mut scope = Scope::new();
+        scope.push("request", request.clone());
+        let outcome = self.run(Hook::PreRequest, session, &mut scope)?;
+        // Scripts may assign anything to `request`; only keep it when it is still one.
+        if let Some(changed) = scope.get_value::<RequestSpec>("request") {
+            *request = changed;
+        }
+        Ok(outcome)
+    }
+
+    /// Runs the post-response scripts with the request as it was sent and its response,
+    /// and sets the variables they set in `session`.
+    pub fn post_response(
+        &self,
+        session: &mut Session,
+        request: &RequestSpec,
+        response: &ResponseRecord,
+    ) -> Result<Outcome, Error> {
+        let mut scope = Scope::new();
+        scope.push("request", request.clone());
+        scope.push("response", ScriptResponse::new(response));
+        self.run(Hook::PostResponse, session, &mut scope)
+    }
+
+    /// Runs the scripts for `hook` one after the other in `scope`. Each script starts with
+    /// the values in `scope` as the previous one left them, but without its own variables.
+    fn run(&self, hook: Hook, session: &mut Session, scope: &mut Scope) -> Result<Outcome, Error> {
+        let state = Rc::new(RefCell::new(State {
+            variables: session.variables().clone(),
+            outcome: Outcome::default(),
+        }));
+        let engine = api::engine(&state);
+        let globals = scope.len();
+
+        for (origin, scripts) in &self.scripts {
+            let source = scripts.source(hook);
+            if source.trim().is_empty() {
+                continue;
+            }
+            let ast = engine.compile(source).map_err(|error| Error::Syntax {
+                hook,
+                origin: origin.clone(),
+                message: error.to_string(),
+            })?;
+            engine
+                .run_ast_with_scope(scope, &ast)
+                .map_err(|error| Error::Runtime {
+                    hook,
+                    origin: origin.clone(),
+                    message: error.to_string(),
+                })?;
+            scope.rewind(globals);
+        }
+
+        let outcome = state.borrow().outcome.clone();
+        for (name, value) in &outcome.variables {
+            session.set_variable(name, value);
+        }
+        Ok(outcome)
+    }
+}
+
+impl Outcome {
+    /// Saves the variables set with `set_env` in the environment called `environment`.
+    /// Without an environment they only last for the current session.
+    pub fn save_environment(&self, environment: Option<&str>) -> Result<(), environments::Error> {
+        let Some(name) = environment else {
+            return Ok(());
+        };
+        if self.environment.is_empty() {
+            return Ok(());
+        }
+        let mut environments = Environments::load_default()?;
+        let target = environments.get_or_insert(name);
+        for (variable, value) in &self.environment {
+            target.set(variable, value);
+        }
+        environments.save_default()
+    }
+}
@@ -0,0 +1,68 @@
+use super::{GUI, Message};
+use crate::core::auth::{ApiKeyLocation, Auth, AuthKind, OAuth2Grant, OAuth2GrantKind};
+use iced::Task;
+
+#[derive(Debug, Clone)]
+pub enum AuthEvent {
+    Kind(AuthKind),
+    Username(String),
+    Password(String),
+    Token(String),
+    Key(String),
+    Value(String),
+    Location(ApiKeyLocation),
+    TokenUrl(String),
+    ClientId(String),
+    ClientSecret(String),
+    Scope(String),
+    CredentialsInBody(bool),
+    Grant(OAuth2GrantKind),
+    RefreshToken(String),
+}
+
+impl GUI {
+    pub fn update_auth(&mut self, event: AuthEvent) -> Task<Message> {
+        match (event, &mut self.tab_mut().auth) {
+            (AuthEvent::Kind(kind), auth) => *auth = auth.with_kind(kind),
+            (
+                AuthEvent::Username(input),
+                Auth::Basic { username, .. } | Auth::Digest { username, .. },
+            ) => *username = input,
+            (
+                AuthEvent::Password(input),
+                Auth::Basic { password, .. } | Auth::Digest { password, .. },
+            ) => *password = input,
+            (AuthEvent::Token(input), Auth::Bearer { token }) => *token = input,
+            (AuthEvent::Key(input), Auth::ApiKey { key, .. }) => *key = input,
+            (AuthEvent::Value(input), Auth::ApiKey { value, .. }) => *value = input,
+            (AuthEvent::Location(input), Auth::ApiKey { location, .. }) => *location = input,
+            (AuthEvent::TokenUrl(input), Auth::OAuth2(oauth2)) => oauth2.token_url = input,
+            (AuthEvent::ClientId(input), Auth::OAuth2(oauth2)) => oauth2.client_id = input,
+            (AuthEvent::ClientSecret(input), Auth::OAuth2(oauth2)) => oauth2.client_secret = input,
+            (AuthEvent::Scope(input), Auth::OAuth2(oauth2)) => oauth2.scope = input,
+            (AuthEvent::CredentialsInBody(input), Auth::OAuth2(oauth2)) => {
+                oauth2.credentials_in_body = input
+            }
+            (AuthEvent::Grant(kind), Auth::OAuth2(oauth2)) => {
+                oauth2.grant = OAuth2Grant::from_kind(kind)
+            }
+            (AuthEvent::Username(input), Auth::OAuth2(oauth2)) => {
+                if let OAuth2Grant::Password { username, .. } = &mut oauth2.grant {
+                    *username = input
+                }
+            }
+            (AuthEvent::Password(input), Auth::OAuth2(oauth2)) => {
+                if let OAuth2Grant::Password { password, .. } = &mut oauth2.grant {
+                    *password = input
+                }
+            }
+            (AuthEvent::RefreshToken(input), Auth::OAuth2(oauth2)) => {
+                if let OAuth2Grant::RefreshToken { refresh_token } = &mut oauth2.grant {
+                    *refresh_token = input
+                }
+            }
+            _ => {}
+        }
+        Task::none()
+    }
+}
@@ -0,0 +1,213 @@
+use super::{BodyType, GUI, Message, file};
+use crate::core::collections::SavedRequest;
+use crate::core::import;
+use crate::core::requests::{BodySpec, RequestSpec};
+use iced::Task;
+use iced::widget::text_editor;
+use std::path::PathBuf;
+
+#[derive(Debug, Clone)]
+pub enum CollectionEvent {
+    Refresh,
+    Select(String),
+    NameChanged(String),
+    Save,
+    NewFolder,
+    Rename,
+    Duplicate,
+    Delete,
+    Import,
+    ImportPicked(Option<PathBuf>),
+}
+
+impl GUI {
+    pub fn update_collection(&mut self, event: CollectionEvent) -> Task<Message> {
+        let result = match event {
+            CollectionEvent::Refresh => Ok(()),
+            CollectionEvent::Select(path) => self.collection_select(path),
+            CollectionEvent::NameChanged(name) => {
+                self.collection_name_input = name;
+                return Task::none();
+            }
+            CollectionEvent::Save => {
+                let path = self.collection_name_input.trim().to_string();
+                // Overwriting keeps what the editor does not show, such as assertions.
+                let request = match self.collection.load(&path) {
+                    Ok(existing) => SavedRequest {
+                        request: self.tab().request_spec(),
+                        scripts: match self.tab().scripts_folder {
+                            Some(_) => existing.scripts.clone(),
+                            None => self.tab().scripts_editor(),
+                        },
+                        ..existing
+                    },
+                    Err(_) => SavedRequest::new(self.tab().request_spec())
+                        .with_scripts(self.scripts_of_request()),
+                };
+                self.collection.save(&path, &request).map(|_| {
+                    self.scripts_load(&request.scripts, None);
+                    let tab = self.tab_mut();
+                    tab.collection_selected = Some(path);
+                    tab.mark_saved();
+                })
+            }
+            CollectionEvent::NewFolder => self
+                .collection
+                .create_folder(self.collection_name_input.trim()),
+            CollectionEvent::Rename => match self.tab().collection_selected.clone() {
+                Some(path) => self
+                    .collection
+                    .rename(&path, self.collection_name_input.trim())
+                    .map(|new_path| self.collection_moved(&path, Some(&new_path))),
+                None => Ok(()),
+            },
+            CollectionEvent::Duplicate => match self.tab().collection_selected.clone() {
+                Some(path) => self
+                    .collection
+                    .duplicate(&path)
+                    .map(|new_path| self.tab_mut().collection_selected = Some(new_path)),
+                None => Ok(()),
+            },
+            CollectionEvent::Delete => match self.tab().collection_selected.clone() {
+                Some(path) => self
+                    .collection
+                    .delete(&path)
+                    .map(|_| self.collection_moved(&path, None)),
+                None => Ok(()),
+            },
+            CollectionEvent::Import => {
+                return Task::perform(file::pick_file("Import a collection..."), |path| {
+                    Message::Collection(CollectionEvent::ImportPicked(path))
+                });
+            }
+            CollectionEvent::ImportPicked(path) => {
+                if let Some(path) = path {
+                    self.collection_import(path);
+                }
+                return Task::none();
+            }
+        };
+
+        self.collection_notice = None;
+        self.collection_error = result.err().map(|error| error.to_string());
+        self.collection_refresh();
+        Task::none()
+    }
+
+    pub fn collection_refresh(&mut self) {
+        match self.collection.tree() {
+            Ok(tree) => self.collection_tree = tree,
+            Err(error) => self.collection_error = Some(error.to_string()),
+        }
+    }
+
+    /// Imports an export file of another tool into the collection folder named after it.
+    fn collection_import(&mut self, path: PathBuf) {
+        self.collection_notice = None;
+        let imported = match import::from_file(&path) {
+            Ok(imported) => imported,
+            Err(error) => {
+                self.collection_error = Some(error.to_string());
+                return;
+            }
+        };
+
+        let folder = imported.default_folder(&path);
+        let result = imported.save_requests(&self.collection, &folder);
+        self.collection_error = result.as_ref().err().map(|error| error.to_string());
+        if !imported.environments.is_empty() {
+            imported.merge_environments(&mut self.environments);
+            self.environment_save(Ok(()));
+            self.environment_load_rows();
+        }
+
+        let mut notice = vec![format!(
+            "Imported {} requests and {} environments",
+            result.map_or(0, |paths| paths.len()),
+            imported.environments.len()
+        )];
+        notice.extend(imported.warnings);
+        self.collection_notice = Some(notice.join("\n"));
+        self.collection_refresh();
+    }
+
+    fn collection_select(&mut self, path: String) -> Result<(), crate::core::collections::Error> {
+        self.collection_name_input = path.clone();
+        if self.collection.exists(&path) {
+            let saved = self.collection.load(&path)?;
+            if self.tab_for_collection(&path) {
+                return Ok(());
+            }
+            self.load_request_spec(&saved.request);
+            self.scripts_load(&saved.scripts, None);
+            let tab = self.tab_mut();
+            if let Some(reference) = &saved.response {
+                tab.response = None;
+                tab.response_body =
+                    text_editor::Content::with_text(&format!("Reference response\n\n{reference}"));
+            }
+            tab.mark_saved();
+        } else {
+            let scripts = self.collection.scripts(&path)?;
+            self.scripts_load(&scripts, Some(path.clone()));
+            self.tab_mut().collection_saved = None;
+        }
+        self.tab_mut().collection_selected = Some(path);
+        Ok(())
+    }
+
+    /// Follows a renamed item, or a folder, in the tabs it is open in, or unbinds them from
+    /// it once it is deleted.
+    fn collection_moved(&mut self, path: &str, new_path: Option<&str>) {
+        for tab in &mut self.tabs {
+            let Some(selected) = &tab.collection_selected else {
+                continue;
+            };
+            let rest = match selected.strip_prefix(path) {
+                Some(rest) if rest.is_empty() || rest.starts_with('/') => rest.to_string(),
+                _ => continue,
+            };
+            match new_path {
+                Some(new_path) => tab.collection_selected = Some(format!("{new_path}{rest}")),
+                None => {
+                    tab.collection_selected = None;
+                    tab.collection_saved = None;
+                }
+            }
+        }
+    }
+
+    /// Replaces the editor fields with the given request.
+    pub fn load_request_spec(&mut self, request: &RequestSpec) {
+        let tab = self.tab_mut();
+        tab.method_selected = Some(request.method.clone());
+        tab.url_input = request.url.clone();
+        tab.query_input = Self::tuples(&request.query);
+        tab.header_input = Self::tuples(&request.headers);
+        tab.auth = request.auth.clone();
+
+        match &request.body {
+            BodySpec::Empty => {
+                tab.body_type_select = Some(BodyType::Empty);
+            }
+            BodySpec::Text { text } => {
+                tab.body_type_select = Some(BodyType::Text);
+                tab.body_content = text_editor::Content::with_text(text);
+            }
+            BodySpec::File { path } => {
+                tab.body_type_select = Some(BodyType::File);
+                tab.body_file_path = Some(path.clone());
+                tab.body_file_size = std::fs::metadata(path).ok().map(|metadata| metadata.len());
+            }
+            BodySpec::Multipart { parts } => {
+                tab.body_type_select = Some(BodyType::Multipart);
+                tab.multipart_input = parts.clone();
+            }
+            BodySpec::Form { fields } => {
+                tab.body_type_select = Some(BodyType::Form);
+                tab.form_input = Self::tuples(fields);
+            }
+        }
+        self.tab_mut().url_input_valid = self.url_valid();
+    }
+}
@@ -0,0 +1,119 @@
+use super::{GUI, Message, TupleEvent};
+use crate::core::environments::{Environments, Variables};
+use crate::core::requests::{Error as RequestError, validators};
+use iced::Task;
+use std::fmt;
+
+/// Entry of the environment picker; `None` means no environment.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct EnvironmentChoice(pub Option<String>);
+
+#[derive(Debug, Clone)]
+pub enum EnvironmentEvent {
+    Selected(EnvironmentChoice),
+    VariableChanged(TupleEvent),
+    NameChanged(String),
+    Add,
+}
+
+impl fmt::Display for EnvironmentChoice {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match &self.0 {
+            Some(name) => write!(f, "{name}"),
+            None => write!(f, "No environment"),
+        }
+    }
+}
+
+impl GUI {
+    pub fn update_environment(&mut self, event: EnvironmentEvent) -> Task<Message> {
+        match event {
+            EnvironmentEvent::Selected(EnvironmentChoice(name)) => {
+                let result = self.environments.set_active(name.as_deref());
+                self.environment_save(result.map_err(|error| error.to_string()));
+                self.environment_load_rows();
+            }
+            EnvironmentEvent::VariableChanged(event) => {
+                let _ = Self::update_tuple(&mut self.environment_input, event);
+                let rows = Self::key_values(&self.environment_input);
+                if let Some(name) = self.environments.active.clone() {
+                    self.environments.get_or_insert(&name).variables = rows;
+                    self.environment_save(Ok(()));
+                }
+            }
+            EnvironmentEvent::NameChanged(name) => {
+                self.environment_name_input = name;
+            }
+            EnvironmentEvent::Add => {
+                let name = self.environment_name_input.trim().to_string();
+                if !name.is_empty() {
+                    self.environments.get_or_insert(&name);
+                    let result = self.environments.set_active(Some(&name));
+                    self.environment_save(result.map_err(|error| error.to_string()));
+                    self.environment_name_input.clear();
+                    self.environment_load_rows();
+                }
+            }
+        }
+
+        self.tab_mut().url_input_valid = self.url_valid();
+        Task::none()
+    }
+
+    pub fn environment_load(&mut self) {
+        match Environments::load_default() {
+            Ok(environments) => self.environments = environments,
+            Err(error) => self.environment_error = Some(error.to_string()),
+        }
+        self.environment_load_rows();
+    }
+
+    pub fn environment_choices(&self) -> Vec<EnvironmentChoice> {
+        std::iter::once(EnvironmentChoice(None))
+            .chain(
+                self.environments
+                    .names()
+                    .into_iter()
+                    .map(|name| EnvironmentChoice(Some(name))),
+            )
+            .collect()
+    }
+
+    /// Variables of the active environment.
+    pub fn variables(&self) -> Variables {
+        self.environments.variables(None).unwrap_or_default()
+    }
+
+    /// Variables used by the current request that the active environment does not define.
+    pub fn unresolved_variables(&self) -> Vec<String> {
+        match self.tab().request_spec().resolve(&self.variables()) {
+            Err(RequestError::UnresolvedVariables(names)) => names,
+            _ => vec![],
+        }
+    }
+
+    /// Whether the URL is valid once its variables are expanded.
+    pub fn url_valid(&self) -> bool {
+        self.variables()
+            .expand(&self.tab().url_input)
+            .is_ok_and(|url| validators::is_valid_url(&url))
+    }
+
+    pub fn environment_load_rows(&mut self) {
+        self.environment_input = self
+            .environments
+            .active_environment()
+            .map(|environment| Self::tuples(&environment.variables))
+            .unwrap_or_default();
+    }
+
+    pub fn environment_save(&mut self, result: Result<(), String>) {
+        self.environment_error = result
+            .and_then(|_| {
+                self.environments
+                    .save_default()
+                    .map_err(|error| error.to_string())
+            })
+            .err();
+    }
+}
@@ -1,6 +1,5 @@
 use std::io;
 use std::path::PathBuf;
-use std::sync::Arc;
 
 #[derive(Debug, Clone)]
 pub enum FileOpenDialogError {
@@ -8,25 +7,34 @@ pub enum FileOpenDialogError {
     IoError(io::ErrorKind),
 }
 
-pub async fn open_file() -> Result<(PathBuf, Arc<String>), FileOpenDialogError> {
+/// Asks for a body file and returns its path and size in bytes. The file is not read: it
+/// is streamed from disk when the request is sent.
+pub async fn open_file() -> Result<(PathBuf, u64), FileOpenDialogError> {
     let picked_file = rfd::AsyncFileDialog::new()
         .set_title("Open a file...")
         .pick_file()
         .await
         .ok_or(FileOpenDialogError::DialogClosed)?;
 
-    load_file(picked_file).await
+    file_size(picked_file).await
 }
 
-pub async fn load_file(
-    path: impl Into<PathBuf>,
-) -> Result<(PathBuf, Arc<String>), FileOpenDialogError> {
+/// Asks for a file without reading it; `None` when the dialog is closed.
+pub async fn pick_file(title: &'static str) -> Option<PathBuf> {
+    rfd::AsyncFileDialog::new()
+        .set_title(title)
+        .pick_file()
+        .await
+        .map(|file| file.path().to_path_buf())
+}
+
+pub async fn file_size(path: impl Into<PathBuf>) -> Result<(PathBuf, u64), FileOpenDialogError> {
     let path = path.into();
 
-    let contents = tokio::fs::read_to_string(&path)
+    let size = tokio::fs::metadata(&path)
         .await
-        .map(Arc::new)
+        .map(|metadata| metadata.len())
         .map_err(|error| FileOpenDialogError::IoError(error.kind()))?;
 
-    Ok((path, contents))
+    Ok((path, size))
 }
@@ -0,0 +1,45 @@
+use super::{GUI, Message};
+use iced::Task;
+
+#[derive(Debug, Clone)]
+pub enum HistoryEvent {
+    Refresh,
+    Select(u64),
+    Clear,
+}
+
+impl GUI {
+    pub fn update_history(&mut self, event: HistoryEvent) -> Task<Message> {
+        match event {
+            HistoryEvent::Refresh => {}
+            HistoryEvent::Select(id) => {
+                if let Some(entry) = self.history_entries.iter().find(|entry| entry.id == id) {
+                    let request = entry.request.clone();
+                    self.load_request_spec(&request);
+                    self.history_selected = Some(id);
+                }
+                return Task::none();
+            }
+            HistoryEvent::Clear => {
+                if let Err(error) = self.history.clear() {
+                    self.history_error = Some(error.to_string());
+                    return Task::none();
+                }
+                self.history_selected = None;
+            }
+        }
+
+        self.history_refresh();
+        Task::none()
+    }
+
+    pub fn history_refresh(&mut self) {
+        match self.history.entries() {
+            Ok(entries) => {
+                self.history_entries = entries;
+                self.history_error = None;
+            }
+            Err(error) => self.history_error = Some(error.to_string()),
+        }
+    }
+}
@@ -0,0 +1,87 @@
+use super::{GUI, Message};
+use crate::core::jsonpath::{JsonPath, index_location, member_location};
+use iced::Task;
+use serde_json::Value;
+
+#[derive(Debug, Clone)]
+pub enum JsonTreeEvent {
+    /// Expands or collapses the node at a location such as `$.items[0]`.
+    Toggle(String),
+    FilterChanged(String),
+    ExpandAll,
+    CollapseAll,
+    CopyPath(String),
+    CopyValue(String),
+}
+
+impl GUI {
+    pub fn update_json_tree(&mut self, event: JsonTreeEvent) -> Task<Message> {
+        match event {
+            JsonTreeEvent::Toggle(location) => {
+                let expanded = &mut self.tab_mut().json_expanded;
+                if !expanded.remove(&location) {
+                    expanded.insert(location);
+                }
+            }
+            JsonTreeEvent::FilterChanged(filter) => {
+                let tab = self.tab_mut();
+                tab.json_filter_input = filter;
+                // Matches start expanded.
+                if let (Some(json), Ok(path)) =
+                    (&tab.response_json, JsonPath::parse(&tab.json_filter_input))
+                {
+                    for (location, _) in path.locate(json) {
+                        tab.json_expanded.insert(location);
+                    }
+                }
+            }
+            JsonTreeEvent::ExpandAll => {
+                let tab = self.tab_mut();
+                if let Some(json) = &tab.response_json {
+                    collect_containers(json, "$".to_string(), &mut |location| {
+                        tab.json_expanded.insert(location);
+                    });
+                }
+            }
+            JsonTreeEvent::CollapseAll => self.json_tree_reset(),
+            JsonTreeEvent::CopyPath(location) => return iced::clipboard::write(location),
+            JsonTreeEvent::CopyValue(location) => {
+                let value = JsonPath::parse(&location)
+                    .ok()
+                    .zip(self.tab().response_json.as_ref())
+                    .and_then(|(path, json)| path.first(json).cloned());
+                if let Some(value) = value {
+                    let text = match value {
+                        Value::String(text) => text,
+                        value => serde_json::to_string_pretty(&value).unwrap_or_default(),
+                    };
+                    return iced::clipboard::write(text);
+                }
+            }
+        }
+        Task::none()
+    }
+
+    /// Collapses everything but the root.
+    pub fn json_tree_reset(&mut self) {
+        self.tab_mut().json_expanded.clear();
+        self.tab_mut().json_expanded.insert("$".to_string());
+    }
+}
+
+fn collect_containers(value: &Value, location: String, output: &mut impl FnMut(String)) {
+    match value {
+        Value::Array(items) => {
+            for (index, item) in items.iter().enumerate() {
+                collect_containers(item, index_location(&location, index), output);
+            }
+        }
+        Value::Object(fields) => {
+            for (name, field) in fields {
+                collect_containers(field, member_location(&location, name), output);
+            }
+        }
+        _ => return,
+    }
+    output(location);
+}
@@ -1,21 +1,38 @@
+mod auth;
+mod collections;
 mod default_styles;
+mod environments;
 mod file;
+mod history;
+mod json_tree;
+mod multipart;
+mod openapi;
+mod scripts;
+mod tabs;
 mod views;
 
+use crate::core::codegen::Format;
+use crate::core::collections::{Collection, Entry};
+use crate::core::environments::{Environments, Variables};
+use crate::core::history::{History, HistoryEntry};
+use crate::core::import;
+use crate::core::render::{self, BodyKind};
 use crate::core::requests;
-use crate::core::requests::{Method, constants, send_requests, validators};
-use http::{HeaderMap, HeaderName};
+use crate::core::requests::{KeyValue, Method, ResponseRecord, Session, constants, send_requests};
 use iced;
-use iced::widget::column;
+use iced::widget::image;
 use iced::widget::text_editor;
 use iced::widget::text_editor::{Action, Content};
+use iced::widget::{column, row};
 use iced::{Element, Task};
-use reqwest::{Body, Client};
 use std::path::PathBuf;
-use std::sync::Arc;
+use tabs::RequestTab;
 
 pub fn init() {
-    iced::run(GUI::title, GUI::update, GUI::view).unwrap()
+    iced::application(GUI::title, GUI::update, GUI::view)
+        .subscription(GUI::subscription)
+        .run()
+        .unwrap()
 }
 
 #[derive(Debug, Clone)]
@@ -25,12 +42,30 @@ enum Message {
     HeaderInputChanged(TupleEvent),
     QueryInputChanged(TupleEvent),
     SendRequest,
+    PasteCurl,
+    CurlPasted(Option<String>),
+    /// The response to the request of the tab with the given id.
+    ResponseReceived(u64, Result<ResponseRecord, String>),
     ResponseBodyChanged(String),
     ResponseBodyText(Action),
+    ResponseTabChanged(ResponseTab),
+    ResponsePrettyToggled(bool),
+    JsonTree(json_tree::JsonTreeEvent),
     BodyTypeChanged(BodyType),
     BodyContentChanged(text_editor::Action),
     BodyContentOpenFile,
-    BodyContentFileOpened(Result<(PathBuf, Arc<String>), file::FileOpenDialogError>),
+    BodyContentFileOpened(Result<(PathBuf, u64), file::FileOpenDialogError>),
+    Multipart(multipart::MultipartEvent),
+    FormInputChanged(TupleEvent),
+    Auth(auth::AuthEvent),
+    Collection(collections::CollectionEvent),
+    Environment(environments::EnvironmentEvent),
+    History(history::HistoryEvent),
+    Spec(openapi::SpecEvent),
+    Script(scripts::ScriptEvent),
+    CodeFormatChanged(Format),
+    CodeCopy,
+    Tab(tabs::TabEvent),
 }
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
@@ -38,6 +73,18 @@ enum BodyType {
     Empty,
     File,
     Text,
+    Multipart,
+    Form,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum ResponseTab {
+    Body,
+    Headers,
+    Cookies,
+    Timeline,
+    /// Only offered for JSON bodies.
+    JsonTree,
 }
 
 #[derive(Debug, Clone)]
@@ -51,138 +98,271 @@ enum TupleEvent {
 #[derive(Debug)]
 #[allow(clippy::upper_case_acronyms)]
 struct GUI {
-    client: Client,
+    session: Session,
     methods: &'static [Method],
-    method_selected: Option<Method>,
-    url_input: String,
-    url_input_valid: bool,
-    query_input: Vec<(String, String)>,
-    header_input: Vec<(String, String)>,
-    response_body: Content,
-    body_content: text_editor::Content,
-    body_type_select: Option<BodyType>,
-    body_file_path: Option<PathBuf>,
-    body_file_content: Option<Arc<String>>,
+    tabs: Vec<RequestTab>,
+    tab_active: usize,
+    tab_next_id: u64,
+    collection: Collection,
+    collection_tree: Vec<Entry>,
+    collection_name_input: String,
+    collection_error: Option<String>,
+    /// Summary and warnings of the last import.
+    collection_notice: Option<String>,
+    environments: Environments,
+    environment_input: Vec<(String, String)>,
+    environment_name_input: String,
+    environment_error: Option<String>,
+    history: History,
+    history_entries: Vec<HistoryEntry>,
+    history_selected: Option<u64>,
+    history_error: Option<String>,
+    code_format: Format,
+    /// Title of the loaded OpenAPI spec responses are validated against.
+    spec_name: Option<String>,
+    spec_error: Option<String>,
+    /// Variables set by scripts with `set_var`, kept for the next requests.
+    script_variables: Variables,
 }
 
 impl GUI {
     fn new() -> Self {
-        Self {
-            client: Client::new(),
+        let history = History::open_default();
+        let mut gui = Self {
+            session: Session::default().with_history(history.clone()),
             methods: &constants::METHODS,
-            method_selected: Some(Method::GET),
-            url_input: String::new(),
-            url_input_valid: false,
-            query_input: vec![(String::new(), String::new())],
-            header_input: vec![(String::new(), String::new())],
-            response_body: Content::with_text("Response body will go here..."),
-            body_content: text_editor::Content::default(),
-            body_type_select: Some(BodyType::Text),
-            body_file_path: None,
-            body_file_content: None,
-        }
+            tabs: vec![RequestTab::new(0)],
+            tab_active: 0,
+            tab_next_id: 0,
+            collection: Collection::open_default(),
+            collection_tree: vec![],
+            collection_name_input: String::new(),
+            collection_error: None,
+            collection_notice: None,
+            environments: Environments::default(),
+            environment_input: vec![],
+            environment_name_input: String::new(),
+            environment_error: None,
+            history,
+            history_entries: vec![],
+            history_selected: None,
+            history_error: None,
+            code_format: Format::Curl,
+            spec_name: None,
+            spec_error: None,
+            script_variables: Variables::new(),
+        };
+        gui.collection_refresh();
+        gui.environment_load();
+        gui.history_refresh();
+        gui
     }
 
     fn title(&self) -> String {
-        crate::core::app::constants::APP_NAME.to_string()
+        self.tab_window_title()
     }
 
     fn update(&mut self, event: Message) -> Task<Message> {
         match event {
             Message::MethodChanged(method) => {
-                self.method_selected = Some(method);
+                self.tab_mut().method_selected = Some(method);
                 Task::none()
             }
             Message::UrlInputChanged(url) => {
-                self.url_input = url;
-                self.url_input_valid = validators::is_valid_url(&self.url_input);
+                self.tab_mut().url_input = url;
+                self.tab_mut().url_input_valid = self.url_valid();
                 Task::none()
             }
             Message::HeaderInputChanged(header_message) => {
-                Self::update_tuple(&mut self.header_input, header_message)
+                Self::update_tuple(&mut self.tab_mut().header_input, header_message)
             }
             Message::QueryInputChanged(query_message) => {
-                Self::update_tuple(&mut self.query_input, query_message)
+                Self::update_tuple(&mut self.tab_mut().query_input, query_message)
             }
             Message::SendRequest => {
-                self.url_input_valid = validators::is_valid_url(&self.url_input);
-                if !self.url_input_valid {
-                    return Task::none();
-                }
+                self.tab_mut().url_input_valid = self.url_valid();
 
-                let mut headers = HeaderMap::new();
-                for (key, value) in self.header_input.iter() {
-                    if key.is_empty() {
-                        continue;
+                let request = self.tab().request_spec();
+                let session = self
+                    .session
+                    .clone()
+                    .with_environment(self.environments.active.clone(), self.variables());
+                let sent = match self.scripts_before_send(session, request) {
+                    Ok(sent) => sent,
+                    Err(error) => {
+                        return Task::done(Message::ResponseBodyChanged(format!("Error: {error}")));
                     }
-
-                    headers.insert(
-                        HeaderName::from_lowercase(key.to_lowercase().as_ref()).unwrap(),
-                        value.parse().unwrap(),
-                    );
+                };
+                if let Err(error) = requests::build_request(
+                    sent.session.client(),
+                    &sent.request,
+                    sent.session.variables(),
+                ) {
+                    return Task::done(Message::ResponseBodyChanged(format!("Error: {error}")));
                 }
 
-                let request = requests::build_request(
-                    &self.client,
-                    self.url_input.parse().unwrap(),
-                    self.query_input.clone(),
-                    self.method_selected.clone().unwrap(),
-                    headers,
-                    Body::from(String::new()),
-                );
-
-                let handles = send_requests(vec![request]);
-                let handle = handles.into_iter().nth(0).unwrap();
-                Task::perform(
-                    async move { handle.await.unwrap().unwrap().text().await },
-                    |result| match result {
-                        Ok(response) => Message::ResponseBodyChanged(response),
-                        Err(error) => Message::ResponseBodyChanged(error.to_string()),
+                let handles = send_requests(&sent.session, vec![sent.request.clone()]);
+                self.tab_mut().scripts_sent = Some(sent);
+                let Some(handle) = handles.into_iter().next() else {
+                    return Task::none();
+                };
+                let id = self.tab().id;
+                let (task, in_flight) = Task::perform(
+                    async move {
+                        requests::wait_response(handle)
+                            .await
+                            .map_err(|error| error.to_string())
                     },
+                    move |result| Message::ResponseReceived(id, result),
                 )
+                .abortable();
+                self.tab_mut().in_flight = Some(in_flight.abort_on_drop());
+                task
+            }
+            Message::PasteCurl => iced::clipboard::read().map(Message::CurlPasted),
+            Message::CurlPasted(clipboard) => {
+                let Some(command) = clipboard else {
+                    return Task::none();
+                };
+                match import::curl::parse(&command) {
+                    Ok(request) => {
+                        self.load_request_spec(&request);
+                        Task::none()
+                    }
+                    Err(error) => Task::done(Message::ResponseBodyChanged(format!(
+                        "Error: could not import curl command: {error}"
+                    ))),
+                }
+            }
+            Message::ResponseReceived(id, result) => {
+                self.history_refresh();
+                self.with_tab(id, |gui| {
+                    gui.tab_mut().in_flight = None;
+                    match result {
+                        Ok(response) => {
+                            gui.scripts_after_response(&response);
+                            gui.tab_mut().response = Some(response);
+                            gui.response_load_body();
+                            gui.json_tree_reset();
+                        }
+                        Err(error) => gui.response_show_text(&format!("Error: {error}")),
+                    }
+                });
+                Task::none()
             }
             Message::ResponseBodyChanged(response) => {
-                self.response_body = Content::with_text(&response);
+                self.response_show_text(&response);
                 Task::none()
             }
             Message::ResponseBodyText(action) => {
                 match action {
                     Action::Edit(_text) => {}
                     _ => {
-                        self.response_body.perform(action);
+                        self.tab_mut().response_body.perform(action);
                     }
                 }
 
                 Task::none()
             }
+            Message::ResponseTabChanged(tab) => {
+                self.tab_mut().response_tab = tab;
+                Task::none()
+            }
+            Message::JsonTree(event) => self.update_json_tree(event),
+            Message::ResponsePrettyToggled(pretty) => {
+                self.tab_mut().response_pretty = pretty;
+                self.response_load_body();
+                Task::none()
+            }
             Message::BodyTypeChanged(body_type) => {
-                self.body_type_select = Some(body_type);
+                self.tab_mut().body_type_select = Some(body_type);
                 Task::none()
             }
             Message::BodyContentChanged(action) => {
-                self.body_content.perform(action);
+                self.tab_mut().body_content.perform(action);
                 Task::none()
             }
             Message::BodyContentOpenFile => {
                 Task::perform(file::open_file(), Message::BodyContentFileOpened)
             }
-            Message::BodyContentFileOpened(result) => {
-                match result {
-                    Ok((path, content)) => {
-                        self.body_file_content = Some(content);
-                        self.body_file_path = Some(path);
-                    }
-                    Err(error) => {
-                        // TODO: use tracing
-                        println!("Error opening file: {:?}", error);
-                        if let file::FileOpenDialogError::IoError(kind) = error {
-                            println!("Error kind: {:?}", kind);
-                        }
-                    }
+            Message::BodyContentFileOpened(result) => match result {
+                Ok((path, size)) => {
+                    self.tab_mut().body_file_size = Some(size);
+                    self.tab_mut().body_file_path = Some(path);
+                    Task::none()
                 }
+                Err(file::FileOpenDialogError::DialogClosed) => Task::none(),
+                Err(file::FileOpenDialogError::IoError(kind)) => Task::done(
+                    Message::ResponseBodyChanged(format!("Error: could not open file: {kind}")),
+                ),
+            },
+            Message::Multipart(event) => self.update_multipart(event),
+            Message::FormInputChanged(form_message) => {
+                Self::update_tuple(&mut self.tab_mut().form_input, form_message)
+            }
+            Message::Auth(event) => self.update_auth(event),
+            Message::Collection(event) => self.update_collection(event),
+            Message::Environment(event) => self.update_environment(event),
+            Message::History(event) => self.update_history(event),
+            Message::Spec(event) => self.update_spec(event),
+            Message::Script(event) => self.update_scripts(event),
+            Message::CodeFormatChanged(format) => {
+                self.code_format = format;
                 Task::none()
             }
+            Message::CodeCopy => iced::clipboard::write(self.code_snippet()),
+            Message::Tab(event) => self.update_tabs(event),
+        }
+    }
+
+    /// Shows the body of the response, in the form its content type and the Raw/Pretty
+    /// toggle ask for.
+    fn response_load_body(&mut self) {
+        let tab = self.tab_mut();
+        let Some(response) = &tab.response else {
+            return;
+        };
+        let kind = BodyKind::of(response);
+        tab.response_body =
+            Content::with_text(&render::body_text(response, kind, tab.response_pretty));
+        tab.response_image = match kind {
+            BodyKind::Image => Some(image::Handle::from_bytes(response.body.clone())),
+            _ => None,
+        };
+        tab.response_json = match kind {
+            BodyKind::Json => serde_json::from_slice(&response.body).ok(),
+            _ => None,
+        };
+        if tab.response_json.is_none() && tab.response_tab == ResponseTab::JsonTree {
+            tab.response_tab = ResponseTab::Body;
+        }
+    }
+
+    /// Shows `text`, such as an error, in place of a response.
+    fn response_show_text(&mut self, text: &str) {
+        let tab = self.tab_mut();
+        tab.response = None;
+        tab.response_json = None;
+        tab.response_body = Content::with_text(text);
+    }
+
+    pub fn key_values(tuple_vec: &[(String, String)]) -> Vec<KeyValue> {
+        tuple_vec
+            .iter()
+            .filter(|(key, _)| !key.is_empty())
+            .map(|(key, value)| KeyValue::new(key.clone(), value.clone()))
+            .collect()
+    }
+
+    fn tuples(key_values: &[KeyValue]) -> Vec<(String, String)> {
+        let mut tuples: Vec<(String, String)> = key_values
+            .iter()
+            .map(|key_value| (key_value.key.clone(), key_value.value.clone()))
+            .collect();
+        if tuples.is_empty() {
+            tuples.push((String::new(), String::new()));
         }
+        tuples
     }
 
     fn update_tuple(tuple_vec: &mut Vec<(String, String)>, message: TupleEvent) -> Task<Message> {
@@ -211,9 +391,15 @@ impl GUI {
     }
 
     fn view(&self) -> Element<Message> {
+        // ROW: Tabs
+        let tabs_row = self.view_tabs();
+
         // ROW: Method, URI, Send Button
         let request_row = self.view_request();
 
+        // ROW: Auth
+        let auth_row = self.view_request_auth();
+
         // ROW: Headers
         let headers_row = self.view_request_headers();
 
@@ -226,12 +412,32 @@ impl GUI {
         // ROW: Response
         let response_row = self.view_response();
 
-        column![
-            request_row,
-            headers_row,
-            body_row,
-            queries_column,
-            response_row
+        // ROW: Scripts
+        let scripts_row = self.view_scripts();
+
+        // ROW: Code
+        let code_row = self.view_code();
+
+        // COLUMN: Collection, environment and history sidebar
+        let sidebar_column = column![
+            self.view_collections(),
+            self.view_environments(),
+            self.view_history()
+        ];
+
+        row![
+            sidebar_column,
+            column![
+                tabs_row,
+                request_row,
+                auth_row,
+                headers_row,
+                body_row,
+                queries_column,
+                scripts_row,
+                response_row,
+                code_row
+            ]
         ]
         .into()
     }
@@ -0,0 +1,72 @@
+use super::{GUI, Message, file};
+use crate::core::requests::{MultipartPart, PartValue};
+use iced::Task;
+use std::path::PathBuf;
+
+#[derive(Debug, Clone)]
+pub enum MultipartEvent {
+    NameChanged(usize, String),
+    TextChanged(usize, String),
+    PickFile(usize),
+    FilePicked(usize, Option<PathBuf>),
+    /// Turns a file part back into an empty text part.
+    ClearFile(usize),
+    FilenameChanged(usize, String),
+    ContentTypeChanged(usize, String),
+    Remove(usize),
+    Add,
+}
+
+impl GUI {
+    pub fn update_multipart(&mut self, event: MultipartEvent) -> Task<Message> {
+        let parts = &mut self.tab_mut().multipart_input;
+        match event {
+            MultipartEvent::NameChanged(index, name) => {
+                if let Some(part) = parts.get_mut(index) {
+                    part.name = name;
+                }
+            }
+            MultipartEvent::TextChanged(index, text) => {
+                if let Some(part) = parts.get_mut(index) {
+                    part.value = PartValue::Text { text };
+                }
+            }
+            MultipartEvent::PickFile(index) => {
+                return Task::perform(file::pick_file("Attach a file..."), move |path| {
+                    Message::Multipart(MultipartEvent::FilePicked(index, path))
+                });
+            }
+            MultipartEvent::FilePicked(index, Some(path)) => {
+                if let Some(part) = parts.get_mut(index) {
+                    part.value = PartValue::File { path };
+                }
+            }
+            MultipartEvent::FilePicked(_, None) => {}
+            MultipartEvent::ClearFile(index) => {
+                if let Some(part) = parts.get_mut(index) {
+                    part.value = PartValue::Text {
+                        text: String::new(),
+                    };
+                }
+            }
+            MultipartEvent::FilenameChanged(index, filename) => {
+                if let Some(part) = parts.get_mut(index) {
+                    part.filename = Some(filename).filter(|filename| !filename.is_empty());
+                }
+            }
+            MultipartEvent::ContentTypeChanged(index, content_type) => {
+                if let Some(part) = parts.get_mut(index) {
+                    part.content_type =
+                        Some(content_type).filter(|content_type| !content_type.is_empty());
+                }
+            }
+            MultipartEvent::Remove(index) => {
+                if index < parts.len() {
+                    parts.remove(index);
+                }
+            }
+            MultipartEvent::Add => parts.push(MultipartPart::text("", "")),
+        }
+        Task::none()
+    }
+}
@@ -0,0 +1,44 @@
+use super::{GUI, Message, file};
+use crate::core::openapi::OpenApi;
+use iced::Task;
+use std::path::PathBuf;
+
+#[derive(Debug, Clone)]
+pub enum SpecEvent {
+    Load,
+    Picked(Option<PathBuf>),
+    ToggleViolations,
+}
+
+impl GUI {
+    pub fn update_spec(&mut self, event: SpecEvent) -> Task<Message> {
+        match event {
+            SpecEvent::Load => {
+                return Task::perform(file::pick_file("Load an OpenAPI spec..."), |path| {
+                    Message::Spec(SpecEvent::Picked(path))
+                });
+            }
+            SpecEvent::Picked(Some(path)) => match OpenApi::load(&path) {
+                Ok(spec) => {
+                    self.spec_name = Some(match spec.title() {
+                        "" => path
+                            .file_name()
+                            .unwrap_or_default()
+                            .to_string_lossy()
+                            .into(),
+                        title => title.to_string(),
+                    });
+                    self.session = self.session.clone().with_spec(spec);
+                    self.spec_error = None;
+                }
+                Err(error) => self.spec_error = Some(error.to_string()),
+            },
+            SpecEvent::Picked(None) => {}
+            SpecEvent::ToggleViolations => {
+                let tab = self.tab_mut();
+                tab.violations_expanded = !tab.violations_expanded;
+            }
+        }
+        Task::none()
+    }
+}
@@ -0,0 +1,157 @@
+use super::{GUI, Message};
+use crate::core::collections::parent_of;
+use crate::core::requests::{RequestSpec, ResponseRecord, Session};
+use crate::core::scripts::{Hooks, Outcome, Scripts};
+use iced::Task;
+use iced::widget::text_editor::{Action, Content};
+
+#[derive(Debug, Clone)]
+pub enum ScriptEvent {
+    PreRequestEdited(Action),
+    PostResponseEdited(Action),
+    /// Saves the scripts of the folder being edited; request scripts are saved with it.
+    SaveFolder,
+}
+
+/// What the post-response scripts need once the response of a sent request arrives.
+#[derive(Debug)]
+pub struct SentRequest {
+    pub hooks: Hooks,
+    pub session: Session,
+    pub request: RequestSpec,
+}
+
+impl GUI {
+    pub fn update_scripts(&mut self, event: ScriptEvent) -> Task<Message> {
+        match event {
+            ScriptEvent::PreRequestEdited(action) => {
+                self.tab_mut().scripts_pre_request.perform(action)
+            }
+            ScriptEvent::PostResponseEdited(action) => {
+                self.tab_mut().scripts_post_response.perform(action)
+            }
+            ScriptEvent::SaveFolder => {
+                if let Some(folder) = &self.tab().scripts_folder {
+                    let result = self
+                        .collection
+                        .save_scripts(folder, &self.tab().scripts_editor());
+                    self.tab_mut().scripts_output =
+                        result.err().map(|error| format!("Error: {error}"));
+                }
+            }
+        }
+        Task::none()
+    }
+
+    /// Shows `scripts` in the editors, as those of `folder` or, without one, of the request.
+    pub fn scripts_load(&mut self, scripts: &Scripts, folder: Option<String>) {
+        let tab = self.tab_mut();
+        tab.scripts_pre_request = Content::with_text(&scripts.pre_request);
+        tab.scripts_post_response = Content::with_text(&scripts.post_response);
+        tab.scripts_folder = folder;
+    }
+
+    /// The request's scripts in the editors, or those saved with it while a folder's are
+    /// being edited.
+    pub fn scripts_of_request(&self) -> Scripts {
+        match (&self.tab().scripts_folder, &self.tab().collection_selected) {
+            (None, _) => self.tab().scripts_editor(),
+            (Some(_), Some(path)) => self
+                .collection
+                .load(path)
+                .map(|saved| saved.scripts)
+                .unwrap_or_default(),
+            (Some(_), None) => Scripts::default(),
+        }
+    }
+
+    /// Runs the pre-request scripts of the folders of the selected request and its own on
+    /// `request`, and returns what the post-response scripts will need.
+    pub fn scripts_before_send(
+        &mut self,
+        mut session: Session,
+        mut request: RequestSpec,
+    ) -> Result<SentRequest, String> {
+        for (name, value) in self.script_variables.iter() {
+            session.set_variable(name, value);
+        }
+        let hooks = self.scripts_hooks()?;
+        let outcome = hooks
+            .pre_request(&mut session, &mut request)
+            .map_err(|error| error.to_string())?;
+        self.tab_mut().scripts_output = None;
+        self.scripts_apply(outcome);
+        Ok(SentRequest {
+            hooks,
+            session,
+            request,
+        })
+    }
+
+    pub fn scripts_after_response(&mut self, response: &ResponseRecord) {
+        let Some(SentRequest {
+            hooks,
+            mut session,
+            request,
+        }) = self.tab_mut().scripts_sent.take()
+        else {
+            return;
+        };
+        let request = request
+            .resolve(session.variables())
+            .unwrap_or_else(|_| request.clone());
+        match hooks.post_response(&mut session, &request, response) {
+            Ok(outcome) => self.scripts_apply(outcome),
+            Err(error) => self.scripts_output_push(format!("Error: {error}")),
+        }
+    }
+
+    /// With a folder selected, the request is sent as if it were in that folder.
+    fn scripts_hooks(&self) -> Result<Hooks, String> {
+        let request = self.scripts_of_request();
+        let folder_hooks = match (&self.tab().scripts_folder, &self.tab().collection_selected) {
+            (Some(folder), _) => self.collection.folder_hooks(folder),
+            (None, Some(path)) => self.collection.folder_hooks(parent_of(path)),
+            (None, None) => Ok(Hooks::new()),
+        };
+        folder_hooks
+            .map(|hooks| hooks.with_request(&request))
+            .map_err(|error| error.to_string())
+    }
+
+    /// Keeps the variables scripts set for the next requests, saves those set with
+    /// `set_env` in the active environment and shows what they printed and tested.
+    fn scripts_apply(&mut self, outcome: Outcome) {
+        for (name, value) in &outcome.variables {
+            self.script_variables.insert(name.as_str(), value.as_str());
+        }
+        if let Some(active) = self.environments.active.clone() {
+            if !outcome.environment.is_empty() {
+                let environment = self.environments.get_or_insert(&active);
+                for (name, value) in &outcome.environment {
+                    environment.set(name, value);
+                }
+                self.environment_save(Ok(()));
+                self.environment_load_rows();
+            }
+        }
+        for line in outcome.logs {
+            self.scripts_output_push(format!("> {line}"));
+        }
+        for result in outcome.tests {
+            let label = if result.passed { "PASS" } else { "FAIL" };
+            self.scripts_output_push(format!("{label} {result}"));
+        }
+    }
+
+    fn scripts_output_push(&mut self, line: String) {
+        let output = &mut self.tab_mut().scripts_output;
+        match output {
+            Some(text) => {
+                text.push('\n');
+                text.push_str(&line);
+            }
+            None => *output = Some(line),
+        }
+    }
+}
@@ -0,0 +1,375 @@
+use super::{BodyType, GUI, Message, ResponseTab, scripts};
+use crate::core::auth::Auth;
+use crate::core::requests::{BodySpec, Method, MultipartPart, RequestSpec, ResponseRecord};
+use crate::core::scripts::Scripts;
+use iced::keyboard::key::Named;
+use iced::keyboard::{Key, Modifiers};
+use iced::task::Handle;
+use iced::widget::image;
+use iced::widget::text_editor::Content;
+use iced::{Subscription, Task, event, keyboard};
+use std::collections::HashSet;
+use std::path::PathBuf;
+
+#[derive(Debug, Clone)]
+pub enum TabEvent {
+    New,
+    Select(usize),
+    SelectLast,
+    Next,
+    Previous,
+    /// Closes the active tab when `None`.
+    Close(Option<usize>),
+    Duplicate(Option<usize>),
+    MoveLeft(usize),
+    MoveRight(usize),
+}
+
+/// A request being edited, with its own response and the task waiting for it.
+#[derive(Debug)]
+pub struct RequestTab {
+    /// Stays the same while the tab moves, so a response finds the tab that sent it.
+    pub id: u64,
+    pub method_selected: Option<Method>,
+    pub url_input: String,
+    pub url_input_valid: bool,
+    pub query_input: Vec<(String, String)>,
+    pub header_input: Vec<(String, String)>,
+    pub response: Option<ResponseRecord>,
+    pub response_body: Content,
+    pub response_tab: ResponseTab,
+    /// Pretty print the response body, and preview images, instead of showing it as sent.
+    pub response_pretty: bool,
+    /// Preview of an image response, decoded once when it arrives.
+    pub response_image: Option<image::Handle>,
+    /// The body parsed, when it is JSON, for the tree view.
+    pub response_json: Option<serde_json::Value>,
+    pub json_filter_input: String,
+    /// Locations of the expanded nodes of the JSON tree, e.g. `$.items[0]`.
+    pub json_expanded: HashSet<String>,
+    pub body_content: Content,
+    pub body_type_select: Option<BodyType>,
+    pub body_file_path: Option<PathBuf>,
+    /// Size in bytes of the body file when it was picked.
+    pub body_file_size: Option<u64>,
+    pub multipart_input: Vec<MultipartPart>,
+    pub form_input: Vec<(String, String)>,
+    pub auth: Auth,
+    pub collection_selected: Option<String>,
+    /// The request and its scripts as the editors showed them when last loaded from or saved
+    /// to the collection.
+    pub collection_saved: Option<(RequestSpec, Scripts)>,
+    pub violations_expanded: bool,
+    pub scripts_pre_request: Content,
+    pub scripts_post_response: Content,
+    /// Folder whose scripts are in the editors, `None` when they are the request's.
+    pub scripts_folder: Option<String>,
+    /// What the scripts of the last request printed and tested.
+    pub scripts_output: Option<String>,
+    /// The request waiting for its response, for the post-response scripts.
+    pub scripts_sent: Option<scripts::SentRequest>,
+    /// Aborts the request in flight when the tab is closed or sends another one.
+    pub in_flight: Option<Handle>,
+}
+
+impl RequestTab {
+    pub fn new(id: u64) -> Self {
+        Self {
+            id,
+            method_selected: Some(Method::GET),
+            url_input: String::new(),
+            url_input_valid: false,
+            query_input: vec![(String::new(), String::new())],
+            header_input: vec![(String::new(), String::new())],
+            response: None,
+            response_body: Content::with_text("Response body will go here..."),
+            response_tab: ResponseTab::Body,
+            response_pretty: true,
+            response_image: None,
+            response_json: None,
+            json_filter_input: String::new(),
+            json_expanded: HashSet::new(),
+            body_content: Content::default(),
+            body_type_select: Some(BodyType::Text),
+            body_file_path: None,
+            body_file_size: None,
+            multipart_input: vec![MultipartPart::text("", "")],
+            form_input: vec![(String::new(), String::new())],
+            auth: Auth::None,
+            collection_selected: None,
+            collection_saved: None,
+            violations_expanded: false,
+            scripts_pre_request: Content::default(),
+            scripts_post_response: Content::default(),
+            scripts_folder: None,
+            scripts_output: None,
+            scripts_sent: None,
+            in_flight: None,
+        }
+    }
+
+    /// Name of the saved request, or its method and URL.
+    pub fn title(&self) -> String {
+        match (&self.collection_selected, &self.collection_saved) {
+            (Some(path), Some(_)) => path.rsplit('/').next().unwrap_or(path).to_string(),
+            _ if self.url_input.is_empty() => "New request".to_string(),
+            _ => {
+                let method = self.method_selected.as_ref().unwrap_or(&Method::GET);
+                format!("{method} {}", self.url_input)
+            }
+        }
+    }
+
+    /// Whether the request differs from the collection item it was loaded from.
+    pub fn dirty(&self) -> bool {
+        self.collection_saved
+            .as_ref()
+            .is_some_and(|(request, scripts)| {
+                *request != self.request_spec()
+                    || (self.scripts_folder.is_none() && *scripts != self.scripts_editor())
+            })
+    }
+
+    pub fn mark_saved(&mut self) {
+        self.collection_saved = Some((self.request_spec(), self.scripts_editor()));
+    }
+
+    /// Nothing was typed, loaded or sent yet, so a saved request can be opened here.
+    fn blank(&self) -> bool {
+        self.url_input.is_empty()
+            && self.collection_selected.is_none()
+            && self.response.is_none()
+            && self.in_flight.is_none()
+    }
+
+    pub fn request_spec(&self) -> RequestSpec {
+        let mut request = RequestSpec::new(
+            self.method_selected.clone().unwrap_or(Method::GET),
+            self.url_input.clone(),
+        );
+        request.query = GUI::key_values(&self.query_input);
+        request.headers = GUI::key_values(&self.header_input);
+        request.auth = self.auth.clone();
+        request.body = self.request_body();
+        request
+    }
+
+    fn request_body(&self) -> BodySpec {
+        match self.body_type_select {
+            Some(BodyType::Text) => {
+                // Joined by hand: `Content::text` adds a newline even when none was typed.
+                let lines: Vec<String> = self
+                    .body_content
+                    .lines()
+                    .map(|line| line.to_string())
+                    .collect();
+                match lines.join("\n") {
+                    text if text.is_empty() => BodySpec::Empty,
+                    text => BodySpec::Text { text },
+                }
+            }
+            Some(BodyType::File) => match &self.body_file_path {
+                Some(path) => BodySpec::File { path: path.clone() },
+                None => BodySpec::Empty,
+            },
+            Some(BodyType::Multipart) => BodySpec::Multipart {
+                parts: self.multipart_parts(),
+            },
+            Some(BodyType::Form) => BodySpec::Form {
+                fields: GUI::key_values(&self.form_input),
+            },
+            Some(BodyType::Empty) | None => BodySpec::Empty,
+        }
+    }
+
+    /// Parts with a name, in order.
+    pub fn multipart_parts(&self) -> Vec<MultipartPart> {
+        self.multipart_input
+            .iter()
+            .filter(|part| !part.name.is_empty())
+            .cloned()
+            .collect()
+    }
+
+    /// The scripts in the editors.
+    pub fn scripts_editor(&self) -> Scripts {
+        let source = |content: &Content| match content.text().trim() {
+            "" => String::new(),
+            _ => content.text(),
+        };
+        Scripts {
+            pre_request: source(&self.scripts_pre_request),
+            post_response: source(&self.scripts_post_response),
+        }
+    }
+}
+
+impl GUI {
+    pub fn tab(&self) -> &RequestTab {
+        &self.tabs[self.tab_active]
+    }
+
+    pub fn tab_mut(&mut self) -> &mut RequestTab {
+        &mut self.tabs[self.tab_active]
+    }
+
+    pub fn update_tabs(&mut self, event: TabEvent) -> Task<Message> {
+        let count = self.tabs.len();
+        match event {
+            TabEvent::New => self.tab_open(),
+            TabEvent::Select(index) => {
+                if index < count {
+                    self.tab_select(index);
+                }
+            }
+            TabEvent::SelectLast => self.tab_select(count - 1),
+            TabEvent::Next => self.tab_select((self.tab_active + 1) % count),
+            TabEvent::Previous => self.tab_select((self.tab_active + count - 1) % count),
+            TabEvent::Close(index) => self.tab_close(index.unwrap_or(self.tab_active)),
+            TabEvent::Duplicate(index) => self.tab_duplicate(index.unwrap_or(self.tab_active)),
+            TabEvent::MoveLeft(index) => {
+                if index > 0 && index < count {
+                    self.tab_swap(index, index - 1);
+                }
+            }
+            TabEvent::MoveRight(index) => {
+                if index + 1 < count {
+                    self.tab_swap(index, index + 1);
+                }
+            }
+        }
+        Task::none()
+    }
+
+    /// Ctrl+T opens a tab, Ctrl+W closes it, Ctrl+D duplicates it, Ctrl+Tab, Ctrl+Shift+Tab,
+    /// Ctrl+PageDown and Ctrl+PageUp switch to the next or previous one and Ctrl+1 to Ctrl+9
+    /// to a given one, Ctrl+9 being the last.
+    pub fn subscription(&self) -> Subscription<Message> {
+        // Listens to all events, as focused inputs capture the key presses.
+        event::listen_with(|event, _status, _window| {
+            let iced::Event::Keyboard(keyboard::Event::KeyPressed { key, modifiers, .. }) = event
+            else {
+                return None;
+            };
+            Self::tab_shortcut(key, modifiers).map(Message::Tab)
+        })
+    }
+
+    fn tab_shortcut(key: Key, modifiers: Modifiers) -> Option<TabEvent> {
+        if !modifiers.command() {
+            return None;
+        }
+        let event = match key.as_ref() {
+            Key::Named(Named::Tab) if modifiers.shift() => TabEvent::Previous,
+            Key::Named(Named::Tab) | Key::Named(Named::PageDown) => TabEvent::Next,
+            Key::Named(Named::PageUp) => TabEvent::Previous,
+            Key::Character("t") => TabEvent::New,
+            Key::Character("w") => TabEvent::Close(None),
+            Key::Character("d") => TabEvent::Duplicate(None),
+            Key::Character("9") => TabEvent::SelectLast,
+            Key::Character(digit) => match digit.parse::<usize>() {
+                Ok(number @ 1..=8) => TabEvent::Select(number - 1),
+                _ => return None,
+            },
+            _ => return None,
+        };
+        Some(event)
+    }
+
+    /// Opens a blank tab after the others and switches to it.
+    fn tab_open(&mut self) {
+        self.tab_next_id += 1;
+        self.tabs.push(RequestTab::new(self.tab_next_id));
+        self.tab_select(self.tabs.len() - 1);
+    }
+
+    fn tab_select(&mut self, index: usize) {
+        self.tab_active = index;
+        // The environment may have changed while the tab was in the background.
+        self.tab_mut().url_input_valid = self.url_valid();
+    }
+
+    /// Closing the last tab leaves a blank one.
+    fn tab_close(&mut self, index: usize) {
+        if index >= self.tabs.len() {
+            return;
+        }
+        // Dropping the tab aborts its request in flight.
+        self.tabs.remove(index);
+        if self.tabs.is_empty() {
+            self.tab_open();
+            return;
+        }
+        if index < self.tab_active || self.tab_active == self.tabs.len() {
+            self.tab_active -= 1;
+        }
+        self.tab_select(self.tab_active);
+    }
+
+    /// Opens a copy of the request of the tab at `index` next to it, not bound to the
+    /// collection so it can be changed freely.
+    fn tab_duplicate(&mut self, index: usize) {
+        let Some(source) = self.tabs.get(index) else {
+            return;
+        };
+        let request = source.request_spec();
+        let scripts = source.scripts_editor();
+        self.tab_open();
+        self.load_request_spec(&request);
+        self.scripts_load(&scripts, None);
+
+        let tab = self.tabs.pop().expect("a tab was just opened");
+        self.tabs.insert(index + 1, tab);
+        self.tab_select(index + 1);
+    }
+
+    fn tab_swap(&mut self, index: usize, other: usize) {
+        self.tabs.swap(index, other);
+        if self.tab_active == index {
+            self.tab_active = other;
+        } else if self.tab_active == other {
+            self.tab_active = index;
+        }
+    }
+
+    /// Switches to the tab the saved request at `path` is open in, or opens it in a new one
+    /// unless the active tab is still blank.
+    pub fn tab_for_collection(&mut self, path: &str) -> bool {
+        let open = self.tabs.iter().position(|tab| {
+            tab.collection_saved.is_some() && tab.collection_selected.as_deref() == Some(path)
+        });
+        match open {
+            Some(index) => {
+                self.tab_select(index);
+                true
+            }
+            None => {
+                if !self.tab().blank() {
+                    self.tab_open();
+                }
+                false
+            }
+        }
+    }
+
+    /// Runs `update` with the tab `id` active, for results that arrive after the user
+    /// switched to another tab. Does nothing when the tab was closed since.
+    pub fn with_tab<T>(&mut self, id: u64, update: impl FnOnce(&mut Self) -> T) -> Option<T> {
+        let index = self.tabs.iter().position(|tab| tab.id == id)?;
+        let active = std::mem::replace(&mut self.tab_active, index);
+        let result = update(self);
+        self.tab_active = active;
+        Some(result)
+    }
+
+    /// Default window title, followed by the active tab's.
+    pub fn tab_window_title(&self) -> String {
+        let tab = self.tab();
+        let dirty = if tab.dirty() { " •" } else { "" };
+        format!(
+            "{} - {}{dirty}",
+            crate::core::app::constants::APP_NAME,
+            tab.title()
+        )
+    }
+}
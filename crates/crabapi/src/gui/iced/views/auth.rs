@@ -0,0 +1,122 @@
+use super::GUI;
+use crate::core::auth::{ApiKeyLocation, Auth, AuthKind, OAuth2, OAuth2Grant, OAuth2GrantKind};
+use crate::gui::iced::auth::AuthEvent;
+use crate::gui::iced::{Message, default_styles};
+use iced::widget::{Text, TextInput, checkbox, column, container, pick_list, row};
+use iced::{Element, Length};
+
+impl GUI {
+    pub fn view_request_auth(&self) -> Element<Message> {
+        container(self.view_request_auth_inner())
+            .width(Length::Fill)
+            .padding(default_styles::padding())
+            .into()
+    }
+
+    fn view_request_auth_inner(&self) -> Element<Message> {
+        let auth_title = Text::new("Auth").size(default_styles::input_size());
+
+        let kind_input = pick_list(AuthKind::ALL, Some(self.tab().auth.kind()), |kind| {
+            Message::Auth(AuthEvent::Kind(kind))
+        });
+
+        let fields = self.view_request_auth_fields();
+
+        column![
+            row![auth_title, kind_input].spacing(default_styles::spacing()),
+            fields
+        ]
+        .spacing(default_styles::spacing())
+        .into()
+    }
+
+    fn view_request_auth_fields(&self) -> Element<Message> {
+        match &self.tab().auth {
+            Auth::None => row![].into(),
+            Auth::Basic { username, password } | Auth::Digest { username, password } => row![
+                TextInput::new("Username", username)
+                    .on_input(|input| Message::Auth(AuthEvent::Username(input))),
+                TextInput::new("Password", password)
+                    .on_input(|input| Message::Auth(AuthEvent::Password(input)))
+                    .secure(true),
+            ]
+            .spacing(default_styles::spacing())
+            .into(),
+            Auth::Bearer { token } => TextInput::new("Token", token)
+                .on_input(|input| Message::Auth(AuthEvent::Token(input)))
+                .into(),
+            Auth::ApiKey {
+                key,
+                value,
+                location,
+            } => row![
+                TextInput::new("Key", key)
+                    .on_input(|input| Message::Auth(AuthEvent::Key(input)))
+                    .width(Length::FillPortion(1)),
+                TextInput::new("Value", value)
+                    .on_input(|input| Message::Auth(AuthEvent::Value(input)))
+                    .width(Length::FillPortion(2)),
+                pick_list(
+                    [ApiKeyLocation::Header, ApiKeyLocation::Query],
+                    Some(*location),
+                    |location| Message::Auth(AuthEvent::Location(location)),
+                ),
+            ]
+            .spacing(default_styles::spacing())
+            .into(),
+            Auth::OAuth2(oauth2) => self.view_request_auth_oauth2(oauth2),
+        }
+    }
+
+    fn view_request_auth_oauth2<'a>(&'a self, oauth2: &'a OAuth2) -> Element<'a, Message> {
+        let endpoint = row![
+            TextInput::new("Token URL", &oauth2.token_url)
+                .on_input(|input| Message::Auth(AuthEvent::TokenUrl(input)))
+                .width(Length::FillPortion(2)),
+            TextInput::new("Scope", &oauth2.scope)
+                .on_input(|input| Message::Auth(AuthEvent::Scope(input)))
+                .width(Length::FillPortion(1)),
+        ]
+        .spacing(default_styles::spacing());
+
+        let client = row![
+            TextInput::new("Client ID", &oauth2.client_id)
+                .on_input(|input| Message::Auth(AuthEvent::ClientId(input))),
+            TextInput::new("Client Secret", &oauth2.client_secret)
+                .on_input(|input| Message::Auth(AuthEvent::ClientSecret(input)))
+                .secure(true),
+            checkbox("Credentials in body", oauth2.credentials_in_body)
+                .on_toggle(|input| Message::Auth(AuthEvent::CredentialsInBody(input))),
+        ]
+        .spacing(default_styles::spacing());
+
+        let grant_input = pick_list(OAuth2GrantKind::ALL, Some(oauth2.grant.kind()), |kind| {
+            Message::Auth(AuthEvent::Grant(kind))
+        });
+        let grant_fields: Element<Message> = match &oauth2.grant {
+            OAuth2Grant::ClientCredentials => row![].into(),
+            OAuth2Grant::Password { username, password } => row![
+                TextInput::new("Username", username)
+                    .on_input(|input| Message::Auth(AuthEvent::Username(input))),
+                TextInput::new("Password", password)
+                    .on_input(|input| Message::Auth(AuthEvent::Password(input)))
+                    .secure(true),
+            ]
+            .spacing(default_styles::spacing())
+            .into(),
+            OAuth2Grant::RefreshToken { refresh_token } => {
+                TextInput::new("Refresh Token", refresh_token)
+                    .on_input(|input| Message::Auth(AuthEvent::RefreshToken(input)))
+                    .into()
+            }
+        };
+
+        column![
+            endpoint,
+            client,
+            row![grant_input, grant_fields].spacing(default_styles::spacing())
+        ]
+        .spacing(default_styles::spacing())
+        .into()
+    }
+}
@@ -3,6 +3,7 @@ use super::GUI;
 use crate::gui::iced::{Message, default_styles};
 use iced::widget::{Button, Row, Space, Text, column, container, radio, row, text_editor};
 use iced::{Center, Element, Length};
+use std::path::Path;
 
 impl GUI {
     pub fn view_request_body(&self) -> Element<Message> {
@@ -32,31 +33,46 @@ impl GUI {
         let empty = radio(
             "Empty",
             BodyType::Empty,
-            self.body_type_select,
+            self.tab().body_type_select,
             Message::BodyTypeChanged,
         );
 
         let text = radio(
             "Text",
             BodyType::Text,
-            self.body_type_select,
+            self.tab().body_type_select,
             Message::BodyTypeChanged,
         );
         let file = radio(
             "File",
             BodyType::File,
-            self.body_type_select,
+            self.tab().body_type_select,
+            Message::BodyTypeChanged,
+        );
+        let multipart = radio(
+            "Multipart",
+            BodyType::Multipart,
+            self.tab().body_type_select,
+            Message::BodyTypeChanged,
+        );
+
+        let form = radio(
+            "Form",
+            BodyType::Form,
+            self.tab().body_type_select,
             Message::BodyTypeChanged,
         );
 
-        row![empty, text, file].spacing(default_styles::spacing())
+        row![empty, text, file, multipart, form].spacing(default_styles::spacing())
     }
 
     fn view_request_body_content(&self) -> Row<Message> {
-        let content = match self.body_type_select {
+        let content = match self.tab().body_type_select {
             Some(BodyType::Empty) => row![],
             Some(BodyType::File) => self.view_request_body_file(),
             Some(BodyType::Text) => self.view_request_body_text(),
+            Some(BodyType::Multipart) => self.view_request_body_multipart(),
+            Some(BodyType::Form) => self.view_request_body_form(),
             None => row![],
         };
 
@@ -65,7 +81,7 @@ impl GUI {
 
     fn view_request_body_text(&self) -> Row<Message> {
         row![
-            text_editor(&self.body_content)
+            text_editor(&self.tab().body_content)
                 .on_action(Message::BodyContentChanged)
                 .placeholder("Introduce body here...")
                 .size(default_styles::input_size())
@@ -73,18 +89,13 @@ impl GUI {
     }
 
     fn view_request_body_file(&self) -> Row<Message> {
-        let file_name_string = format!(
-            "File: {}",
-            self.body_file_path
-                .as_ref()
-                .map(|path| path.to_string_lossy().to_string())
-                .unwrap_or_else(|| "No file selected".to_string())
-        );
+        let tab = self.tab();
+        let label = file_label(tab.body_file_path.as_deref(), tab.body_file_size);
 
         row![
             Self::view_request_body_text_button(),
             Space::new(default_styles::input_size(), default_styles::input_size()),
-            Text::new(file_name_string).size(default_styles::input_size())
+            Text::new(label).size(default_styles::input_size())
         ]
         .align_y(Center)
     }
@@ -95,3 +106,46 @@ impl GUI {
             .into()
     }
 }
+
+/// The picked body file and its size when it was picked.
+fn file_label(path: Option<&Path>, size: Option<u64>) -> String {
+    match (path, size) {
+        (Some(path), Some(size)) => format!("File: {} ({size} bytes)", path.to_string_lossy()),
+        (Some(path), None) => format!("File: {}", path.to_string_lossy()),
+        (None, _) => "File: No file selected".to_string(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::gui::iced::file;
+
+    #[test]
+    fn labels_the_file_with_its_size() {
+        let path = Path::new("/tmp/upload.bin");
+
+        assert_eq!(file_label(None, None), "File: No file selected");
+        assert_eq!(file_label(Some(path), None), "File: /tmp/upload.bin");
+        assert_eq!(
+            file_label(Some(path), Some(1024)),
+            "File: /tmp/upload.bin (1024 bytes)"
+        );
+    }
+
+    #[tokio::test]
+    async fn reads_the_size_without_the_content() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("upload.bin");
+        std::fs::write(&path, [0u8; 300]).unwrap();
+
+        let (picked, size) = file::file_size(&path).await.unwrap();
+        assert_eq!((picked, size), (path, 300));
+        assert!(matches!(
+            file::file_size(dir.path().join("missing")).await,
+            Err(file::FileOpenDialogError::IoError(
+                std::io::ErrorKind::NotFound
+            ))
+        ));
+    }
+}
@@ -0,0 +1,50 @@
+use super::GUI;
+use crate::core::codegen::{self, Format};
+use crate::gui::iced::{Message, default_styles};
+use iced::widget::{Button, Text, column, container, pick_list, row, scrollable};
+use iced::{Alignment, Element, Font, Length};
+
+const CODE_HEIGHT: f32 = 200.0;
+
+impl GUI {
+    pub fn view_code(&self) -> Element<Message> {
+        container(self.view_code_inner())
+            .width(Length::Fill)
+            .padding(default_styles::padding())
+            .into()
+    }
+
+    fn view_code_inner(&self) -> Element<Message> {
+        let title = Text::new("Code").size(default_styles::input_size());
+        let format_input = pick_list(
+            Format::ALL,
+            Some(self.code_format),
+            Message::CodeFormatChanged,
+        );
+        let copy_button = Button::new(Text::new("Copy")).on_press(Message::CodeCopy);
+
+        let code = container(scrollable(
+            Text::new(self.code_snippet()).font(Font::MONOSPACE),
+        ))
+        .width(Length::Fill)
+        .max_height(CODE_HEIGHT)
+        .padding(default_styles::padding())
+        .style(container::rounded_box);
+
+        column![
+            row![title, format_input, copy_button]
+                .spacing(default_styles::spacing())
+                .align_y(Alignment::Center),
+            code
+        ]
+        .spacing(default_styles::spacing())
+        .into()
+    }
+
+    /// The edited request in the selected format, with variables resolved when they all are.
+    pub fn code_snippet(&self) -> String {
+        let request = self.tab().request_spec();
+        let request = request.resolve(&self.variables()).unwrap_or(request);
+        codegen::render(&request, self.code_format)
+    }
+}
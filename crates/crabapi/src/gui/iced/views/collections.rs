@@ -0,0 +1,103 @@
+use super::{GUI, SIDEBAR_WIDTH};
+use crate::core::collections::Entry;
+use crate::gui::iced::collections::CollectionEvent;
+use crate::gui::iced::{Message, default_styles};
+use iced::widget::{Button, Column, Space, Text, TextInput, column, container, row, scrollable};
+use iced::{Element, Length};
+
+const INDENT: f32 = 16.0;
+
+impl GUI {
+    pub fn view_collections(&self) -> Element<Message> {
+        container(self.view_collections_inner())
+            .width(Length::Fixed(SIDEBAR_WIDTH))
+            .height(Length::Fill)
+            .padding(default_styles::padding())
+            .into()
+    }
+
+    fn view_collections_inner(&self) -> Element<Message> {
+        let title = Text::new("Collection").size(default_styles::input_size());
+
+        let mut rows = vec![];
+        Self::view_collections_entries(
+            &mut rows,
+            &self.collection_tree,
+            0,
+            &self.tab().collection_selected,
+        );
+
+        let name_input = TextInput::new("folder/request-name", &self.collection_name_input)
+            .on_input(|name| Message::Collection(CollectionEvent::NameChanged(name)));
+
+        let actions = column![
+            row![
+                Self::view_collections_button("Save", CollectionEvent::Save),
+                Self::view_collections_button("New Folder", CollectionEvent::NewFolder),
+                Self::view_collections_button("Refresh", CollectionEvent::Refresh),
+                Self::view_collections_button("Import...", CollectionEvent::Import),
+            ]
+            .spacing(default_styles::spacing()),
+            row![
+                Self::view_collections_button("Rename", CollectionEvent::Rename),
+                Self::view_collections_button("Duplicate", CollectionEvent::Duplicate),
+                Button::new(Text::new("Delete"))
+                    .on_press(Message::Collection(CollectionEvent::Delete))
+                    .style(iced::widget::button::danger),
+            ]
+            .spacing(default_styles::spacing()),
+        ]
+        .spacing(default_styles::spacing());
+
+        let mut content = column![title, name_input, actions].spacing(default_styles::spacing());
+        if let Some(error) = &self.collection_error {
+            content = content.push(Text::new(error).style(iced::widget::text::danger));
+        }
+        if let Some(notice) = &self.collection_notice {
+            content = content.push(Text::new(notice).style(iced::widget::text::secondary));
+        }
+
+        content
+            .push(scrollable(Column::with_children(rows)).height(Length::Fill))
+            .into()
+    }
+
+    fn view_collections_entries<'a>(
+        rows: &mut Vec<Element<'a, Message>>,
+        entries: &'a [Entry],
+        depth: usize,
+        selected: &Option<String>,
+    ) {
+        for entry in entries {
+            let is_selected = selected.as_deref() == Some(entry.path());
+            let label = match entry {
+                Entry::Folder { name, .. } => format!("▸ {name}"),
+                Entry::Request { name, .. } => name.clone(),
+            };
+
+            let button = Button::new(Text::new(label))
+                .on_press(Message::Collection(CollectionEvent::Select(
+                    entry.path().to_string(),
+                )))
+                .width(Length::Fill)
+                .style(if is_selected {
+                    iced::widget::button::primary
+                } else {
+                    iced::widget::button::text
+                });
+
+            let indent = Space::with_width(Length::Fixed(INDENT * depth as f32));
+            rows.push(row![indent, button].into());
+
+            if let Entry::Folder { children, .. } = entry {
+                Self::view_collections_entries(rows, children, depth + 1, selected);
+            }
+        }
+    }
+
+    fn view_collections_button(label: &str, event: CollectionEvent) -> Element<'_, Message> {
+        Button::new(Text::new(label))
+            .on_press(Message::Collection(event))
+            .into()
+    }
+}
@@ -0,0 +1,88 @@
+use super::GUI;
+use crate::gui::iced::environments::{EnvironmentChoice, EnvironmentEvent};
+use crate::gui::iced::{Message, TupleEvent, default_styles};
+use iced::widget::{Button, Column, Text, TextInput, column, pick_list, row};
+use iced::{Element, Length};
+
+impl GUI {
+    pub fn view_environment_picker(&self) -> Element<Message> {
+        pick_list(
+            self.environment_choices(),
+            Some(EnvironmentChoice(self.environments.active.clone())),
+            |choice| Message::Environment(EnvironmentEvent::Selected(choice)),
+        )
+        .text_size(default_styles::input_size())
+        .into()
+    }
+
+    pub fn view_environments(&self) -> Element<Message> {
+        let title = Text::new("Environment").size(default_styles::input_size());
+
+        let new_environment = row![
+            TextInput::new("New environment", &self.environment_name_input)
+                .on_input(|name| Message::Environment(EnvironmentEvent::NameChanged(name)))
+                .on_submit(Message::Environment(EnvironmentEvent::Add)),
+            Button::new(Text::new("Add")).on_press(Message::Environment(EnvironmentEvent::Add)),
+        ]
+        .spacing(default_styles::spacing());
+
+        let mut content = column![title, self.view_environment_picker(), new_environment]
+            .spacing(default_styles::spacing());
+
+        if self.environments.active.is_some() {
+            let rows = self
+                .environment_input
+                .iter()
+                .enumerate()
+                .map(|(index, variable)| Self::view_environments_row(index, variable));
+            content = content
+                .push(Column::with_children(rows).spacing(default_styles::spacing()))
+                .push(
+                    Button::new(Text::new("Add Variable")).on_press(Message::Environment(
+                        EnvironmentEvent::VariableChanged(TupleEvent::Add),
+                    )),
+                );
+        }
+
+        if let Some(error) = &self.environment_error {
+            content = content.push(Text::new(error).style(iced::widget::text::danger));
+        }
+
+        content.into()
+    }
+
+    fn view_environments_row(index: usize, variable: &(String, String)) -> Element<Message> {
+        let event = |event| Message::Environment(EnvironmentEvent::VariableChanged(event));
+        row![
+            TextInput::new("Name", &variable.0)
+                .on_input(move |key| event(TupleEvent::KeyChanged(index, key)))
+                .width(Length::FillPortion(1)),
+            TextInput::new("Value", &variable.1)
+                .on_input(move |value| event(TupleEvent::ValueChanged(index, value)))
+                .width(Length::FillPortion(1)),
+            Button::new(Text::new("X"))
+                .on_press(event(TupleEvent::Remove(index)))
+                .style(iced::widget::button::danger),
+        ]
+        .spacing(default_styles::spacing())
+        .into()
+    }
+
+    /// Warning listing the `{{variables}}` the active environment does not define.
+    pub fn view_unresolved_variables(&self) -> Option<Element<Message>> {
+        let unresolved = self.unresolved_variables();
+        if unresolved.is_empty() {
+            return None;
+        }
+
+        let names: Vec<String> = unresolved
+            .iter()
+            .map(|name| format!("{{{{{name}}}}}"))
+            .collect();
+        Some(
+            Text::new(format!("Unresolved variables: {}", names.join(", ")))
+                .style(iced::widget::text::danger)
+                .into(),
+        )
+    }
+}
@@ -0,0 +1,43 @@
+use super::GUI;
+use crate::gui::iced::{Message, TupleEvent, default_styles};
+use iced::widget::{Button, Column, Row, Text, TextInput, column, row};
+use iced::{Element, Length};
+
+impl GUI {
+    pub fn view_request_body_form(&self) -> Row<Message> {
+        let fields = self
+            .tab()
+            .form_input
+            .iter()
+            .enumerate()
+            .map(|(index, field)| Self::view_request_body_form_row(index, field));
+        let add_button = Button::new(Text::new("Add Field").size(default_styles::input_size()))
+            .on_press(Message::FormInputChanged(TupleEvent::Add));
+
+        row![
+            column![
+                Column::with_children(fields).spacing(default_styles::spacing()),
+                add_button
+            ]
+            .spacing(default_styles::spacing())
+        ]
+    }
+
+    fn view_request_body_form_row(index: usize, field: &(String, String)) -> Element<Message> {
+        row![
+            TextInput::new("Key", &field.0)
+                .on_input(move |key| Message::FormInputChanged(TupleEvent::KeyChanged(index, key)))
+                .width(Length::FillPortion(1)),
+            TextInput::new("Value", &field.1)
+                .on_input(move |value| {
+                    Message::FormInputChanged(TupleEvent::ValueChanged(index, value))
+                })
+                .width(Length::FillPortion(2)),
+            Button::new(Text::new("X"))
+                .on_press(Message::FormInputChanged(TupleEvent::Remove(index)))
+                .style(iced::widget::button::danger),
+        ]
+        .spacing(default_styles::spacing())
+        .into()
+    }
+}
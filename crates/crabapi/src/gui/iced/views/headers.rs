@@ -32,7 +32,7 @@ impl GUI {
     fn view_request_headers_column(&self) -> Element<Message> {
         let mut headers_column = iced::widget::column![];
 
-        for (i, header) in self.header_input.iter().enumerate() {
+        for (i, header) in self.tab().header_input.iter().enumerate() {
             let header_row = self.view_request_headers_column_row(i, header);
             headers_column = headers_column.push(header_row);
         }
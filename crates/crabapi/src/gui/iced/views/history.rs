@@ -0,0 +1,52 @@
+use super::{GUI, SIDEBAR_WIDTH};
+use crate::gui::iced::history::HistoryEvent;
+use crate::gui::iced::{Message, default_styles};
+use iced::widget::{Button, Column, Text, column, container, row, scrollable};
+use iced::{Element, Length};
+
+impl GUI {
+    pub fn view_history(&self) -> Element<Message> {
+        container(self.view_history_inner())
+            .width(Length::Fixed(SIDEBAR_WIDTH))
+            .height(Length::Fill)
+            .padding(default_styles::padding())
+            .into()
+    }
+
+    fn view_history_inner(&self) -> Element<Message> {
+        let title = Text::new("History").size(default_styles::input_size());
+
+        let actions = row![
+            Button::new(Text::new("Refresh")).on_press(Message::History(HistoryEvent::Refresh)),
+            Button::new(Text::new("Clear"))
+                .on_press(Message::History(HistoryEvent::Clear))
+                .style(iced::widget::button::danger),
+        ]
+        .spacing(default_styles::spacing());
+
+        let rows = self.history_entries.iter().rev().map(|entry| {
+            let label = column![
+                Text::new(format!("{} {}", entry.request.method, entry.request.url)),
+                Text::new(format!("{} · {}", entry.time_string(), entry.outcome)).size(12),
+            ];
+            Button::new(label)
+                .on_press(Message::History(HistoryEvent::Select(entry.id)))
+                .width(Length::Fill)
+                .style(if self.history_selected == Some(entry.id) {
+                    iced::widget::button::primary
+                } else {
+                    iced::widget::button::text
+                })
+                .into()
+        });
+
+        let mut content = column![title, actions].spacing(default_styles::spacing());
+        if let Some(error) = &self.history_error {
+            content = content.push(Text::new(error).style(iced::widget::text::danger));
+        }
+
+        content
+            .push(scrollable(Column::with_children(rows)).height(Length::Fill))
+            .into()
+    }
+}
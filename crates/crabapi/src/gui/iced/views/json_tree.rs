@@ -0,0 +1,165 @@
+use super::GUI;
+use crate::core::jsonpath::{JsonPath, index_location, member_location};
+use crate::gui::iced::json_tree::JsonTreeEvent;
+use crate::gui::iced::{Message, default_styles};
+use iced::widget::{Button, Column, Space, Text, TextInput, column, row, scrollable, text};
+use iced::{Center, Element, Length};
+use serde_json::Value;
+
+/// Children shown per array or object; the rest are counted.
+const MAX_CHILDREN: usize = 200;
+/// Characters of a scalar shown before it is cut.
+const MAX_VALUE_LENGTH: usize = 200;
+const INDENT: f32 = 20.0;
+
+impl GUI {
+    pub fn view_response_json_tree(&self) -> Element<'_, Message> {
+        let event = |event: JsonTreeEvent| Message::JsonTree(event);
+        let Some(json) = &self.tab().response_json else {
+            return Text::new("The body is not JSON")
+                .style(text::secondary)
+                .into();
+        };
+
+        let filter = row![
+            TextInput::new(
+                "Filter: $.items[*].id or .items[].id",
+                &self.tab().json_filter_input
+            )
+            .on_input(move |filter| event(JsonTreeEvent::FilterChanged(filter))),
+            Button::new(Text::new("Expand All"))
+                .on_press(event(JsonTreeEvent::ExpandAll))
+                .style(iced::widget::button::secondary),
+            Button::new(Text::new("Collapse All"))
+                .on_press(event(JsonTreeEvent::CollapseAll))
+                .style(iced::widget::button::secondary),
+        ]
+        .spacing(default_styles::spacing())
+        .align_y(Center);
+        let hint = Text::new("Click a key to copy its path, a value to copy the value")
+            .size(12)
+            .style(text::secondary);
+        let mut content = column![filter, hint].spacing(default_styles::spacing());
+
+        let mut rows = vec![];
+        match JsonPath::parse(&self.tab().json_filter_input) {
+            _ if self.tab().json_filter_input.trim().is_empty() => {
+                self.view_json_node(&mut rows, "$".to_string(), "$".to_string(), json, 0)
+            }
+            Ok(path) => {
+                let matches = path.locate(json);
+                if matches.is_empty() {
+                    rows.push(Text::new("No matches").style(text::secondary).into());
+                }
+                for (location, value) in matches {
+                    self.view_json_node(&mut rows, location.clone(), location, value, 0);
+                }
+            }
+            Err(error) => {
+                content = content.push(Text::new(error.to_string()).style(text::danger));
+                self.view_json_node(&mut rows, "$".to_string(), "$".to_string(), json, 0);
+            }
+        }
+        content.push(scrollable(Column::with_children(rows))).into()
+    }
+
+    /// A row for `value`, then rows for its children when it is expanded.
+    fn view_json_node(
+        &self,
+        rows: &mut Vec<Element<'_, Message>>,
+        label: String,
+        location: String,
+        value: &Value,
+        depth: usize,
+    ) {
+        let event = |event: JsonTreeEvent| Message::JsonTree(event);
+        let expanded = self.tab().json_expanded.contains(&location);
+        let (badge, summary) = match value {
+            Value::Object(fields) => (format!("object {{{}}}", fields.len()), "{…}".to_string()),
+            Value::Array(items) => (format!("array [{}]", items.len()), "[…]".to_string()),
+            Value::String(_) => ("string".to_string(), scalar(value)),
+            Value::Number(_) => ("number".to_string(), scalar(value)),
+            Value::Bool(_) => ("boolean".to_string(), scalar(value)),
+            Value::Null => ("null".to_string(), scalar(value)),
+        };
+        let has_children = match value {
+            Value::Object(fields) => !fields.is_empty(),
+            Value::Array(items) => !items.is_empty(),
+            _ => false,
+        };
+
+        let toggle: Element<Message> = match has_children {
+            true => Button::new(Text::new(if expanded { "▾" } else { "▸" }))
+                .on_press(event(JsonTreeEvent::Toggle(location.clone())))
+                .style(iced::widget::button::text)
+                .padding(0)
+                .width(INDENT)
+                .into(),
+            false => Space::with_width(INDENT).into(),
+        };
+        let mut node = row![
+            Space::with_width(INDENT * depth as f32),
+            toggle,
+            Button::new(Text::new(label))
+                .on_press(event(JsonTreeEvent::CopyPath(location.clone())))
+                .style(iced::widget::button::text)
+                .padding(0),
+            Text::new(badge).size(12).style(text::secondary),
+        ]
+        .spacing(default_styles::spacing())
+        .align_y(Center);
+        if !(has_children && expanded) {
+            node = node.push(
+                Button::new(Text::new(summary))
+                    .on_press(event(JsonTreeEvent::CopyValue(location.clone())))
+                    .style(iced::widget::button::text)
+                    .padding(0),
+            );
+        }
+        rows.push(node.width(Length::Fill).into());
+
+        if !expanded {
+            return;
+        }
+        let children: Vec<(String, String, &Value)> = match value {
+            Value::Object(fields) => fields
+                .iter()
+                .take(MAX_CHILDREN)
+                .map(|(name, field)| (name.clone(), member_location(&location, name), field))
+                .collect(),
+            Value::Array(items) => items
+                .iter()
+                .take(MAX_CHILDREN)
+                .enumerate()
+                .map(|(index, item)| (format!("[{index}]"), index_location(&location, index), item))
+                .collect(),
+            _ => vec![],
+        };
+        let total = match value {
+            Value::Object(fields) => fields.len(),
+            Value::Array(items) => items.len(),
+            _ => 0,
+        };
+        for (label, location, child) in children {
+            self.view_json_node(rows, label, location, child, depth + 1);
+        }
+        if total > MAX_CHILDREN {
+            rows.push(
+                row![
+                    Space::with_width(INDENT * (depth + 2) as f32),
+                    Text::new(format!("… {} more", total - MAX_CHILDREN)).style(text::secondary),
+                ]
+                .into(),
+            );
+        }
+    }
+}
+
+/// A scalar as JSON, cut after [`MAX_VALUE_LENGTH`] characters.
+fn scalar(value: &Value) -> String {
+    let text = value.to_string();
+    match text.char_indices().nth(MAX_VALUE_LENGTH) {
+        Some((end, _)) => format!("{}…", &text[..end]),
+        None => text,
+    }
+}
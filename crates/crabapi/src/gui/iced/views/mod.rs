@@ -1,7 +1,20 @@
 use super::GUI;
 
+mod auth;
 mod body;
+mod code;
+mod collections;
+mod environments;
+mod form;
 mod headers;
+mod history;
+mod json_tree;
+mod multipart;
 mod queries;
 mod request;
 mod response;
+mod scripts;
+mod tabs;
+
+/// Width of the collection, environment and history sidebar.
+const SIDEBAR_WIDTH: f32 = 300.0;
@@ -0,0 +1,87 @@
+use super::GUI;
+use crate::core::requests::{MultipartPart, PartValue};
+use crate::gui::iced::multipart::MultipartEvent;
+use crate::gui::iced::{Message, default_styles};
+use iced::widget::{Button, Column, Row, Text, TextInput, column, row};
+use iced::{Center, Element, Length};
+
+impl GUI {
+    pub fn view_request_body_multipart(&self) -> Row<Message> {
+        let parts = self
+            .tab()
+            .multipart_input
+            .iter()
+            .enumerate()
+            .map(|(index, part)| Self::view_request_body_multipart_row(index, part));
+        let add_button = Button::new(Text::new("Add Part").size(default_styles::input_size()))
+            .on_press(Message::Multipart(MultipartEvent::Add));
+
+        row![
+            column![
+                Column::with_children(parts).spacing(default_styles::spacing()),
+                add_button
+            ]
+            .spacing(default_styles::spacing())
+        ]
+    }
+
+    /// Name, then the text or the attached file, then the optional file name and type.
+    fn view_request_body_multipart_row(index: usize, part: &MultipartPart) -> Element<Message> {
+        let event = |event: MultipartEvent| Message::Multipart(event);
+        let name = TextInput::new("Name", &part.name)
+            .on_input(move |name| event(MultipartEvent::NameChanged(index, name)))
+            .width(Length::FillPortion(1));
+
+        let value: Element<Message> = match &part.value {
+            PartValue::Text { text } => row![
+                TextInput::new("Value", text)
+                    .on_input(move |text| event(MultipartEvent::TextChanged(index, text))),
+                Button::new(Text::new("File..."))
+                    .on_press(event(MultipartEvent::PickFile(index)))
+                    .style(iced::widget::button::secondary),
+            ]
+            .spacing(default_styles::spacing())
+            .align_y(Center)
+            .width(Length::FillPortion(2))
+            .into(),
+            PartValue::File { path } => row![
+                Button::new(Text::new(format!("@{}", path.display())))
+                    .on_press(event(MultipartEvent::PickFile(index)))
+                    .style(iced::widget::button::secondary)
+                    .width(Length::Fill),
+                Button::new(Text::new("Text"))
+                    .on_press(event(MultipartEvent::ClearFile(index)))
+                    .style(iced::widget::button::secondary),
+            ]
+            .spacing(default_styles::spacing())
+            .align_y(Center)
+            .width(Length::FillPortion(2))
+            .into(),
+        };
+
+        let filename = TextInput::new("File name", part.filename.as_deref().unwrap_or_default())
+            .on_input(move |filename| event(MultipartEvent::FilenameChanged(index, filename)))
+            .width(Length::FillPortion(1));
+        let content_type = TextInput::new(
+            "Content type",
+            part.content_type.as_deref().unwrap_or_default(),
+        )
+        .on_input(move |content_type| {
+            event(MultipartEvent::ContentTypeChanged(index, content_type))
+        })
+        .width(Length::FillPortion(1));
+
+        row![
+            name,
+            value,
+            filename,
+            content_type,
+            Button::new(Text::new("X"))
+                .on_press(event(MultipartEvent::Remove(index)))
+                .style(iced::widget::button::danger),
+        ]
+        .spacing(default_styles::spacing())
+        .align_y(Center)
+        .into()
+    }
+}
@@ -32,7 +32,7 @@ impl GUI {
     fn view_request_queries_column(&self) -> Element<Message> {
         let mut queries_column = iced::widget::column![];
 
-        for (i, query) in self.query_input.iter().enumerate() {
+        for (i, query) in self.tab().query_input.iter().enumerate() {
             let header_row = self.view_request_queries_row(i, query);
             queries_column = queries_column.push(header_row);
         }
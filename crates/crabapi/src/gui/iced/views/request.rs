@@ -1,11 +1,15 @@
 use super::GUI;
 use crate::gui::iced::{Message, default_styles};
-use iced::widget::{Button, Row, Text, TextInput, column, pick_list, row};
+use iced::widget::{Button, Row, Text, TextInput, column, container, pick_list, row};
 use iced::{Alignment, Element, Length};
 
 impl GUI {
     pub fn view_request(&self) -> Element<Message> {
-        let title_row = Self::view_request_row_setup(row![Self::view_request_title()]);
+        let title_row = Self::view_request_row_setup(row![
+            Self::view_request_title(),
+            self.view_environment_picker(),
+            Self::view_request_paste_curl_button()
+        ]);
 
         let url_input = self.view_request_url_input();
 
@@ -15,7 +19,12 @@ impl GUI {
 
         let request_row = Self::view_request_row_setup(row![method_input, url_input, send_button]);
 
-        column![title_row, request_row].into()
+        let mut request_column = column![title_row, request_row];
+        if let Some(unresolved) = self.view_unresolved_variables() {
+            request_column =
+                request_column.push(container(unresolved).padding(default_styles::padding()));
+        }
+        request_column.into()
     }
 
     fn view_request_title() -> Element<'static, Message> {
@@ -25,8 +34,8 @@ impl GUI {
     }
 
     fn view_request_url_input(&self) -> Element<Message> {
-        let url_input_icon = Self::view_request_url_input_icon(self.url_input_valid);
-        let url_input = TextInput::new("Enter URI", &self.url_input)
+        let url_input_icon = Self::view_request_url_input_icon(self.tab().url_input_valid);
+        let url_input = TextInput::new("Enter URI", &self.tab().url_input)
             .on_input(Message::UrlInputChanged)
             .size(default_styles::input_size())
             .icon(url_input_icon)
@@ -48,7 +57,7 @@ impl GUI {
     fn view_request_method_input(&self) -> Element<Message> {
         pick_list(
             self.methods,
-            self.method_selected.clone(),
+            self.tab().method_selected.clone(),
             Message::MethodChanged,
         )
         .placeholder("Method")
@@ -64,6 +73,13 @@ impl GUI {
             .align_y(Alignment::Center)
     }
 
+    fn view_request_paste_curl_button() -> Element<'static, Message> {
+        Button::new(Text::new("Paste curl"))
+            .on_press(Message::PasteCurl)
+            .style(iced::widget::button::secondary)
+            .into()
+    }
+
     fn view_request_send_button() -> Element<'static, Message> {
         Button::new(Text::new("Send").size(default_styles::input_size()))
             .on_press(Message::SendRequest)
@@ -1,7 +1,14 @@
 use super::GUI;
-use crate::gui::iced::{Message, default_styles};
-use iced::widget::{Text, column, container, scrollable, text_editor};
-use iced::{Center, Element, Length};
+use crate::core::render::BodyKind;
+use crate::core::requests::{ResponseRecord, SetCookie};
+use crate::gui::iced::openapi::SpecEvent;
+use crate::gui::iced::{Message, ResponseTab, default_styles};
+use http::StatusCode;
+use iced::widget::{
+    Button, Column, Row, Text, column, container, image, row, scrollable, text, text_editor,
+    toggler,
+};
+use iced::{Center, Color, Element, Length};
 use iced_highlighter::Highlighter;
 
 impl GUI {
@@ -15,17 +22,260 @@ impl GUI {
 
     fn view_response_inner(&self) -> Element<'_, Message> {
         let label = Text::new("Response:").size(default_styles::input_size());
-        let body = text_editor(&self.response_body)
+        let spec_button = Button::new(Text::new(match &self.spec_name {
+            Some(name) => format!("Spec: {name}"),
+            None => "Load spec...".to_string(),
+        }))
+        .on_press(Message::Spec(SpecEvent::Load))
+        .style(iced::widget::button::secondary);
+
+        let mut header = row![label]
+            .spacing(default_styles::spacing())
+            .align_y(Center);
+        if let Some(response) = &self.tab().response {
+            header = header
+                .push(Self::view_response_status(response.status))
+                .push(Text::new(Self::view_response_summary(response)));
+        }
+        if let Some(badge) = self.view_response_validation_badge() {
+            header = header.push(badge);
+        }
+        let mut content = column![header.push(spec_button)].spacing(default_styles::spacing());
+        if let Some(response) = &self.tab().response {
+            content = content.push(Text::new(response.url.to_string()).style(text::secondary));
+        }
+        if let Some(error) = &self.spec_error {
+            content = content.push(Text::new(error).style(text::danger));
+        }
+        if let Some(violations) = self.view_response_violations() {
+            content = content.push(violations);
+        }
+        content = content.push(self.view_response_tabs());
+
+        let tab = match (self.tab().response_tab, &self.tab().response) {
+            (ResponseTab::Body, _) => self.view_response_body(),
+            (ResponseTab::Headers, Some(response)) => Self::view_response_headers(response),
+            (ResponseTab::Cookies, Some(response)) => Self::view_response_cookies(response),
+            (ResponseTab::Timeline, Some(response)) => Self::view_response_timeline(response),
+            (ResponseTab::JsonTree, Some(_)) => self.view_response_json_tree(),
+            (_, None) => Text::new("No response yet").style(text::secondary).into(),
+        };
+        content.push(tab).into()
+    }
+
+    fn view_response_body(&self) -> Element<'_, Message> {
+        if let (Some(_), Some(handle), true) = (
+            &self.tab().response,
+            &self.tab().response_image,
+            self.tab().response_pretty,
+        ) {
+            return image(handle.clone()).into();
+        }
+        let syntax = self
+            .tab()
+            .response
+            .as_ref()
+            .and_then(|response| BodyKind::of(response).syntax())
+            .unwrap_or("txt");
+        let body = text_editor(&self.tab().response_body)
             .on_action(Message::ResponseBodyText)
             .highlight_with::<Highlighter>(
                 iced_highlighter::Settings {
                     theme: iced_highlighter::Theme::SolarizedDark,
-                    token: "html".to_string(),
+                    token: syntax.to_string(),
                 },
                 |highlight, _theme| highlight.to_format(),
             );
-        column![label, scrollable(body)]
+        scrollable(body).into()
+    }
+
+    /// The status line, on a background colored by status class.
+    fn view_response_status(status: StatusCode) -> Element<'static, Message> {
+        let background = match status.as_u16() {
+            100..=199 => Color::from_rgb8(0x6c, 0x75, 0x7d),
+            200..=299 => Color::from_rgb8(0x2e, 0x9d, 0x4f),
+            300..=399 => Color::from_rgb8(0x1f, 0x6f, 0xd1),
+            400..=499 => Color::from_rgb8(0xd9, 0x82, 0x1e),
+            _ => Color::from_rgb8(0xd0, 0x3b, 0x3b),
+        };
+        container(Text::new(status.to_string()).color(Color::WHITE))
+            .padding([2, 8])
+            .style(move |_theme| container::Style {
+                background: Some(background.into()),
+                border: iced::border::rounded(4),
+                ..container::Style::default()
+            })
+            .into()
+    }
+
+    fn view_response_summary(response: &ResponseRecord) -> String {
+        let mut size = format!("{} bytes", response.size());
+        if response.received_size != response.size() {
+            size.push_str(&format!(" ({} bytes compressed)", response.received_size));
+        }
+        format!(
+            "{:?} · {} ms · {size}",
+            response.version,
+            response.elapsed.as_millis()
+        )
+    }
+
+    fn view_response_tabs(&self) -> Row<'_, Message> {
+        let (headers, cookies) = self
+            .tab()
+            .response
+            .as_ref()
+            .map(|response| (response.headers.len(), response.cookies().len()))
+            .unwrap_or_default();
+        let mut tabs = vec![
+            (ResponseTab::Body, "Body".to_string()),
+            (ResponseTab::Headers, format!("Headers ({headers})")),
+            (ResponseTab::Cookies, format!("Cookies ({cookies})")),
+            (ResponseTab::Timeline, "Timeline".to_string()),
+        ];
+        if self.tab().response_json.is_some() {
+            tabs.insert(1, (ResponseTab::JsonTree, "JSON Tree".to_string()));
+        }
+        let tabs = Row::with_children(tabs.into_iter().map(|(tab, label)| {
+            Button::new(Text::new(label))
+                .on_press(Message::ResponseTabChanged(tab))
+                .style(if self.tab().response_tab == tab {
+                    iced::widget::button::primary
+                } else {
+                    iced::widget::button::secondary
+                })
+                .into()
+        }))
+        .spacing(default_styles::spacing())
+        .align_y(Center);
+        match self.tab().response_tab {
+            ResponseTab::Body => tabs.push(
+                toggler(self.tab().response_pretty)
+                    .label("Pretty")
+                    .on_toggle(Message::ResponsePrettyToggled),
+            ),
+            _ => tabs,
+        }
+    }
+
+    fn view_response_headers(response: &ResponseRecord) -> Element<'_, Message> {
+        let rows = response.header_pairs().map(|(name, value)| {
+            row![
+                Text::new(name).width(Length::FillPortion(1)),
+                Text::new(value).width(Length::FillPortion(2)),
+            ]
             .spacing(default_styles::spacing())
             .into()
+        });
+        scrollable(Column::with_children(rows)).into()
+    }
+
+    fn view_response_cookies(response: &ResponseRecord) -> Element<'_, Message> {
+        let cookies = response.cookies();
+        if cookies.is_empty() {
+            return Text::new("No cookies set").style(text::secondary).into();
+        }
+        let cells = |cells: [String; 6]| -> Element<'_, Message> {
+            Row::with_children(
+                cells
+                    .into_iter()
+                    .map(|cell| Text::new(cell).width(Length::FillPortion(1)).into()),
+            )
+            .spacing(default_styles::spacing())
+            .into()
+        };
+        let header =
+            ["Name", "Value", "Domain", "Path", "Expires", "Flags"].map(|title| title.to_string());
+        let rows = std::iter::once(cells(header)).chain(
+            cookies
+                .iter()
+                .map(|cookie| cells(Self::view_response_cookie(cookie))),
+        );
+        scrollable(Column::with_children(rows)).into()
+    }
+
+    fn view_response_cookie(cookie: &SetCookie) -> [String; 6] {
+        let expires = match (&cookie.max_age, &cookie.expires) {
+            (Some(max_age), _) => format!("in {max_age} s"),
+            (None, Some(expires)) => expires.clone(),
+            (None, None) => "Session".to_string(),
+        };
+        let mut flags = vec![];
+        if cookie.secure {
+            flags.push("Secure".to_string());
+        }
+        if cookie.http_only {
+            flags.push("HttpOnly".to_string());
+        }
+        if let Some(same_site) = &cookie.same_site {
+            flags.push(format!("SameSite={same_site}"));
+        }
+        [
+            cookie.name.clone(),
+            cookie.value.clone(),
+            cookie.domain.clone().unwrap_or_default(),
+            cookie.path.clone().unwrap_or_default(),
+            expires,
+            flags.join(", "),
+        ]
+    }
+
+    fn view_response_timeline(response: &ResponseRecord) -> Element<'_, Message> {
+        let waiting = response.waiting.as_millis();
+        let elapsed = response.elapsed.as_millis();
+        let rows = [
+            ("Request sent", "0 ms".to_string()),
+            ("Headers received", format!("{waiting} ms")),
+            (
+                "Body received",
+                format!("{elapsed} ms (download {} ms)", elapsed - waiting),
+            ),
+            ("HTTP version", format!("{:?}", response.version)),
+            ("Final URL", response.url.to_string()),
+        ]
+        .map(|(label, value)| {
+            row![
+                Text::new(label).width(Length::FillPortion(1)),
+                Text::new(value).width(Length::FillPortion(2)),
+            ]
+            .spacing(default_styles::spacing())
+            .into()
+        });
+        Column::with_children(rows).into()
+    }
+
+    /// `Contract OK`, or a button with the number of violations that shows them.
+    fn view_response_validation_badge(&self) -> Option<Element<'_, Message>> {
+        let validation = self.tab().response.as_ref()?.validation.as_ref()?;
+        if validation.is_valid() {
+            return Some(Text::new("Contract OK").style(text::success).into());
+        }
+        Some(
+            Button::new(Text::new(format!(
+                "⚠ {} contract violation(s)",
+                validation.violations.len()
+            )))
+            .on_press(Message::Spec(SpecEvent::ToggleViolations))
+            .style(iced::widget::button::danger)
+            .into(),
+        )
+    }
+
+    fn view_response_violations(&self) -> Option<Element<'_, Message>> {
+        let validation = self.tab().response.as_ref()?.validation.as_ref()?;
+        if !self.tab().violations_expanded || validation.is_valid() {
+            return None;
+        }
+        let operation = validation
+            .operation
+            .as_deref()
+            .unwrap_or("unknown operation");
+        let rows = std::iter::once(Text::new(operation).into()).chain(
+            validation
+                .violations
+                .iter()
+                .map(|violation| Text::new(violation.to_string()).style(text::danger).into()),
+        );
+        Some(Column::with_children(rows).into())
     }
 }
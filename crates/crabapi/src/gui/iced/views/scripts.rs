@@ -0,0 +1,58 @@
+use super::GUI;
+use crate::gui::iced::scripts::ScriptEvent;
+use crate::gui::iced::{Message, default_styles};
+use iced::widget::{Button, Text, column, container, row, text, text_editor};
+use iced::{Center, Element, Length};
+
+const EDITOR_HEIGHT: f32 = 100.0;
+
+impl GUI {
+    pub fn view_scripts(&self) -> Element<Message> {
+        container(self.view_scripts_inner())
+            .width(Length::Fill)
+            .padding(default_styles::padding())
+            .into()
+    }
+
+    fn view_scripts_inner(&self) -> Element<Message> {
+        let title = Text::new(match &self.tab().scripts_folder {
+            Some(folder) => format!("Scripts of folder '{folder}'"),
+            None => "Scripts (Rhai)".to_string(),
+        })
+        .size(default_styles::input_size());
+        let mut header = row![title]
+            .spacing(default_styles::spacing())
+            .align_y(Center);
+        if self.tab().scripts_folder.is_some() {
+            header = header.push(
+                Button::new(Text::new("Save Scripts"))
+                    .on_press(Message::Script(ScriptEvent::SaveFolder)),
+            );
+        }
+
+        let editors = row![
+            text_editor(&self.tab().scripts_pre_request)
+                .on_action(|action| Message::Script(ScriptEvent::PreRequestEdited(action)))
+                .placeholder("Pre-request: request.set_header(\"X-Signature\", ...);")
+                .height(EDITOR_HEIGHT)
+                .size(default_styles::input_size()),
+            text_editor(&self.tab().scripts_post_response)
+                .on_action(|action| Message::Script(ScriptEvent::PostResponseEdited(action)))
+                .placeholder("Post-response: test(\"ok\", response.status == 200);")
+                .height(EDITOR_HEIGHT)
+                .size(default_styles::input_size()),
+        ]
+        .spacing(default_styles::spacing());
+
+        let mut content = column![header, editors].spacing(default_styles::spacing());
+        if let Some(output) = &self.tab().scripts_output {
+            let style = if output.contains("FAIL") || output.starts_with("Error") {
+                text::danger
+            } else {
+                text::secondary
+            };
+            content = content.push(Text::new(output).style(style));
+        }
+        content.into()
+    }
+}
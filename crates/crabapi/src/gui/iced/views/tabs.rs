@@ -0,0 +1,74 @@
+use super::GUI;
+use crate::gui::iced::tabs::{RequestTab, TabEvent};
+use crate::gui::iced::{Message, default_styles};
+use iced::widget::{Button, Row, Text, row, scrollable};
+use iced::{Alignment, Element};
+
+/// Longest tab title shown, in characters.
+const MAX_TITLE_LENGTH: usize = 32;
+
+impl GUI {
+    pub fn view_tabs(&self) -> Element<Message> {
+        let tabs = self
+            .tabs
+            .iter()
+            .enumerate()
+            .map(|(index, tab)| self.view_tab(index, tab));
+
+        let new_button = Button::new(Text::new("+"))
+            .on_press(Message::Tab(TabEvent::New))
+            .style(iced::widget::button::secondary);
+
+        let tab_row = Row::with_children(tabs)
+            .push(new_button)
+            .spacing(default_styles::spacing())
+            .padding(default_styles::padding())
+            .align_y(Alignment::Center);
+
+        scrollable(tab_row)
+            .direction(scrollable::Direction::Horizontal(
+                scrollable::Scrollbar::default(),
+            ))
+            .into()
+    }
+
+    fn view_tab<'a>(&'a self, index: usize, tab: &'a RequestTab) -> Element<'a, Message> {
+        let mut title = tab.title();
+        if title.chars().count() > MAX_TITLE_LENGTH {
+            title = title.chars().take(MAX_TITLE_LENGTH - 1).collect::<String>() + "…";
+        }
+        if tab.dirty() {
+            title.push_str(" •");
+        }
+        if tab.in_flight.is_some() {
+            title.push_str(" …");
+        }
+
+        let active = index == self.tab_active;
+        let select = Button::new(Text::new(title))
+            .on_press(Message::Tab(TabEvent::Select(index)))
+            .style(if active {
+                iced::widget::button::primary
+            } else {
+                iced::widget::button::secondary
+            });
+
+        let action = |label: &'a str, event: TabEvent| {
+            Button::new(Text::new(label).size(12))
+                .on_press(Message::Tab(event))
+                .style(iced::widget::button::text)
+        };
+
+        let mut tab_row = row![select];
+        if active {
+            tab_row = tab_row
+                .push(action("<", TabEvent::MoveLeft(index)))
+                .push(action(">", TabEvent::MoveRight(index)))
+                .push(action("Duplicate", TabEvent::Duplicate(Some(index))));
+        }
+        tab_row
+            .push(action("x", TabEvent::Close(Some(index))))
+            .align_y(Alignment::Center)
+            .into()
+    }
+}
@@ -1,6 +1,13 @@
 use crabapi::cli::Cli;
+use std::process::ExitCode;
 
 #[tokio::main]
-async fn main() -> Result<(), Box<dyn std::error::Error>> {
-    Cli::new().run().await
+async fn main() -> ExitCode {
+    match Cli::new().run().await {
+        Ok(()) => ExitCode::SUCCESS,
+        Err(error) => {
+            eprintln!("Error: {error}");
+            ExitCode::FAILURE
+        }
+    }
 }